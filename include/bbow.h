@@ -0,0 +1,38 @@
+/* C interface to the bbow Big Bag Of Words library.
+ *
+ * Build the shared library with `cargo build --release
+ * --features ffi` and link against it. Strings are
+ * NUL-terminated UTF-8 and are copied by each call.
+ */
+
+#ifndef BBOW_H
+#define BBOW_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* An opaque bag of words. */
+typedef struct bbow bbow_t;
+
+/* Make a new empty bag, to be released with bbow_free(). */
+bbow_t *bbow_new(void);
+
+/* Add the words of text to bbow. Returns 0 on success, or -1
+ * if either pointer is null or text is not UTF-8. */
+int bbow_extend_text(bbow_t *bbow, const char *text);
+
+/* The number of occurrences of keyword in bbow, or 0 if
+ * either pointer is null or keyword is not UTF-8. */
+size_t bbow_match_count(const bbow_t *bbow, const char *keyword);
+
+/* Release bbow. Null is ignored. */
+void bbow_free(bbow_t *bbow);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* BBOW_H */
@@ -0,0 +1,202 @@
+//! Set operations on BBOWs, treating a BBOW as a multiset of
+//! words.
+
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use crate::{entry_for, Bbow};
+
+impl<'a> Bbow<'a> {
+    /// The words of either this BBOW or `other`, each with
+    /// the larger of its two counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("a a b");
+    /// let b = Bbow::new().extend_from_text("a b b c");
+    /// let union = a.union(&b);
+    /// assert_eq!((2, 2, 1), (union.match_count("a"), union.match_count("b"), union.match_count("c")));
+    /// ```
+    pub fn union(&self, other: &Bbow<'a>) -> Bbow<'a> {
+        let mut union = self.clone();
+        for (word, &count) in &other.counts {
+            let entry = entry_for(&mut union.counts, word);
+            *entry = (*entry).max(count);
+        }
+        union
+    }
+
+    /// The words of both this BBOW and `other`, each with
+    /// the smaller of its two counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("a a b");
+    /// let b = Bbow::new().extend_from_text("a b b c");
+    /// let both = a.intersection(&b);
+    /// assert_eq!(2, both.len());
+    /// assert_eq!((1, 1), (both.match_count("a"), both.match_count("b")));
+    /// ```
+    pub fn intersection(&self, other: &Bbow<'a>) -> Bbow<'a> {
+        let mut intersection = self.options.build();
+        for (word, &count) in &self.counts {
+            if let Some(&other_count) = other.counts.get(word) {
+                intersection
+                    .counts
+                    .insert(word.clone(), count.min(other_count));
+            }
+        }
+        intersection
+    }
+
+    /// The words of this BBOW that are not in `other`, with
+    /// their counts in this BBOW. The counts in `other` do
+    /// not matter, so a BBOW of stop words can be used to
+    /// remove them whatever their counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let text = Bbow::new().extend_from_text("the cat and the hat");
+    /// let stop = Bbow::new().extend_from_text("the and");
+    /// let content = text.difference(&stop);
+    /// assert_eq!(vec!["cat", "hat"], content.words().collect::<Vec<_>>());
+    /// ```
+    pub fn difference(&self, other: &Bbow<'a>) -> Bbow<'a> {
+        let mut difference = self.options.build();
+        for (word, &count) in &self.counts {
+            if !other.counts.contains_key(word) {
+                difference.counts.insert(word.clone(), count);
+            }
+        }
+        difference
+    }
+}
+
+/// The sum of two BBOWs, as by [`Bbow::merge`].
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::Bbow;
+/// let sum = Bbow::new().extend_from_text("a b") + Bbow::new().extend_from_text("b c");
+/// assert_eq!(2, sum.match_count("b"));
+/// ```
+impl<'a> Add for Bbow<'a> {
+    type Output = Bbow<'a>;
+
+    fn add(mut self, other: Bbow<'a>) -> Bbow<'a> {
+        self += &other;
+        self
+    }
+}
+
+impl<'a> AddAssign<&Bbow<'a>> for Bbow<'a> {
+    fn add_assign(&mut self, other: &Bbow<'a>) {
+        self.merge(other);
+    }
+}
+
+impl<'a> AddAssign for Bbow<'a> {
+    fn add_assign(&mut self, other: Bbow<'a>) {
+        self.merge(&other);
+    }
+}
+
+/// The difference of two BBOWs: each word's count is
+/// reduced by its count in the right-hand BBOW, as by
+/// [`Bbow::decrement`]. Counts stop at zero, and words whose
+/// counts reach zero are removed.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::Bbow;
+/// let text = Bbow::new().extend_from_text("the cat and the hat");
+/// let rest = text - Bbow::new().extend_from_text("the the the and");
+/// assert_eq!(vec!["cat", "hat"], rest.words().collect::<Vec<_>>());
+/// ```
+impl<'a> Sub for Bbow<'a> {
+    type Output = Bbow<'a>;
+
+    fn sub(mut self, other: Bbow<'a>) -> Bbow<'a> {
+        self -= &other;
+        self
+    }
+}
+
+impl SubAssign<&Bbow<'_>> for Bbow<'_> {
+    fn sub_assign(&mut self, other: &Bbow<'_>) {
+        for (word, &count) in &other.counts {
+            self.decrement(word, count);
+        }
+    }
+}
+
+impl SubAssign for Bbow<'_> {
+    fn sub_assign(&mut self, other: Self) {
+        *self -= &other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries<'a>(bbow: &'a Bbow<'a>) -> Vec<(&'a str, usize)> {
+        bbow.words().map(|w| (w, bbow.match_count(w))).collect()
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = Bbow::new().extend_from_text("x x x y");
+        let b = Bbow::new().extend_from_text("y y z");
+        assert_eq!(vec![("x", 3), ("y", 2), ("z", 1)], entries(&a.union(&b)));
+        assert_eq!(vec![("y", 1)], entries(&a.intersection(&b)));
+        assert_eq!(vec![("x", 3)], entries(&a.difference(&b)));
+        assert_eq!(vec![("z", 1)], entries(&b.difference(&a)));
+    }
+
+    #[test]
+    fn test_set_operations_empty() {
+        let a = Bbow::new().extend_from_text("x y");
+        let empty = Bbow::new();
+        assert_eq!(entries(&a), entries(&a.union(&empty)));
+        assert!(a.intersection(&empty).is_empty());
+        assert_eq!(entries(&a), entries(&a.difference(&empty)));
+        assert!(empty.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_set_operations_keep_options() {
+        let builder = Bbow::builder().numbers(true);
+        let a = builder.build().extend_from_text("2024 2024 1999 x");
+        let b = builder.build().extend_from_text("2024 x");
+        assert_eq!(1, a.intersection(&b).match_count("2024"));
+        assert_eq!(1, a.difference(&b).match_count("1999"));
+        assert_eq!(2, a.union(&b).match_count("2024"));
+    }
+
+    #[test]
+    fn test_operators() {
+        let a = Bbow::new().extend_from_text("x x x y");
+        let b = Bbow::new().extend_from_text("y y z");
+        assert_eq!(
+            vec![("x", 3), ("y", 3), ("z", 1)],
+            entries(&(a.clone() + b.clone()))
+        );
+        assert_eq!(vec![("x", 3)], entries(&(a.clone() - b.clone())));
+        assert_eq!(vec![("y", 1), ("z", 1)], entries(&(b.clone() - a.clone())));
+        let mut total = Bbow::new();
+        total += &a;
+        total += a.clone();
+        total -= &b;
+        assert_eq!(vec![("x", 6)], entries(&total));
+        total -= total.clone();
+        assert!(total.is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+//! Apache Arrow export of BBOWs and corpora, for handing
+//! counts to analytics engines.
+
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+
+use crate::{Bbow, Corpus};
+
+fn word_field() -> Field {
+    Field::new("word", DataType::Utf8, false)
+}
+
+fn count_field() -> Field {
+    Field::new("count", DataType::UInt64, false)
+}
+
+impl Bbow<'_> {
+    /// This BBOW as an Arrow record batch with a `word`
+    /// column and a `count` column, one row per word in
+    /// alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let batch = Bbow::new().extend_from_text("b a b").to_record_batch().unwrap();
+    /// assert_eq!(2, batch.num_rows());
+    /// assert_eq!("count", batch.schema().field(1).name());
+    /// ```
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let schema = Schema::new(vec![word_field(), count_field()]);
+        let words = StringArray::from_iter_values(self.counts.keys());
+        let counts = UInt64Array::from_iter_values(self.counts.values().map(|&c| c as u64));
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(words) as ArrayRef, Arc::new(counts)],
+        )
+    }
+}
+
+impl Corpus<'_> {
+    /// The document-term data of this corpus as an Arrow
+    /// record batch with `doc_id`, `word` and `count`
+    /// columns: one row for each word of each document, in
+    /// document order and then alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Corpus;
+    /// let mut corpus = Corpus::new();
+    /// corpus.add_text("a", "red fish");
+    /// corpus.add_text("b", "blue fish fish");
+    /// let batch = corpus.to_record_batch().unwrap();
+    /// assert_eq!(4, batch.num_rows());
+    /// ```
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let schema = Schema::new(vec![
+            Field::new("doc_id", DataType::UInt64, false),
+            word_field(),
+            count_field(),
+        ]);
+        let entries = || {
+            self.iter()
+                .enumerate()
+                .flat_map(|(id, doc)| doc.bbow().counts.iter().map(move |(w, &c)| (id, w, c)))
+        };
+        let ids = UInt64Array::from_iter_values(entries().map(|(id, _, _)| id as u64));
+        let words = StringArray::from_iter_values(entries().map(|(_, w, _)| w));
+        let counts = UInt64Array::from_iter_values(entries().map(|(_, _, c)| c as u64));
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(ids) as ArrayRef, Arc::new(words), Arc::new(counts)],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Array;
+
+    #[test]
+    fn test_bbow_record_batch() {
+        let batch = Bbow::new()
+            .extend_from_text("the cat the hat")
+            .to_record_batch()
+            .unwrap();
+        let words = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let counts = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(
+            vec!["cat", "hat", "the"],
+            words.iter().flatten().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![1, 1, 2], counts.values().to_vec());
+        assert_eq!(0, words.null_count());
+    }
+
+    #[test]
+    fn test_corpus_record_batch() {
+        let mut corpus = Corpus::new();
+        corpus.add_text("a", "x y");
+        corpus.add_text("empty", "");
+        corpus.add_text("c", "y y");
+        let batch = corpus.to_record_batch().unwrap();
+        let ids = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        let words = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let counts = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(vec![0, 0, 2], ids.values().to_vec());
+        assert_eq!(
+            vec!["x", "y", "y"],
+            words.iter().flatten().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![1, 1, 2], counts.values().to_vec());
+    }
+
+    #[test]
+    fn test_empty_record_batch() {
+        assert_eq!(0, Bbow::new().to_record_batch().unwrap().num_rows());
+        assert_eq!(0, Corpus::new().to_record_batch().unwrap().num_rows());
+    }
+}
@@ -0,0 +1,153 @@
+//! Fast paths for ASCII text.
+//!
+//! Most English text is entirely ASCII, and checking its
+//! bytes is much cheaper than decoding and classifying each
+//! character by the Unicode tables. Each function here gives
+//! the same answer as its Unicode counterpart.
+
+use std::borrow::Cow;
+
+/// Is `b` an ASCII byte that [`char::is_whitespace`] accepts?
+/// Unlike [`u8::is_ascii_whitespace`], this includes the
+/// vertical tab.
+fn is_space(b: u8) -> bool {
+    b.is_ascii_whitespace() || b == b'\x0b'
+}
+
+/// The pieces of `target` between runs of whitespace, as
+/// [`str::split_whitespace`] gives them. `ascii` says
+/// whether `target` is known to be ASCII.
+pub(crate) fn split_whitespace(target: &str, ascii: bool) -> SplitWhitespace<'_> {
+    SplitWhitespace {
+        rest: target,
+        ascii,
+    }
+}
+
+/// Iterator from [`split_whitespace`]: scans bytes when the
+/// text is ASCII, and characters otherwise.
+pub(crate) struct SplitWhitespace<'t> {
+    rest: &'t str,
+    ascii: bool,
+}
+
+impl<'t> Iterator for SplitWhitespace<'t> {
+    type Item = &'t str;
+
+    fn next(&mut self) -> Option<&'t str> {
+        if !self.ascii {
+            let rest = self.rest.trim_start();
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let (piece, rest) = rest.split_at(end);
+            self.rest = rest;
+            return (!piece.is_empty()).then_some(piece);
+        }
+        let bytes = self.rest.as_bytes();
+        let start = bytes.iter().position(|&b| !is_space(b))?;
+        let end = bytes[start..]
+            .iter()
+            .position(|&b| is_space(b))
+            .map_or(bytes.len(), |n| start + n);
+        let piece = &self.rest[start..end];
+        self.rest = &self.rest[end..];
+        Some(piece)
+    }
+}
+
+/// Are all the characters of the ASCII `word` letters?
+pub(crate) fn is_alphabetic(word: &str) -> bool {
+    word.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+/// The ASCII `word` with leading and trailing non-letters
+/// removed.
+pub(crate) fn trim_non_letters(word: &str) -> &str {
+    word.trim_matches(|c: char| !c.is_ascii_alphabetic())
+}
+
+/// The ASCII `word` in lowercase, copied only if needed.
+pub(crate) fn lowercase(word: &str) -> Cow<'_, str> {
+    if word.bytes().any(|b| b.is_ascii_uppercase()) {
+        Cow::Owned(word.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(word)
+    }
+}
+
+/// The word in the ASCII `token`, lowercased, if there is
+/// one, scanning the token once: the same as trimming it
+/// with [`trim_non_letters`], checking it with
+/// [`is_alphabetic`] and lowercasing it with [`lowercase`].
+pub(crate) fn scan_word(token: &str) -> Option<Cow<'_, str>> {
+    let bytes = token.as_bytes();
+    let start = bytes.iter().position(u8::is_ascii_alphabetic)?;
+    let end = bytes.iter().rposition(u8::is_ascii_alphabetic)? + 1;
+    let mut upper = false;
+    for b in &bytes[start..end] {
+        if !b.is_ascii_alphabetic() {
+            return None;
+        }
+        upper |= b.is_ascii_uppercase();
+    }
+    let word = &token[start..end];
+    Some(if upper {
+        Cow::Owned(word.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(word)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_whitespace() {
+        for text in [
+            "",
+            "   ",
+            "one",
+            " two  words ",
+            "tab\tnew\nline\r\nvt\x0bff\x0cend",
+            "non\u{a0}breaking em\u{2003}space ünïcode",
+        ] {
+            assert_eq!(
+                text.split_whitespace().collect::<Vec<_>>(),
+                split_whitespace(text, text.is_ascii()).collect::<Vec<_>>(),
+                "{text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_letters() {
+        assert!(is_alphabetic("Word"));
+        assert!(!is_alphabetic("can't"));
+        assert!(!is_alphabetic("x1"));
+        assert_eq!("quoted", trim_non_letters("\"quoted\","));
+        assert_eq!("", trim_non_letters("1234"));
+        assert_eq!(Cow::Borrowed("low"), lowercase("low"));
+        assert_eq!("mixed", lowercase("MiXeD"));
+    }
+
+    #[test]
+    fn test_scan_word() {
+        for token in [
+            "",
+            "!!",
+            "Word",
+            "\"quoted\",",
+            "can't",
+            "x1",
+            "1x",
+            "--a--",
+            "A-b",
+            "end.",
+        ] {
+            let word = trim_non_letters(token);
+            let expected = (!word.is_empty() && is_alphabetic(word)).then(|| lowercase(word));
+            assert_eq!(expected, scan_word(token), "{token:?}");
+        }
+        assert!(matches!(scan_word("(low)"), Some(Cow::Borrowed("low"))));
+    }
+}
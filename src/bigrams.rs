@@ -0,0 +1,141 @@
+//! Counts of pairs of adjacent words.
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+use crate::intern::Word;
+use crate::{entry_for, key_for, words_of, Bbow};
+
+/// Counts of how often each word is immediately followed by
+/// each other word in some texts: a bag of bigrams.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::Bigrams;
+/// let bigrams = Bigrams::new().extend_from_text("the cat and the hat and the cat");
+/// assert_eq!(2, bigrams.count("the", "cat"));
+/// assert_eq!(0, bigrams.count("cat", "the"));
+/// assert_eq!(vec!["cat", "hat"], bigrams.followers("the").unwrap().words().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Bigrams<'a> {
+    words: Bbow<'a>,
+    /// For each word, the BBOW of the words that follow it.
+    following: BTreeMap<Word<'a>, Bbow<'a>>,
+}
+
+impl<'a> Bigrams<'a> {
+    /// Make a new empty bag of bigrams.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the `target` text and add its bigrams to this
+    /// bag. The last word of one text and the first of the
+    /// next do not make a bigram.
+    ///
+    /// This is a "builder method", like
+    /// [`Bbow::extend_from_text`].
+    pub fn extend_from_text(mut self, target: &'a str) -> Self {
+        let mut prior: Option<Word<'a>> = None;
+        for word in words_of(target) {
+            let word = key_for(&self.words.counts, word);
+            if let Some(prior) = prior {
+                let followers = self.following.entry(prior).or_default();
+                *entry_for(&mut followers.counts, &word) += 1;
+            }
+            *entry_for(&mut self.words.counts, &word) += 1;
+            prior = Some(word);
+        }
+        self
+    }
+
+    /// The BBOW of all the words seen by this bag.
+    pub fn words(&self) -> &Bbow<'a> {
+        &self.words
+    }
+
+    /// Number of times word `b` immediately follows word
+    /// `a`.
+    pub fn count(&self, a: &str, b: &str) -> usize {
+        self.following
+            .get(a)
+            .map_or(0, |followers| followers.match_count(b))
+    }
+
+    /// The BBOW of the words that immediately follow `word`,
+    /// or `None` if nothing does.
+    pub fn followers(&self, word: &str) -> Option<&Bbow<'a>> {
+        self.following.get(word)
+    }
+
+    /// Random text of `length` words made by a Markov chain
+    /// on the bigrams of this bag, starting with
+    /// `seed_word`. Each next word follows the one before it
+    /// with probability proportional to their bigram count.
+    /// When no word has been seen following the current one,
+    /// the next word is drawn from all the words of the bag
+    /// in proportion to their counts instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bigrams;
+    /// let bigrams = Bigrams::new().extend_from_text("one fish two fish red fish blue fish");
+    /// let text = bigrams.generate(&mut rand::rng(), 5, "red");
+    /// assert!(text.starts_with("red fish "));
+    /// assert_eq!(5, text.split(' ').count());
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R, length: usize, seed_word: &str) -> String {
+        let mut text: Vec<&str> = Vec::with_capacity(length);
+        let mut current = seed_word;
+        while text.len() < length {
+            text.push(current);
+            let next = self
+                .following
+                .get(current)
+                .and_then(|followers| followers.sample(rng))
+                .or_else(|| self.words.sample(rng));
+            let Some(next) = next else {
+                break;
+            };
+            current = next;
+        }
+        text.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bigram_counts() {
+        let bigrams = Bigrams::new()
+            .extend_from_text("A b, a b.")
+            .extend_from_text("c a");
+        assert_eq!(2, bigrams.count("a", "b"));
+        assert_eq!(1, bigrams.count("b", "a"));
+        assert_eq!(0, bigrams.count("b", "c"));
+        assert_eq!(1, bigrams.count("c", "a"));
+        assert_eq!(6, bigrams.words().count());
+        assert!(bigrams.followers("zebra").is_none());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_generate() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(0);
+        let bigrams = Bigrams::new().extend_from_text("a b c");
+        assert_eq!("a b c", bigrams.generate(&mut rng, 3, "a"));
+        // After "c" the chain restarts from any word.
+        assert_eq!(10, bigrams.generate(&mut rng, 10, "b").split(' ').count());
+        assert_eq!("", bigrams.generate(&mut rng, 0, "a"));
+        assert_eq!("x", Bigrams::new().generate(&mut rng, 3, "x"));
+    }
+}
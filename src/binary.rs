@@ -0,0 +1,315 @@
+//! Compact binary serialization of BBOWs.
+//!
+//! The format is the magic bytes `BBOW`, a format version
+//! byte, the options of the BBOW, and the number of words,
+//! followed by the words in sorted order. Each word is
+//! stored as the length of the prefix it shares with the
+//! previous word, the length of the rest of the word, the
+//! rest of the word in UTF-8, and its count. All numbers are
+//! unsigned LEB128 varints.
+
+use std::io::{self, BufReader, Read, Write};
+
+use crate::{Bbow, BbowBuilder};
+
+const MAGIC: &[u8; 4] = b"BBOW";
+const VERSION: u8 = 1;
+
+pub(crate) fn write_varint<W: Write>(w: &mut W, mut n: u64) -> io::Result<()> {
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            return w.write_all(&[byte]);
+        }
+        w.write_all(&[byte | 0x80])?;
+    }
+}
+
+fn read_varint<R: Read>(r: &mut R) -> io::Result<u64> {
+    let mut n = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        r.read_exact(&mut byte)?;
+        let bits = u64::from(byte[0] & 0x7f);
+        if shift == 63 && bits > 1 {
+            break;
+        }
+        n |= bits << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(n);
+        }
+    }
+    Err(invalid("varint too long"))
+}
+
+pub(crate) fn read_usize<R: Read>(r: &mut R) -> io::Result<usize> {
+    usize::try_from(read_varint(r)?).map_err(|_| invalid("number too large"))
+}
+
+pub(crate) fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+/// Length of the common prefix of `a` and `b`, in bytes.
+fn common_prefix(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+/// Write the length of `s`, then `s` in UTF-8.
+pub(crate) fn write_str<W: Write>(w: &mut W, s: &str) -> io::Result<()> {
+    write_varint(w, s.len() as u64)?;
+    w.write_all(s.as_bytes())
+}
+
+/// Read a string written by [`write_str`].
+pub(crate) fn read_str<R: Read>(r: &mut R) -> io::Result<String> {
+    let len = read_usize(r)?;
+    let mut bytes = Vec::new();
+    r.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    String::from_utf8(bytes).map_err(|_| invalid("word is not UTF-8"))
+}
+
+/// Write the `magic` bytes and the format `version`.
+pub(crate) fn write_header<W: Write>(w: &mut W, magic: &[u8; 4], version: u8) -> io::Result<()> {
+    w.write_all(magic)?;
+    w.write_all(&[version])
+}
+
+/// Read the header written by [`write_header`], failing with
+/// `not_magic` if the magic bytes are not `magic`, and with
+/// `not_version` if the format version is not `version`.
+pub(crate) fn read_header<R: Read>(
+    r: &mut R,
+    magic: &[u8; 4],
+    not_magic: &str,
+    version: u8,
+    not_version: &str,
+) -> io::Result<()> {
+    let mut header = [0; 5];
+    r.read_exact(&mut header)?;
+    if &header[..4] != magic {
+        return Err(invalid(not_magic));
+    }
+    if header[4] != version {
+        return Err(invalid(not_version));
+    }
+    Ok(())
+}
+
+/// Write the number `n` of entries, then the `entries`
+/// themselves, which must be in alphabetical order, with
+/// each word stored as the part not shared with the
+/// previous word.
+pub(crate) fn write_entries<'w, W: Write>(
+    w: &mut W,
+    n: usize,
+    entries: impl Iterator<Item = (&'w str, usize)>,
+) -> io::Result<()> {
+    write_varint(w, n as u64)?;
+    let mut previous: &[u8] = &[];
+    for (word, count) in entries {
+        let word = word.as_bytes();
+        let shared = common_prefix(previous, word);
+        write_varint(w, shared as u64)?;
+        write_varint(w, (word.len() - shared) as u64)?;
+        w.write_all(&word[shared..])?;
+        write_varint(w, count as u64)?;
+        previous = word;
+    }
+    Ok(())
+}
+
+/// Read the entries written by [`write_entries`], passing
+/// each word and count to `f`, which may reject them.
+pub(crate) fn read_entries<R: Read>(
+    r: &mut R,
+    mut f: impl FnMut(String, usize) -> io::Result<()>,
+) -> io::Result<()> {
+    let n = read_usize(r)?;
+    let mut previous = String::new();
+    for i in 0..n {
+        let shared = read_usize(r)?;
+        if shared > previous.len() || !previous.is_char_boundary(shared) {
+            return Err(invalid("bad shared prefix length"));
+        }
+        let rest = read_str(r)?;
+        let word = format!("{}{rest}", &previous[..shared]);
+        if i > 0 && word <= previous {
+            return Err(invalid("words out of order"));
+        }
+        let count = read_usize(r)?;
+        f(word.clone(), count)?;
+        previous = word;
+    }
+    Ok(())
+}
+
+impl Bbow<'_> {
+    /// Write this BBOW to `w` in a compact binary format
+    /// that [`Bbow::read_binary`] can load much faster than
+    /// the original text can be parsed again. Its options
+    /// are written too, except for its token filters, which
+    /// are code rather than data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("banana band bandana");
+    /// let mut bytes = Vec::new();
+    /// bbow.write_binary(&mut bytes).unwrap();
+    /// let read = Bbow::read_binary(bytes.as_slice()).unwrap();
+    /// assert_eq!(3, read.len());
+    /// assert_eq!(1, read.match_count("bandana"));
+    ///
+    /// let bbow = Bbow::builder().numbers(true).build().extend_from_text("in 2024");
+    /// let mut bytes = Vec::new();
+    /// bbow.write_binary(&mut bytes).unwrap();
+    /// let read = Bbow::read_binary(bytes.as_slice()).unwrap();
+    /// assert_eq!(1, read.match_count("2024"));
+    /// ```
+    pub fn write_binary<W: Write>(&self, w: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(w);
+        write_header(&mut w, MAGIC, VERSION)?;
+        self.options.write_options(&mut w)?;
+        write_entries(&mut w, self.len(), self.iter())?;
+        w.flush()
+    }
+
+    /// Read a BBOW written by [`Bbow::write_binary`] from
+    /// `r`, with the options it was written with. Data that
+    /// is not in that format, or a count of 0, is an
+    /// [`io::ErrorKind::InvalidData`] error.
+    pub fn read_binary<R: Read>(r: R) -> io::Result<Bbow<'static>> {
+        let mut r = BufReader::new(r);
+        read_header(
+            &mut r,
+            MAGIC,
+            "not a binary BBOW",
+            VERSION,
+            "unsupported binary BBOW version",
+        )?;
+        let mut bbow = BbowBuilder::read_options(&mut r)?.build();
+        read_entries(&mut r, |word, count| {
+            if count == 0 {
+                return Err(invalid("zero count"));
+            }
+            bbow.insert(word, count);
+            Ok(())
+        })?;
+        Ok(bbow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varint_round_trip() {
+        for n in [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut bytes = Vec::new();
+            write_varint(&mut bytes, n).unwrap();
+            assert_eq!(n, read_varint(&mut bytes.as_slice()).unwrap());
+        }
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, 300).unwrap();
+        assert_eq!(vec![0xac, 0x02], bytes);
+        assert!(read_varint(&mut [0xff; 11].as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let bbow = Bbow::new().extend_from_text("It ain't over untïl it ain't, över ötto over.");
+        let mut bytes = Vec::new();
+        bbow.write_binary(&mut bytes).unwrap();
+        let read = Bbow::read_binary(bytes.as_slice()).unwrap();
+        assert_eq!(bbow.counts, read.counts);
+    }
+
+    #[test]
+    fn test_binary_prefix_compression() {
+        let bbow = Bbow::new().extend_from_text("interest interesting interests");
+        let mut bytes = Vec::new();
+        bbow.write_binary(&mut bytes).unwrap();
+        // Header, default options, entry count, then
+        // "interest" in full and only the suffixes "ing" and
+        // "s" of the others.
+        assert_eq!(
+            5 + 8 + 1 + (2 + 8 + 1) + (2 + 3 + 1) + (2 + 1 + 1),
+            bytes.len()
+        );
+    }
+
+    #[test]
+    fn test_binary_round_trip_options() {
+        let builder = Bbow::builder()
+            .numbers(true)
+            .case_sensitive(true)
+            .apostrophes(crate::Apostrophes::Keep)
+            .hyphens(crate::Hyphens::Both)
+            .inner_chars(['.'])
+            .min_word_len(2)
+            .stopwords(crate::StopWords::new().with_words(["the"]));
+        let bbow = builder
+            .build()
+            .extend_from_text("The U.S. in 2024: ain't it well-known? X");
+        let mut bytes = Vec::new();
+        bbow.write_binary(&mut bytes).unwrap();
+        let read = Bbow::read_binary(bytes.as_slice()).unwrap();
+        assert_eq!(bbow, read);
+        for word in ["U.S.", "2024", "ain't", "well-known", "known"] {
+            assert_eq!(1, read.match_count(word), "{word}");
+        }
+        let read = read.extend_from_text("The 2024 X well-known");
+        assert_eq!(2, read.match_count("2024"));
+        assert_eq!(0, read.match_count("The"));
+        assert_eq!(0, read.match_count("X"));
+        assert_eq!(2, read.match_count("well"));
+    }
+
+    #[test]
+    fn test_binary_round_trip_foreign_words() {
+        let mut bbow = Bbow::new().extend_from_text("a cat");
+        bbow.insert("Hello", 2);
+        bbow.merge(
+            &Bbow::builder()
+                .numbers(true)
+                .build()
+                .extend_from_text("2024"),
+        );
+        let mut bytes = Vec::new();
+        bbow.write_binary(&mut bytes).unwrap();
+        let read = Bbow::read_binary(bytes.as_slice()).unwrap();
+        assert_eq!(bbow, read);
+        assert!(read.contains("Hello") && read.contains("2024"));
+    }
+
+    #[test]
+    fn test_read_binary_errors() {
+        let mut options = Vec::new();
+        BbowBuilder::new().write_options(&mut options).unwrap();
+        let file = |header: &[u8], entries: &[u8]| [header, &options, entries].concat();
+        let bad = [
+            file(b"BBOX\x01", b"\x00"),
+            file(b"BBOW\x00", b"\x00"),
+            file(b"BBOW\x02", b"\x00"),
+            b"BBOW\x01\x00".to_vec(),
+            file(b"BBOW\x01", b"\x01\x00\x05ab"),
+            file(b"BBOW\x01", b"\x02\x00\x01b\x01\x00\x01a\x01"),
+            file(b"BBOW\x01", b"\x01\x00\x02\xff\xfe\x01"),
+            file(b"BBOW\x01", b"\x01\x00\x01a\x00"),
+        ];
+        for bytes in bad {
+            assert!(Bbow::read_binary(bytes.as_slice()).is_err(), "{bytes:?}");
+        }
+        let err = Bbow::read_binary(file(b"BBOW\x02", b"\x00").as_slice()).unwrap_err();
+        assert_eq!("unsupported binary BBOW version", err.to_string());
+        let empty = file(b"BBOW\x01", b"\x00");
+        assert!(Bbow::read_binary(empty.as_slice()).unwrap().is_empty());
+    }
+}
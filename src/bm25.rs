@@ -0,0 +1,149 @@
+//! Okapi BM25 ranking of the documents of a corpus against
+//! a query, as search engines do.
+
+use std::collections::BTreeMap;
+
+use crate::{Corpus, InvertedIndex};
+
+/// The usual BM25 term frequency saturation.
+const K1: f64 = 1.2;
+/// The usual BM25 document length normalization.
+const B: f64 = 0.75;
+
+impl Corpus<'_> {
+    /// The documents of this corpus that contain any of
+    /// `query_words`, as `(document index, score)` pairs,
+    /// best match first, ranked by BM25 with the usual
+    /// parameters `k1 = 1.2` and `b = 0.75`. Query words
+    /// must be given as they are stored, as for
+    /// [`crate::Bbow::match_count`]. This makes an
+    /// [`InvertedIndex`] of the whole corpus first: to run
+    /// many queries, make one with [`Corpus::inverted_index`]
+    /// and use [`InvertedIndex::bm25`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Corpus;
+    /// let mut corpus = Corpus::new();
+    /// corpus.add_text("a", "The quick brown fox.");
+    /// corpus.add_text("b", "The lazy dog sleeps all day.");
+    /// corpus.add_text("c", "A fox and a dog.");
+    /// let ranked = corpus.bm25(&["fox", "dog"]);
+    /// assert_eq!(2, ranked[0].0);
+    /// assert_eq!(3, ranked.len());
+    /// assert!(corpus.bm25(&["cat"]).is_empty());
+    /// ```
+    pub fn bm25(&self, query_words: &[&str]) -> Vec<(usize, f64)> {
+        self.inverted_index().bm25(query_words)
+    }
+
+    /// As [`Corpus::bm25`], with term frequency saturation
+    /// `k1` and document length normalization `b`, which is
+    /// from 0 (none) to 1 (full).
+    pub fn bm25_with(&self, query_words: &[&str], k1: f64, b: f64) -> Vec<(usize, f64)> {
+        self.inverted_index().bm25_with(query_words, k1, b)
+    }
+}
+
+impl InvertedIndex<'_> {
+    /// As [`Corpus::bm25`] for the indexed corpus. Only the
+    /// postings of the query words are read, so the time
+    /// taken is in proportion to the number of documents
+    /// containing them rather than to the size of the
+    /// corpus.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Corpus;
+    /// let mut corpus = Corpus::new();
+    /// corpus.add_text("a", "The quick brown fox.");
+    /// corpus.add_text("b", "The lazy dog sleeps all day.");
+    /// let index = corpus.inverted_index();
+    /// assert_eq!(vec![0], index.bm25(&["fox"]).iter().map(|&(id, _)| id).collect::<Vec<_>>());
+    /// assert_eq!(1, index.bm25(&["dog"])[0].0);
+    /// ```
+    pub fn bm25(&self, query_words: &[&str]) -> Vec<(usize, f64)> {
+        self.bm25_with(query_words, K1, B)
+    }
+
+    /// As [`Corpus::bm25_with`] for the indexed corpus.
+    pub fn bm25_with(&self, query_words: &[&str], k1: f64, b: f64) -> Vec<(usize, f64)> {
+        let n = self.lengths.len() as f64;
+        let average = if self.lengths.is_empty() {
+            0.0
+        } else {
+            self.total_length as f64 / n
+        };
+        let mut scores: BTreeMap<usize, f64> = BTreeMap::new();
+        for word in query_words {
+            let postings = self.postings(word);
+            if postings.is_empty() {
+                continue;
+            }
+            let df = postings.len() as f64;
+            // Never negative, unlike the original BM25 idf,
+            // so that words in most documents still count.
+            let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+            for &(id, count) in postings {
+                let tf = count as f64;
+                let norm = if average > 0.0 {
+                    1.0 - b + b * self.lengths[id] as f64 / average
+                } else {
+                    1.0
+                };
+                *scores.entry(id).or_default() += idf * tf * (k1 + 1.0) / (tf + k1 * norm);
+            }
+        }
+        let mut ranked: Vec<(usize, f64)> = scores.into_iter().collect();
+        ranked.sort_by(|(i1, s1), (i2, s2)| s2.total_cmp(s1).then(i1.cmp(i2)));
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bm25() {
+        let mut corpus = Corpus::new();
+        corpus.add_text("short", "cat");
+        corpus.add_text("long", "cat dog dog dog dog dog dog dog");
+        corpus.add_text("none", "");
+        let ranked = corpus.bm25(&["cat"]);
+        assert_eq!(
+            vec![0, 1],
+            ranked.iter().map(|&(id, _)| id).collect::<Vec<_>>()
+        );
+        // With both documents containing "cat", idf is
+        // ln((3 - 2 + 0.5) / (2 + 0.5) + 1) = ln 1.6, and the
+        // one-word document is much shorter than average.
+        let norm = 1.0 - B + B * 1.0 / 3.0;
+        let expected = 1.6f64.ln() * (K1 + 1.0) / (1.0 + K1 * norm);
+        assert!((ranked[0].1 - expected).abs() < 1e-12);
+        // Without length normalization, equal counts score
+        // the same.
+        let flat = corpus.bm25_with(&["cat"], K1, 0.0);
+        assert_eq!(flat[0].1, flat[1].1);
+        assert_eq!(1, corpus.bm25(&["dog", "cat"])[0].0);
+        assert!(Corpus::new().bm25(&["cat"]).is_empty());
+        assert!(corpus.bm25(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_bm25_index() {
+        let mut corpus = Corpus::new();
+        corpus.add_text("a", "red fish blue fish");
+        corpus.add_text("b", "one fish two fish red fish");
+        corpus.add_text("c", "no such words here at all");
+        let index = corpus.inverted_index();
+        assert_eq!(vec![4, 6, 6], index.lengths);
+        assert_eq!(16, index.total_length);
+        for query in [&["fish"][..], &["red", "blue"], &["fish", "fish"], &["cat"]] {
+            assert_eq!(corpus.bm25(query), index.bm25(query), "{query:?}");
+        }
+        assert!(Corpus::new().inverted_index().bm25(&["fish"]).is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+//! A BBOW with a limit on how many words it holds, for
+//! counting endless streams in bounded memory.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{is_word, words_of, Bbow};
+
+/// Which word a [`BoundedBbow`] forgets to make room for a
+/// new one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Eviction {
+    /// The word with the lowest count, or of those the one
+    /// least recently added.
+    #[default]
+    LowestCount,
+    /// The word least recently added.
+    LeastRecent,
+}
+
+/// A BBOW holding at most a given number of distinct
+/// words. When a new word arrives at a full BBOW, one word
+/// is forgotten, as chosen by the [`Eviction`] policy, so
+/// counts are exact only for words never forgotten. With
+/// the default policy the frequent words, which are usually
+/// the ones of interest, stay.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::BoundedBbow;
+/// let bbow = BoundedBbow::with_max_words(2).extend_from_text("a a b c a d");
+/// assert_eq!(2, bbow.len());
+/// assert_eq!(3, bbow.match_count("a"));
+/// assert_eq!(1, bbow.match_count("d"));
+/// assert_eq!(0, bbow.match_count("b"));
+/// assert_eq!(2, bbow.evicted());
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoundedBbow<'a> {
+    max_words: usize,
+    eviction: Eviction,
+    /// How many words have been added, as a clock.
+    tick: u64,
+    /// Each word's count and the tick it was last added.
+    words: BTreeMap<Cow<'a, str>, (usize, u64)>,
+    /// The words in the order they are to be evicted.
+    order: BTreeSet<(u64, u64, Cow<'a, str>)>,
+    evicted: usize,
+}
+
+impl<'a> BoundedBbow<'a> {
+    /// Make a new empty BBOW that holds at most `max_words`
+    /// distinct words, at least one, evicting the word with
+    /// the lowest count.
+    pub fn with_max_words(max_words: usize) -> Self {
+        Self {
+            max_words: max_words.max(1),
+            eviction: Eviction::LowestCount,
+            tick: 0,
+            words: BTreeMap::new(),
+            order: BTreeSet::new(),
+            evicted: 0,
+        }
+    }
+
+    /// Use the `eviction` policy when this BBOW is full.
+    pub fn with_eviction(mut self, eviction: Eviction) -> Self {
+        self.eviction = eviction;
+        let entries: Vec<_> = std::mem::take(&mut self.order).into_iter().collect();
+        for (_, _, word) in entries {
+            let key = self.key(word);
+            self.order.insert(key);
+        }
+        self
+    }
+
+    /// The key of `word` in `order`.
+    fn key(&self, word: Cow<'a, str>) -> (u64, u64, Cow<'a, str>) {
+        let (count, tick) = self.words[&word];
+        match self.eviction {
+            Eviction::LowestCount => (count as u64, tick, word),
+            Eviction::LeastRecent => (tick, 0, word),
+        }
+    }
+
+    /// Add one occurrence of `word`, evicting another word
+    /// first if need be.
+    fn add(&mut self, word: Cow<'a, str>) {
+        self.tick += 1;
+        if self.words.contains_key(&word) {
+            let old = self.key(word.clone());
+            self.order.remove(&old);
+        } else if self.words.len() >= self.max_words {
+            if let Some((_, _, victim)) = self.order.pop_first() {
+                self.words.remove(&victim);
+                self.evicted += 1;
+            }
+        }
+        let entry = self.words.entry(word.clone()).or_insert((0, 0));
+        *entry = (entry.0 + 1, self.tick);
+        let key = self.key(word);
+        self.order.insert(key);
+    }
+
+    /// Parse the `target` text and add the sequence of
+    /// valid words contained in it to this BBOW, as
+    /// [`Bbow::extend_from_text`] does.
+    ///
+    /// This is a "builder method", like
+    /// [`Bbow::extend_from_text`].
+    pub fn extend_from_text(mut self, target: &'a str) -> Self {
+        for word in words_of(target) {
+            self.add(word);
+        }
+        self
+    }
+
+    /// Report the number of occurrences of the given
+    /// `keyword` counted since it last entered this BBOW,
+    /// as [`Bbow::match_count`] does.
+    pub fn match_count(&self, keyword: &str) -> usize {
+        if !is_word(keyword) {
+            return 0;
+        }
+        self.words.get(keyword).map_or(0, |&(count, _)| count)
+    }
+
+    /// The number of times a word has been evicted.
+    pub fn evicted(&self) -> usize {
+        self.evicted
+    }
+
+    /// The most distinct words this BBOW holds.
+    pub fn max_words(&self) -> usize {
+        self.max_words
+    }
+
+    /// Count the number of unique words contained in this
+    /// BBOW.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Is this BBOW empty?
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// An ordinary BBOW of the words now held, with their
+    /// counts.
+    pub fn to_bbow(&self) -> Bbow<'a> {
+        let mut bbow = Bbow::new();
+        for (word, &(count, _)) in &self.words {
+            bbow.insert(word.clone(), count);
+        }
+        bbow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lowest_count() {
+        let bbow = BoundedBbow::with_max_words(3).extend_from_text("a a a b b c d e");
+        // "c" and then "d" are evicted as the least recent
+        // of the words counted once.
+        assert_eq!(
+            vec!["a", "b", "e"],
+            bbow.to_bbow().words().collect::<Vec<_>>()
+        );
+        assert_eq!(2, bbow.evicted());
+        assert_eq!(3, bbow.max_words());
+    }
+
+    #[test]
+    fn test_least_recent() {
+        let bbow = BoundedBbow::with_max_words(2)
+            .with_eviction(Eviction::LeastRecent)
+            .extend_from_text("a a a b a c");
+        assert_eq!(vec!["a", "c"], bbow.to_bbow().words().collect::<Vec<_>>());
+        let bbow = bbow
+            .with_eviction(Eviction::LowestCount)
+            .extend_from_text("d");
+        assert_eq!(vec!["a", "d"], bbow.to_bbow().words().collect::<Vec<_>>());
+        assert_eq!(4, bbow.match_count("a"));
+        let empty = BoundedBbow::with_max_words(0);
+        assert!(empty.is_empty());
+        assert_eq!(1, empty.extend_from_text("x y").len());
+    }
+}
@@ -0,0 +1,1069 @@
+//! Configuration of how a BBOW splits text into words.
+
+use std::borrow::Cow;
+use std::io::{self, Read, Write};
+use std::ops::Range;
+use std::sync::Arc;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::{is_nfc_quick, is_nfkc_quick, IsNormalized, UnicodeNormalization};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::binary::{invalid, read_str, read_usize, write_str, write_varint};
+use crate::symbols::{is_symbol, split_symbols};
+use crate::{lowercase, Bbow, StopWords, TokenFilter};
+
+/// How text is split into candidate words. See
+/// [`BbowBuilder::segmentation`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Segmentation {
+    /// Split at whitespace, then trim leading and trailing
+    /// punctuation: the BBOW rules.
+    #[default]
+    Whitespace,
+    /// Split at the word boundaries of [Unicode Standard
+    /// Annex #29](https://www.unicode.org/reports/tr29/), so
+    /// that words joined by dashes, slashes or other
+    /// punctuation without spaces are still separated.
+    UnicodeWords,
+}
+
+/// What to do with words containing apostrophes, such as
+/// "ain't". See [`BbowBuilder::apostrophes`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Apostrophes {
+    /// Drop the word, as the BBOW rules do.
+    #[default]
+    Drop,
+    /// Count it as one word. Typographic apostrophes (’)
+    /// are replaced by straight ones, so "ain’t" and "ain't"
+    /// are the same word.
+    Keep,
+    /// Count the parts on either side of each apostrophe as
+    /// separate words: "ain" and "t".
+    Split,
+}
+
+/// What to do with hyphenated words, such as
+/// "state-of-the-art". See [`BbowBuilder::hyphens`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Hyphens {
+    /// Drop the word, as the BBOW rules do.
+    #[default]
+    Drop,
+    /// Count it as one word.
+    Keep,
+    /// Count the parts between the hyphens as separate
+    /// words: "state", "of", "the" and "art".
+    Split,
+    /// Count both the whole word and its parts.
+    Both,
+}
+
+/// A Unicode normalization form to put words in before
+/// counting them, so that different encodings of the same
+/// word count as one. See [`BbowBuilder::normalization`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    /// Canonical composition: "e" followed by a combining
+    /// acute accent becomes "é".
+    Nfc,
+    /// Compatibility composition: as [`Normalization::Nfc`],
+    /// but compatibility characters such as the ligature
+    /// "ﬁ" are also replaced by their plain equivalents.
+    Nfkc,
+}
+
+impl Normalization {
+    fn normalizer(self) -> fn(&str) -> Cow<'_, str> {
+        match self {
+            Normalization::Nfc => nfc,
+            Normalization::Nfkc => nfkc,
+        }
+    }
+}
+
+fn nfc(word: &str) -> Cow<'_, str> {
+    match is_nfc_quick(word.chars()) {
+        IsNormalized::Yes => Cow::Borrowed(word),
+        _ => Cow::Owned(word.nfc().collect()),
+    }
+}
+
+fn nfkc(word: &str) -> Cow<'_, str> {
+    match is_nfkc_quick(word.chars()) {
+        IsNormalized::Yes => Cow::Borrowed(word),
+        _ => Cow::Owned(word.nfkc().collect()),
+    }
+}
+
+/// `word` without diacritical marks: each letter is
+/// decomposed and its combining marks are dropped.
+fn fold_accents(word: &str) -> Cow<'_, str> {
+    if word.is_ascii() {
+        return Cow::Borrowed(word);
+    }
+    let folded: String = word
+        .nfd()
+        .filter(|&c| !is_combining_mark(c))
+        .nfc()
+        .collect();
+    if folded == word {
+        Cow::Borrowed(word)
+    } else {
+        Cow::Owned(folded)
+    }
+}
+
+fn is_hyphen(c: char) -> bool {
+    c == '-' || c == '\u{2010}' || c == '\u{2011}'
+}
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '\u{2019}'
+}
+
+/// `word` with typographic apostrophes made straight.
+fn straighten(word: &str) -> Cow<'_, str> {
+    if word.contains('\u{2019}') {
+        Cow::Owned(word.replace('\u{2019}', "'"))
+    } else {
+        Cow::Borrowed(word)
+    }
+}
+
+/// The parts of `word` between the characters that are
+/// `sep`, still borrowing from the original text if `word`
+/// does.
+fn split_cow(word: Cow<'_, str>, sep: fn(char) -> bool) -> Vec<Cow<'_, str>> {
+    match word {
+        Cow::Borrowed(word) => word.split(sep).map(Cow::Borrowed).collect(),
+        Cow::Owned(word) => word.split(sep).map(|w| Cow::Owned(w.to_string())).collect(),
+    }
+}
+
+/// The part of `word` in `range`, still borrowing from the
+/// original text if `word` does.
+fn slice_cow(word: Cow<'_, str>, range: Range<usize>) -> Cow<'_, str> {
+    match word {
+        Cow::Borrowed(word) => Cow::Borrowed(&word[range]),
+        Cow::Owned(word) if range == (0..word.len()) => Cow::Owned(word),
+        Cow::Owned(word) => Cow::Owned(word[range].to_string()),
+    }
+}
+
+/// Apply `f` to `word`, still borrowing from the original
+/// text if neither `word` nor `f` had to make a copy.
+pub(crate) fn map_cow<'t>(word: Cow<'t, str>, f: fn(&str) -> Cow<'_, str>) -> Cow<'t, str> {
+    match word {
+        Cow::Borrowed(word) => f(word),
+        Cow::Owned(word) => match f(&word) {
+            Cow::Borrowed(w) if w.len() == word.len() => Cow::Owned(word),
+            w => Cow::Owned(w.into_owned()),
+        },
+    }
+}
+
+/// Options for turning text into words, for making a
+/// [`Bbow`] that counts words differently from the default
+/// one given by [`Bbow::new`]. A BBOW keeps the options it
+/// was built with and applies them to all the text later
+/// added to it, so there is no need for variants of
+/// [`Bbow::extend_from_text`] for each way of counting.
+///
+/// The default options are those of [`Bbow::new`]: words
+/// are lowercased, and no words are left out.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::{Bbow, StopWords};
+/// let bbow = Bbow::builder()
+///     .min_word_len(3)
+///     .stopwords(StopWords::english())
+///     .build()
+///     .extend_from_text("It is a cat on the mat.");
+/// assert_eq!(vec!["cat", "mat"], bbow.words().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct BbowBuilder {
+    min_word_len: usize,
+    case_sensitive: bool,
+    stopwords: StopWords,
+    normalization: Option<Normalization>,
+    fold_accents: bool,
+    segmentation: Segmentation,
+    #[cfg(feature = "cjk")]
+    cjk_bigrams: bool,
+    symbols: bool,
+    numbers: bool,
+    apostrophes: Apostrophes,
+    hyphens: Hyphens,
+    inner_chars: Vec<char>,
+    filters: Vec<Arc<dyn TokenFilter>>,
+    pub(crate) track_variants: bool,
+    pub(crate) track_positions: bool,
+}
+
+impl BbowBuilder {
+    /// Make a builder with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ignore words shorter than `len` characters.
+    pub fn min_word_len(mut self, len: usize) -> Self {
+        self.min_word_len = len;
+        self
+    }
+
+    /// Count words as they are written if `case_sensitive`,
+    /// rather than lowercasing them, so that "Apple" and
+    /// "apple" are different words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::builder()
+    ///     .case_sensitive(true)
+    ///     .build()
+    ///     .extend_from_text("Apple sells no apple");
+    /// assert_eq!((1, 1), (bbow.match_count("Apple"), bbow.match_count("apple")));
+    /// ```
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Leave the words in `stopwords` out.
+    pub fn stopwords(mut self, stopwords: StopWords) -> Self {
+        self.stopwords = stopwords;
+        self
+    }
+
+    /// Put each word in the Unicode normalization `form`
+    /// before counting it, so that, for example, "café" with
+    /// a precomposed "é" and "café" with a combining accent
+    /// are the same word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::{Bbow, Normalization};
+    /// let bbow = Bbow::builder()
+    ///     .normalization(Normalization::Nfc)
+    ///     .build()
+    ///     .extend_from_text("caf\u{e9} cafe\u{301}");
+    /// assert_eq!(2, bbow.match_count("caf\u{e9}"));
+    /// ```
+    pub fn normalization(mut self, form: Normalization) -> Self {
+        self.normalization = Some(form);
+        self
+    }
+
+    /// Strip accents and other diacritical marks from words
+    /// if `fold`, so that "untïl" is counted as "until".
+    /// Letters that are not a base letter and a mark, such
+    /// as "ø" or "ß", are left alone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::builder()
+    ///     .fold_accents(true)
+    ///     .build()
+    ///     .extend_from_text("Naïve café, naive cafe");
+    /// assert_eq!(vec![("cafe", 2), ("naive", 2)], bbow.top_n(10));
+    /// ```
+    pub fn fold_accents(mut self, fold: bool) -> Self {
+        self.fold_accents = fold;
+        self
+    }
+
+    /// Split text into words as given by `segmentation`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::{Bbow, Segmentation};
+    /// let text = "black—and—white/grey";
+    /// let bbow = Bbow::new().extend_from_text(text);
+    /// assert!(bbow.is_empty());
+    /// let bbow = Bbow::builder()
+    ///     .segmentation(Segmentation::UnicodeWords)
+    ///     .build()
+    ///     .extend_from_text(text);
+    /// assert_eq!(vec!["and", "black", "grey", "white"], bbow.words().collect::<Vec<_>>());
+    /// ```
+    pub fn segmentation(mut self, segmentation: Segmentation) -> Self {
+        self.segmentation = segmentation;
+        self
+    }
+
+    /// Count runs of Chinese and Japanese characters, which
+    /// are written without spaces, as their overlapping
+    /// character bigrams if `bigrams`, rather than as one
+    /// long word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::builder()
+    ///     .cjk_bigrams(true)
+    ///     .build()
+    ///     .extend_from_text("東京都と京都");
+    /// assert_eq!(2, bbow.match_count("京都"));
+    /// assert_eq!(1, bbow.match_count("東京"));
+    /// ```
+    #[cfg(feature = "cjk")]
+    pub fn cjk_bigrams(mut self, bigrams: bool) -> Self {
+        self.cjk_bigrams = bigrams;
+        self
+    }
+
+    /// Count emoji and other pictographic symbols as tokens
+    /// if `symbols`, rather than ignoring them as
+    /// punctuation. Each grapheme cluster is one token, so
+    /// skin-tone modifiers and joined emoji stay together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::builder()
+    ///     .symbols(true)
+    ///     .build()
+    ///     .extend_from_text("I love it 😂😂 so much 😂 🎉party🎉");
+    /// assert_eq!(3, bbow.match_count("😂"));
+    /// assert_eq!(2, bbow.match_count("🎉"));
+    /// assert_eq!(1, bbow.match_count("party"));
+    /// ```
+    pub fn symbols(mut self, symbols: bool) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Allow digits in words if `numbers`, so that years
+    /// and model numbers such as "2024" or "covid19" are
+    /// counted rather than dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::builder()
+    ///     .numbers(true)
+    ///     .build()
+    ///     .extend_from_text("In 2024, COVID19 cases fell.");
+    /// assert_eq!(1, bbow.match_count("2024"));
+    /// assert_eq!(1, bbow.match_count("covid19"));
+    /// ```
+    pub fn numbers(mut self, numbers: bool) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    /// Handle words with apostrophes inside them, such as
+    /// contractions and possessives, as given by
+    /// `apostrophes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::{Apostrophes, Bbow};
+    /// let text = "It ain't over untïl it ain’t, over.";
+    /// let bbow = Bbow::builder()
+    ///     .apostrophes(Apostrophes::Keep)
+    ///     .build()
+    ///     .extend_from_text(text);
+    /// assert_eq!(2, bbow.match_count("ain't"));
+    /// let bbow = Bbow::builder()
+    ///     .apostrophes(Apostrophes::Split)
+    ///     .build()
+    ///     .extend_from_text(text);
+    /// assert_eq!((2, 2), (bbow.match_count("ain"), bbow.match_count("t")));
+    /// ```
+    pub fn apostrophes(mut self, apostrophes: Apostrophes) -> Self {
+        self.apostrophes = apostrophes;
+        self
+    }
+
+    /// Handle hyphenated words as given by `hyphens`. A
+    /// word is only hyphenated if there is a word on both
+    /// sides of each hyphen, so a double hyphen used as a
+    /// dash does not join words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::{Bbow, Hyphens};
+    /// let bbow = Bbow::builder()
+    ///     .hyphens(Hyphens::Both)
+    ///     .build()
+    ///     .extend_from_text("A state-of-the-art state.");
+    /// assert_eq!(1, bbow.match_count("state-of-the-art"));
+    /// assert_eq!(2, bbow.match_count("state"));
+    /// ```
+    pub fn hyphens(mut self, hyphens: Hyphens) -> Self {
+        self.hyphens = hyphens;
+        self
+    }
+
+    /// Allow the characters of `chars` inside words as well
+    /// as letters, for example "." for abbreviations such as
+    /// "U.S." or "/" for "and/or". Words still start and end
+    /// with a letter, except for a final character of
+    /// `chars` that also occurs earlier in the word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::builder()
+    ///     .inner_chars(['.', '/'])
+    ///     .build()
+    ///     .extend_from_text("The U.S. and/or Canada, e.g. Ontario.");
+    /// let words: Vec<&str> = bbow.words().collect();
+    /// assert_eq!(vec!["and/or", "canada", "e.g.", "ontario", "the", "u.s."], words);
+    /// ```
+    pub fn inner_chars<I: IntoIterator<Item = char>>(mut self, chars: I) -> Self {
+        self.inner_chars = chars.into_iter().collect();
+        self
+    }
+
+    /// Pass each word through `filter` before counting it,
+    /// after all the other options, stop words included,
+    /// and after the filters already added. Filters compose
+    /// into a pipeline, one step each.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::{Bbow, Lowercase, MinLen, Stemmer};
+    /// let bbow = Bbow::builder()
+    ///     .case_sensitive(true)
+    ///     .filter(Lowercase)
+    ///     .filter(Stemmer::english())
+    ///     .filter(MinLen(3))
+    ///     .build()
+    ///     .extend_from_text("Fishing boats fished as the fisher fishes");
+    /// assert_eq!(3, bbow.match_count("fish"));
+    /// assert_eq!(0, bbow.match_count("as"));
+    /// ```
+    pub fn filter<F: TokenFilter + 'static>(mut self, filter: F) -> Self {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    /// Record the forms in which each word was written if
+    /// `track`, for [`Bbow::variants`].
+    pub fn track_variants(mut self, track: bool) -> Self {
+        self.track_variants = track;
+        self
+    }
+
+    /// Record the position of each occurrence of each word
+    /// if `track`, for [`Bbow::positions`].
+    pub fn track_positions(mut self, track: bool) -> Self {
+        self.track_positions = track;
+        self
+    }
+
+    /// Make a new empty BBOW with these options.
+    pub fn build<'a>(&self) -> Bbow<'a> {
+        Bbow {
+            counts: Default::default(),
+            options: self.clone(),
+            tracking: Default::default(),
+        }
+    }
+
+    /// The sequence of words in `target` under these
+    /// options.
+    pub(crate) fn words<'t>(
+        &self,
+        target: &'t str,
+    ) -> impl Iterator<Item = Cow<'t, str>> + use<'_, 't> {
+        // With the BBOW rules for finding words, each token
+        // can be trimmed, checked and lowercased in one pass.
+        let plain = self.has_plain_words();
+        let fast = plain
+            .then(|| crate::words_of(target).filter_map(|word| self.accept(word)))
+            .into_iter()
+            .flatten();
+        let general = (!plain)
+            .then(|| {
+                self.tokens(target)
+                    .flat_map(|token| self.token_words(token))
+            })
+            .into_iter()
+            .flatten();
+        fast.chain(general)
+    }
+
+    /// Are words found by the BBOW rules under these
+    /// options, so that only which words are kept, and how
+    /// they are filtered, can differ from [`Bbow::new`]?
+    fn has_plain_words(&self) -> bool {
+        #[cfg(feature = "cjk")]
+        if self.cjk_bigrams {
+            return false;
+        }
+        !self.case_sensitive
+            && self.normalization.is_none()
+            && !self.fold_accents
+            && self.segmentation == Segmentation::Whitespace
+            && !self.symbols
+            && !self.numbers
+            && self.apostrophes == Apostrophes::Drop
+            && self.hyphens == Hyphens::Drop
+            && self.inner_chars.is_empty()
+    }
+
+    /// The words in `token`, one of the candidate words of
+    /// some text.
+    pub(crate) fn token_words<'t>(
+        &self,
+        token: &'t str,
+    ) -> impl Iterator<Item = Cow<'t, str>> + use<'_, 't> {
+        let (word, rest) = self.split_token(token);
+        word.into_iter()
+            .chain(rest)
+            .filter_map(|word| self.finish(word))
+    }
+
+    /// The sequence of words in `target` under these
+    /// options, each with the form in which it was written.
+    pub(crate) fn words_with_forms<'t>(
+        &self,
+        target: &'t str,
+    ) -> impl Iterator<Item = (Cow<'t, str>, Cow<'t, str>)> + use<'_, 't> {
+        self.tokens(target).flat_map(|token| {
+            let (word, rest) = self.split_token(token);
+            word.into_iter().chain(rest).filter_map(|word| {
+                let form = word.clone();
+                Some((form, self.finish(word)?))
+            })
+        })
+    }
+
+    /// The pieces of `token` that may be words, normalized
+    /// and trimmed but not yet checked. Usually there is
+    /// just the first piece, and the rest are empty.
+    fn split_token<'t>(&self, token: &'t str) -> (Option<Cow<'t, str>>, Vec<Cow<'t, str>>) {
+        if self.symbols && is_symbol(token) {
+            return (Some(Cow::Borrowed(token)), Vec::new());
+        }
+        let mut word = Cow::Borrowed(token);
+        if let Some(form) = self.normalization {
+            word = map_cow(word, form.normalizer());
+        }
+        let trim = |word: Cow<'t, str>| {
+            let range = self.word_range(&word);
+            slice_cow(word, range)
+        };
+        let word = trim(word);
+        let parts = |word| split_cow(word, is_hyphen).into_iter().map(trim).collect();
+        let (first, rest) = if word.contains(is_hyphen) {
+            match self.hyphens {
+                Hyphens::Drop | Hyphens::Keep => (Some(word), Vec::new()),
+                Hyphens::Split => (None, parts(word)),
+                Hyphens::Both => (Some(word.clone()), parts(word)),
+            }
+        } else {
+            (Some(word), Vec::new())
+        };
+        match self.apostrophes {
+            Apostrophes::Drop => (first, rest),
+            Apostrophes::Keep => (
+                first.map(|word| map_cow(word, straighten)),
+                rest.into_iter()
+                    .map(|word| map_cow(word, straighten))
+                    .collect(),
+            ),
+            Apostrophes::Split if !first.iter().chain(&rest).any(|w| w.contains(is_apostrophe)) => {
+                (first, rest)
+            }
+            Apostrophes::Split => {
+                let words = first.into_iter().chain(rest);
+                (
+                    None,
+                    words
+                        .flat_map(|word| split_cow(word, is_apostrophe))
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    /// `word` as it is to be counted, if it is to be
+    /// counted at all.
+    fn finish<'t>(&self, word: Cow<'t, str>) -> Option<Cow<'t, str>> {
+        if self.symbols && is_symbol(&word) {
+            return self.keep(&word).then_some(word);
+        }
+        if !self.is_word(&word) {
+            return None;
+        }
+        let word = if self.case_sensitive {
+            word
+        } else {
+            map_cow(word, lowercase)
+        };
+        let word = if self.fold_accents {
+            map_cow(word, fold_accents)
+        } else {
+            word
+        };
+        self.accept(word)
+    }
+
+    /// `word`, found and put in its counted form, if it is
+    /// kept and passes the filters.
+    fn accept<'t>(&self, word: Cow<'t, str>) -> Option<Cow<'t, str>> {
+        if !self.keep(&word) {
+            return None;
+        }
+        self.filters
+            .iter()
+            .try_fold(word, |word, filter| filter.apply(word))
+    }
+
+    /// The candidate words of `target`, before trimming and
+    /// checking.
+    pub(crate) fn tokens<'t>(&self, target: &'t str) -> Box<dyn Iterator<Item = &'t str> + 't> {
+        let mut tokens: Box<dyn Iterator<Item = &'t str> + 't> = match self.segmentation {
+            Segmentation::Whitespace => {
+                Box::new(crate::ascii::split_whitespace(target, target.is_ascii()))
+            }
+            Segmentation::UnicodeWords if self.symbols => Box::new(
+                target
+                    .split_word_bounds()
+                    .filter(|token| !token.trim().is_empty()),
+            ),
+            Segmentation::UnicodeWords => Box::new(target.unicode_words()),
+        };
+        if self.symbols {
+            tokens = Box::new(tokens.flat_map(split_symbols));
+        }
+        #[cfg(feature = "cjk")]
+        if self.cjk_bigrams {
+            return Box::new(tokens.flat_map(crate::cjk::cjk_bigrams));
+        }
+        tokens
+    }
+
+    /// Can `c` be part of a word under these options?
+    fn is_word_char(&self, c: char) -> bool {
+        c.is_alphabetic() || (self.numbers && c.is_numeric())
+    }
+
+    /// The range of `word` left when leading and trailing
+    /// punctuation are removed. A trailing inner character
+    /// is kept if it also occurs inside the word, as the
+    /// last period of "U.S." does.
+    fn word_range(&self, word: &str) -> Range<usize> {
+        let start = word.len() - word.trim_start_matches(|c| !self.is_word_char(c)).len();
+        let mut end = word.trim_end_matches(|c| !self.is_word_char(c)).len();
+        if end <= start {
+            return start..start;
+        }
+        if let Some(c) = word[end..].chars().next() {
+            if self.inner_chars.contains(&c) && word[start..end].contains(c) {
+                end += c.len_utf8();
+            }
+        }
+        start..end
+    }
+
+    /// Is `word` a word under these options?
+    fn is_word(&self, word: &str) -> bool {
+        let hyphens = matches!(self.hyphens, Hyphens::Keep | Hyphens::Both);
+        if hyphens && word.contains(is_hyphen) {
+            return word.split(is_hyphen).all(|part| self.is_word(part));
+        }
+        let inner = |c| {
+            (self.apostrophes == Apostrophes::Keep && is_apostrophe(c))
+                || self.inner_chars.contains(&c)
+        };
+        !word.is_empty() && word.chars().all(|c| self.is_word_char(c) || inner(c))
+    }
+
+    /// Could `word` be a key of a BBOW with these options?
+    pub(crate) fn is_token(&self, word: &str) -> bool {
+        self.is_word(word) || (self.symbols && is_symbol(word))
+    }
+
+    /// Should `word` be counted under these options?
+    fn keep(&self, word: &str) -> bool {
+        if self.min_word_len > 0 && word.chars().count() < self.min_word_len {
+            return false;
+        }
+        self.stopwords.is_empty() || !self.stopwords.contains(word)
+    }
+
+    /// Write these options to `w` for [`Bbow::write_binary`]:
+    /// a varint of flags, the minimum word length, a byte
+    /// each for the normalization form, segmentation,
+    /// apostrophes and hyphens, the inner characters as a
+    /// string, and the number of stop words followed by each
+    /// of them. Token filters are not written.
+    pub(crate) fn write_options<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let flags = [
+            self.case_sensitive,
+            self.fold_accents,
+            self.symbols,
+            self.numbers,
+            self.track_variants,
+            self.track_positions,
+            #[cfg(feature = "cjk")]
+            self.cjk_bigrams,
+        ];
+        let flags = flags
+            .iter()
+            .enumerate()
+            .fold(0, |bits, (i, &flag)| bits | u64::from(flag) << i);
+        write_varint(w, flags)?;
+        write_varint(w, self.min_word_len as u64)?;
+        let normalization = match self.normalization {
+            None => 0,
+            Some(Normalization::Nfc) => 1,
+            Some(Normalization::Nfkc) => 2,
+        };
+        let segmentation = match self.segmentation {
+            Segmentation::Whitespace => 0,
+            Segmentation::UnicodeWords => 1,
+        };
+        let apostrophes = match self.apostrophes {
+            Apostrophes::Drop => 0,
+            Apostrophes::Keep => 1,
+            Apostrophes::Split => 2,
+        };
+        let hyphens = match self.hyphens {
+            Hyphens::Drop => 0,
+            Hyphens::Keep => 1,
+            Hyphens::Split => 2,
+            Hyphens::Both => 3,
+        };
+        w.write_all(&[normalization, segmentation, apostrophes, hyphens])?;
+        write_str(w, &self.inner_chars.iter().collect::<String>())?;
+        write_varint(w, self.stopwords.len() as u64)?;
+        for word in self.stopwords.iter() {
+            write_str(w, word)?;
+        }
+        Ok(())
+    }
+
+    /// Read the options written by
+    /// [`BbowBuilder::write_options`].
+    pub(crate) fn read_options<R: Read>(r: &mut R) -> io::Result<Self> {
+        let flags = read_usize(r)?;
+        let flag = |i: usize| flags & 1 << i != 0;
+        if flags >> 7 != 0 {
+            return Err(invalid("unknown option flags"));
+        }
+        #[cfg(not(feature = "cjk"))]
+        if flag(6) {
+            return Err(invalid("CJK bigrams need the cjk feature"));
+        }
+        let min_word_len = read_usize(r)?;
+        let mut bytes = [0; 4];
+        r.read_exact(&mut bytes)?;
+        let normalization = match bytes[0] {
+            0 => None,
+            1 => Some(Normalization::Nfc),
+            2 => Some(Normalization::Nfkc),
+            _ => return Err(invalid("unknown normalization form")),
+        };
+        let segmentation = match bytes[1] {
+            0 => Segmentation::Whitespace,
+            1 => Segmentation::UnicodeWords,
+            _ => return Err(invalid("unknown segmentation")),
+        };
+        let apostrophes = match bytes[2] {
+            0 => Apostrophes::Drop,
+            1 => Apostrophes::Keep,
+            2 => Apostrophes::Split,
+            _ => return Err(invalid("unknown apostrophe option")),
+        };
+        let hyphens = match bytes[3] {
+            0 => Hyphens::Drop,
+            1 => Hyphens::Keep,
+            2 => Hyphens::Split,
+            3 => Hyphens::Both,
+            _ => return Err(invalid("unknown hyphen option")),
+        };
+        let inner_chars = read_str(r)?.chars().collect();
+        let mut stopwords = Vec::new();
+        for _ in 0..read_usize(r)? {
+            stopwords.push(read_str(r)?);
+        }
+        Ok(BbowBuilder {
+            min_word_len,
+            case_sensitive: flag(0),
+            stopwords: StopWords::new().with_words(stopwords),
+            normalization,
+            fold_accents: flag(1),
+            segmentation,
+            #[cfg(feature = "cjk")]
+            cjk_bigrams: flag(6),
+            symbols: flag(2),
+            numbers: flag(3),
+            apostrophes,
+            hyphens,
+            inner_chars,
+            filters: Vec::new(),
+            track_variants: flag(4),
+            track_positions: flag(5),
+        })
+    }
+}
+
+impl Bbow<'_> {
+    /// Start configuring a BBOW with non-default options.
+    /// See [`BbowBuilder`].
+    pub fn builder() -> BbowBuilder {
+        BbowBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_words() {
+        // An option that changes nothing for these texts
+        // turns off the one-pass path.
+        let general = Bbow::builder().inner_chars(['§']);
+        assert!(Bbow::builder().min_word_len(2).has_plain_words());
+        assert!(!general.has_plain_words());
+        for text in ["The cat's hat, THE HAT!", "Ça va? «Très» bien-être 42", ""] {
+            assert_eq!(
+                Bbow::new().extend_from_text(text),
+                general.build().extend_from_text(text),
+            );
+        }
+    }
+
+    #[test]
+    fn test_min_word_len() {
+        let builder = Bbow::builder().min_word_len(2);
+        let words: Vec<Cow<str>> = builder.words("I am Ōno, ok?").collect();
+        assert_eq!(vec!["am", "ōno", "ok"], words);
+        let mut bbow = builder.build().extend_from_text("a bb ccc");
+        assert_eq!(2, bbow.len());
+        bbow.remove_text("a bb");
+        assert_eq!(vec!["ccc"], bbow.words().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_case_sensitive() {
+        let text = "Rust rust RUST";
+        let bbow = Bbow::builder()
+            .case_sensitive(true)
+            .build()
+            .extend_from_text(text);
+        assert_eq!(3, bbow.len());
+        assert_eq!(0, bbow.match_count("rUST"));
+        let bbow = Bbow::builder()
+            .case_sensitive(false)
+            .build()
+            .extend_from_text(text);
+        assert_eq!(vec![("rust", 3)], bbow.top_n(10));
+    }
+
+    #[test]
+    fn test_normalization() {
+        let text = "caf\u{e9} cafe\u{301} \u{fb01}ne";
+        let bbow = Bbow::new().extend_from_text(text);
+        assert_eq!(
+            (1, 1),
+            (bbow.match_count("caf\u{e9}"), bbow.match_count("cafe"))
+        );
+        let nfc = Bbow::builder()
+            .normalization(Normalization::Nfc)
+            .build()
+            .extend_from_text(text);
+        assert_eq!(2, nfc.match_count("caf\u{e9}"));
+        assert_eq!(1, nfc.match_count("\u{fb01}ne"));
+        let nfkc = Bbow::builder()
+            .normalization(Normalization::Nfkc)
+            .build()
+            .extend_from_text(text);
+        assert_eq!(1, nfkc.match_count("fine"));
+        assert!(matches!(super::nfc("caf\u{e9}"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_fold_accents() {
+        assert_eq!("until", super::fold_accents("untïl"));
+        assert_eq!("Ecole", super::fold_accents("École"));
+        assert_eq!("søren", super::fold_accents("søren"));
+        assert!(matches!(super::fold_accents("plain"), Cow::Borrowed(_)));
+        let bbow = Bbow::builder()
+            .fold_accents(true)
+            .build()
+            .extend_from_text("It ain't over untïl it ain't, över.");
+        assert_eq!(
+            vec!["it", "over", "until"],
+            bbow.words().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_unicode_segmentation() {
+        let builder = Bbow::builder().segmentation(Segmentation::UnicodeWords);
+        let words: Vec<Cow<str>> = builder
+            .words("\"Quoted,\"unspaced...text (it's) e.g. here")
+            .collect();
+        assert_eq!(vec!["quoted", "unspaced", "text", "here"], words);
+        let words: Vec<Cow<str>> = builder
+            .words("It ain't over untïl it ain't, over.")
+            .collect();
+        let expected: Vec<Cow<str>> = Bbow::builder()
+            .words("It ain't over untïl it ain't, over.")
+            .collect();
+        assert_eq!(expected, words);
+    }
+
+    #[cfg(feature = "cjk")]
+    #[test]
+    fn test_cjk_bigrams() {
+        let bbow = Bbow::builder()
+            .cjk_bigrams(true)
+            .build()
+            .extend_from_text("日本語のテキストです。\n中文 文本 Mixed日本 한국어");
+        assert_eq!(2, bbow.match_count("日本"));
+        assert_eq!(1, bbow.match_count("文本"));
+        assert_eq!(1, bbow.match_count("中文"));
+        assert_eq!(1, bbow.match_count("mixed"));
+        assert_eq!(1, bbow.match_count("한국어"));
+        assert_eq!(0, bbow.match_count("日本語のテキストです"));
+    }
+
+    #[test]
+    fn test_symbols() {
+        let text = "❤️ hearts ❤️ and 🎉party🎉 time\n👍🏽 thumbs-up 👨‍👩‍👧 family";
+        for segmentation in [Segmentation::Whitespace, Segmentation::UnicodeWords] {
+            let bbow = Bbow::builder()
+                .symbols(true)
+                .segmentation(segmentation)
+                .build()
+                .extend_from_text(text);
+            assert_eq!(2, bbow.match_count("❤️"), "{segmentation:?}");
+            assert_eq!(2, bbow.match_count("🎉"), "{segmentation:?}");
+            assert_eq!(1, bbow.match_count("👨‍👩‍👧"), "{segmentation:?}");
+            assert_eq!(1, bbow.match_count("👍🏽"), "{segmentation:?}");
+            assert_eq!(1, bbow.match_count("party"), "{segmentation:?}");
+        }
+        let bbow = Bbow::new().extend_from_text(text);
+        assert_eq!(0, bbow.match_count("❤️"));
+    }
+
+    #[test]
+    fn test_numbers() {
+        let text = "Win 3.1 in 1992: (R2D2) ½ ٣ v2.";
+        let bbow = Bbow::new().extend_from_text(text);
+        assert_eq!(vec!["in", "v", "win"], bbow.words().collect::<Vec<_>>());
+        let bbow = Bbow::builder().numbers(true).build().extend_from_text(text);
+        let words = bbow.words().collect::<Vec<_>>();
+        assert_eq!(vec!["1992", "in", "r2d2", "v2", "win", "½", "٣"], words);
+        assert_eq!(0, Bbow::new().match_count("1992"));
+    }
+
+    #[test]
+    fn test_apostrophes() {
+        let text = "'Tis Bob's rock 'n' roll, isn't it? Can''t, o'";
+        let words = |apostrophes| {
+            let builder = Bbow::builder().apostrophes(apostrophes);
+            builder.words(text).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec!["tis", "rock", "n", "roll", "it", "o"],
+            words(Apostrophes::Drop)
+        );
+        assert_eq!(
+            vec!["tis", "bob's", "rock", "n", "roll", "isn't", "it", "can''t", "o"],
+            words(Apostrophes::Keep),
+        );
+        assert_eq!(
+            vec!["tis", "bob", "s", "rock", "n", "roll", "isn", "t", "it", "can", "t", "o"],
+            words(Apostrophes::Split),
+        );
+        let bbow = Bbow::builder().apostrophes(Apostrophes::Keep).build();
+        assert_eq!(0, bbow.extend_from_text("isn’t").match_count("isn’t"));
+    }
+
+    #[test]
+    fn test_hyphens() {
+        let text = "Up-to-date well--known x-ray -dash- rock-'n'-roll";
+        let words = |hyphens| {
+            let builder = Bbow::builder().hyphens(hyphens);
+            builder.words(text).collect::<Vec<_>>()
+        };
+        assert_eq!(vec!["dash"], words(Hyphens::Drop));
+        assert_eq!(vec!["up-to-date", "x-ray", "dash"], words(Hyphens::Keep));
+        assert_eq!(
+            vec!["up", "to", "date", "well", "known", "x", "ray", "dash", "rock", "n", "roll"],
+            words(Hyphens::Split),
+        );
+        let both = words(Hyphens::Both);
+        assert_eq!(vec!["up-to-date", "up", "to", "date"], both[..4]);
+        assert_eq!(vec!["x-ray", "x", "ray"], both[6..9]);
+        assert_eq!(2 + words(Hyphens::Split).len(), both.len());
+        let builder = Bbow::builder()
+            .hyphens(Hyphens::Keep)
+            .apostrophes(Apostrophes::Keep);
+        assert_eq!(
+            vec!["rock-'n'-roll"],
+            builder.words("rock-’n’-roll").collect::<Vec<_>>()
+        );
+        let builder = Bbow::builder()
+            .hyphens(Hyphens::Split)
+            .apostrophes(Apostrophes::Split);
+        let words = builder.words("rock-'n'-roll").collect::<Vec<_>>();
+        assert_eq!(vec!["rock", "n", "roll"], words);
+    }
+
+    #[test]
+    fn test_inner_chars() {
+        let builder = Bbow::builder().inner_chars("'.".chars());
+        assert_eq!(1..5, builder.word_range("(U.S.)"));
+        assert_eq!(1..4, builder.word_range("'etc.'"));
+        assert_eq!(3..3, builder.word_range("..."));
+        let words: Vec<Cow<str>> = builder.words("It ain't U.S.A., .dot. a..b").collect();
+        assert_eq!(vec!["it", "ain't", "u.s.a.", "dot", "a..b"], words);
+        let bbow = builder.build().extend_from_text("e.g.");
+        assert_eq!(1, bbow.match_count("e.g."));
+    }
+
+    #[test]
+    fn test_map_cow() {
+        let word: Cow<str> = Cow::Owned("Word".to_string());
+        assert_eq!("word", map_cow(word, lowercase));
+        assert!(matches!(
+            map_cow(Cow::Borrowed("word"), lowercase),
+            Cow::Borrowed("word")
+        ));
+        let word: Cow<str> = Cow::Owned("(word)".to_string());
+        assert_eq!("word", slice_cow(word, 1..5));
+        let word = slice_cow(Cow::Borrowed("(word)"), 1..5);
+        assert!(matches!(word, Cow::Borrowed("word")));
+    }
+
+    #[test]
+    fn test_stopwords() {
+        let stop = StopWords::english().with_words(["Ahab"]);
+        let bbow = Bbow::builder()
+            .case_sensitive(true)
+            .stopwords(stop)
+            .build()
+            .extend_from_text("The captain: Ahab, THE captain!");
+        assert_eq!(vec![("captain", 2)], bbow.top_n(10));
+    }
+}
@@ -0,0 +1,68 @@
+//! Segmentation of Chinese and Japanese text, which is
+//! written without spaces between words, into overlapping
+//! character bigrams.
+//!
+//! Without a dictionary the word boundaries in such text are
+//! unknown, so each run of Han, Hiragana or Katakana
+//! characters is instead counted as all its pairs of adjacent
+//! characters: "日本語" gives "日本" and "本語". A run of one
+//! character is counted as itself. This is the usual
+//! dictionary-free approach of search engines for these
+//! languages. Korean is written with spaces and is left
+//! alone.
+
+/// Is `c` a Han, Hiragana or Katakana character?
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{309f}'     // Hiragana
+        | '\u{30a0}'..='\u{30ff}'   // Katakana
+        | '\u{31f0}'..='\u{31ff}'   // Katakana phonetic extensions
+        | '\u{3400}'..='\u{4dbf}'   // CJK extension A
+        | '\u{4e00}'..='\u{9fff}'   // CJK unified ideographs
+        | '\u{f900}'..='\u{faff}'   // CJK compatibility ideographs
+        | '\u{20000}'..='\u{3134f}' // CJK extensions B to G
+    ) && c != '\u{30fb}' // Katakana middle dot
+}
+
+/// Split `token` into its runs of CJK and other characters,
+/// replacing each CJK run by its bigrams.
+pub(crate) fn cjk_bigrams(token: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut chars = token.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        let cjk = is_cjk(c);
+        let mut starts = Vec::new();
+        let mut end = token.len();
+        while let Some(&(i, c)) = chars.peek() {
+            if is_cjk(c) != cjk {
+                end = i;
+                break;
+            }
+            starts.push(i);
+            chars.next();
+        }
+        if !cjk || starts.len() == 1 {
+            pieces.push(&token[start..end]);
+        } else {
+            starts.push(end);
+            pieces.extend(starts.windows(3).map(|w| &token[w[0]..w[2]]));
+        }
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cjk_bigrams() {
+        assert_eq!(vec!["日本", "本語"], cjk_bigrams("日本語"));
+        assert_eq!(vec!["中"], cjk_bigrams("中"));
+        assert_eq!(vec!["word"], cjk_bigrams("word"));
+        assert_eq!(vec!["日本", "本語", "。"], cjk_bigrams("日本語。"));
+        assert_eq!(vec!["(", "東京", ")OK"], cjk_bigrams("(東京)OK"));
+        assert_eq!(vec!["한국어"], cjk_bigrams("한국어"));
+        assert!(cjk_bigrams("").is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+//! Multinomial Naive Bayes text classification on BBOWs.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::Bbow;
+
+/// What the classifier knows about one label.
+#[derive(Debug, Default, Clone)]
+struct Class {
+    /// Number of BBOWs trained with this label.
+    documents: usize,
+    /// Total count of each word in those BBOWs.
+    counts: BTreeMap<String, usize>,
+    /// Total count of all words in those BBOWs.
+    total: usize,
+}
+
+/// A multinomial Naive Bayes classifier: trained on BBOWs
+/// with known labels, it gives the most likely label of new
+/// BBOWs. Word probabilities use add-one (Laplace)
+/// smoothing, and words never seen in training are ignored.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::{Bbow, NaiveBayes};
+/// let mut nb = NaiveBayes::new();
+/// nb.train("spam", &Bbow::new().extend_from_text("win money now, free money"));
+/// nb.train("ham", &Bbow::new().extend_from_text("lunch meeting moved to noon"));
+/// let mail = Bbow::new().extend_from_text("Free money!");
+/// assert_eq!(Some("spam"), nb.classify(&mail));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct NaiveBayes {
+    classes: BTreeMap<String, Class>,
+    vocabulary: BTreeSet<String>,
+    documents: usize,
+}
+
+impl NaiveBayes {
+    /// Make a new untrained classifier.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Train the classifier with `bbow`, an example of
+    /// `label`.
+    pub fn train(&mut self, label: &str, bbow: &Bbow) {
+        let class = self.classes.entry(label.to_string()).or_default();
+        class.documents += 1;
+        for (word, &count) in &bbow.counts {
+            *class.counts.entry(word.to_string()).or_insert(0) += count;
+            class.total += count;
+            if !self.vocabulary.contains(word.as_ref()) {
+                self.vocabulary.insert(word.to_string());
+            }
+        }
+        self.documents += 1;
+    }
+
+    /// The labels trained so far, in alphabetical order.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.classes.keys().map(|label| label.as_str())
+    }
+
+    /// The log probability, up to a constant, of each label
+    /// for `bbow`, in alphabetical order of label.
+    pub fn scores(&self, bbow: &Bbow) -> Vec<(&str, f64)> {
+        let v = self.vocabulary.len() as f64;
+        self.classes
+            .iter()
+            .map(|(label, class)| {
+                let prior = (class.documents as f64 / self.documents as f64).ln();
+                let denominator = (class.total as f64 + v).ln();
+                let likelihood: f64 = bbow
+                    .counts
+                    .iter()
+                    .filter(|(word, _)| self.vocabulary.contains(word.as_ref()))
+                    .map(|(word, &count)| {
+                        let n = class.counts.get(word.as_ref()).copied().unwrap_or(0);
+                        count as f64 * ((n as f64 + 1.0).ln() - denominator)
+                    })
+                    .sum();
+                (label.as_str(), prior + likelihood)
+            })
+            .collect()
+    }
+
+    /// The most likely label for `bbow`, or `None` if the
+    /// classifier has not been trained. Of equally likely
+    /// labels, the alphabetically first is given.
+    pub fn classify(&self, bbow: &Bbow) -> Option<&str> {
+        self.scores(bbow)
+            .into_iter()
+            .reduce(|best, score| if score.1 > best.1 { score } else { best })
+            .map(|(label, _)| label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_naive_bayes_scores() {
+        // The worked example of Manning, Raghavan and
+        // Schütze, "Introduction to Information Retrieval",
+        // section 13.2.
+        let mut nb = NaiveBayes::new();
+        nb.train(
+            "china",
+            &Bbow::new().extend_from_text("Chinese Beijing Chinese"),
+        );
+        nb.train(
+            "china",
+            &Bbow::new().extend_from_text("Chinese Chinese Shanghai"),
+        );
+        nb.train("china", &Bbow::new().extend_from_text("Chinese Macao"));
+        nb.train(
+            "japan",
+            &Bbow::new().extend_from_text("Tokyo Japan Chinese"),
+        );
+        let test = Bbow::new().extend_from_text("Chinese Chinese Chinese Tokyo Japan");
+        let scores = nb.scores(&test);
+        let expected = [("china", 0.0003), ("japan", 0.0001)];
+        for ((label, score), (expected_label, p)) in scores.iter().zip(expected) {
+            assert_eq!(expected_label, *label);
+            assert!(
+                (score.exp() - p).abs() < 0.00005,
+                "{label}: {}",
+                score.exp()
+            );
+        }
+        assert_eq!(Some("china"), nb.classify(&test));
+        assert_eq!(vec!["china", "japan"], nb.labels().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_naive_bayes_untrained_and_unknown() {
+        let mut nb = NaiveBayes::new();
+        assert_eq!(None, nb.classify(&Bbow::new().extend_from_text("anything")));
+        nb.train("b", &Bbow::new().extend_from_text("x"));
+        nb.train("a", &Bbow::new().extend_from_text("y"));
+        assert_eq!(
+            Some("a"),
+            nb.classify(&Bbow::new().extend_from_text("unseen"))
+        );
+        assert_eq!(
+            Some("b"),
+            nb.classify(&Bbow::new().extend_from_text("x unseen"))
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Keyword-in-context concordances.
+
+use crate::Bbow;
+
+/// One occurrence of a keyword in a text, with the text
+/// around it. All three parts are slices of the text, as it
+/// was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Concordance<'t> {
+    left: &'t str,
+    keyword: &'t str,
+    right: &'t str,
+}
+
+impl<'t> Concordance<'t> {
+    /// The text before the keyword.
+    pub fn left(&self) -> &'t str {
+        self.left
+    }
+
+    /// The keyword, with any punctuation attached to it.
+    pub fn keyword(&self) -> &'t str {
+        self.keyword
+    }
+
+    /// The text after the keyword.
+    pub fn right(&self) -> &'t str {
+        self.right
+    }
+}
+
+/// Byte offset of `part` in `text`, of which it is a slice.
+fn offset(text: &str, part: &str) -> usize {
+    part.as_ptr() as usize - text.as_ptr() as usize
+}
+
+impl Bbow<'_> {
+    /// Each occurrence of `keyword` in `text`, with up to
+    /// `window` words of context on each side. The text is
+    /// split into words as it would be by
+    /// [`Bbow::extend_from_text`] on this BBOW, so the
+    /// keyword should be given as it would be counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let text = "The cat sat on the mat. Then the cat, bored, left.";
+    /// let kwic = Bbow::new().concordance(text, "cat", 2);
+    /// assert_eq!(2, kwic.len());
+    /// assert_eq!(("The", "cat", "sat on"), (kwic[0].left(), kwic[0].keyword(), kwic[0].right()));
+    /// assert_eq!(("Then the", "cat,", "bored, left."), (kwic[1].left(), kwic[1].keyword(), kwic[1].right()));
+    /// ```
+    pub fn concordance<'t>(
+        &self,
+        text: &'t str,
+        keyword: &str,
+        window: usize,
+    ) -> Vec<Concordance<'t>> {
+        let tokens: Vec<(&str, bool)> = self
+            .options
+            .tokens(text)
+            .filter_map(|token| {
+                let mut words = self.options.token_words(token).peekable();
+                words.peek()?;
+                Some((token, words.any(|word| word == keyword)))
+            })
+            .collect();
+        let start = |i: usize| offset(text, tokens[i].0);
+        let end = |i: usize| start(i) + tokens[i].0.len();
+        tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, &(_, matched))| matched)
+            .map(|(i, &(token, _))| {
+                let first = i.saturating_sub(window);
+                let last = (i + window).min(tokens.len() - 1);
+                Concordance {
+                    left: text[start(first)..start(i)].trim_end(),
+                    keyword: token,
+                    right: text[end(i)..end(last)].trim_start(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts<'t>(kwic: &[Concordance<'t>]) -> Vec<(&'t str, &'t str, &'t str)> {
+        kwic.iter()
+            .map(|c| (c.left(), c.keyword(), c.right()))
+            .collect()
+    }
+
+    #[test]
+    fn test_concordance_edges() {
+        let text = "Spam — spam, eggs and\nspam!";
+        let kwic = Bbow::new().concordance(text, "spam", 1);
+        assert_eq!(
+            vec![
+                ("", "Spam", "— spam,"),
+                ("Spam —", "spam,", "eggs"),
+                ("and", "spam!", "")
+            ],
+            parts(&kwic),
+        );
+        assert_eq!(3, Bbow::new().concordance(text, "spam", 10).len());
+        let kwic = Bbow::new().concordance(text, "eggs", 0);
+        assert_eq!(vec![("", "eggs", "")], parts(&kwic));
+        assert!(Bbow::new().concordance(text, "Spam", 1).is_empty());
+        assert!(Bbow::new().concordance("", "spam", 1).is_empty());
+    }
+}
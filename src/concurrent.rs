@@ -0,0 +1,262 @@
+//! A BBOW that many threads can add text to at once.
+
+use std::collections::BTreeMap;
+use std::hash::{BuildHasher, BuildHasherDefault, DefaultHasher};
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+
+use crate::intern::Word;
+use crate::{entry_for, Bbow, BbowBuilder};
+
+type Shard<'a> = BTreeMap<Word<'a>, usize>;
+
+/// A BBOW that can be shared between threads, each adding
+/// texts at the same time. The words are split among several
+/// shards, each behind its own lock, so that threads rarely
+/// wait for each other. Each text is counted first without
+/// any lock, then added to the shards a shard at a time.
+///
+/// When adding is done, [`ConcurrentBbow::freeze`] gives an
+/// ordinary BBOW of all the words, with the options the
+/// texts were split into words with.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::ConcurrentBbow;
+/// let texts = ["one fish", "two fish", "red fish", "blue fish"];
+/// let bbow = ConcurrentBbow::new();
+/// std::thread::scope(|s| {
+///     for text in texts {
+///         s.spawn(|| bbow.extend_from_text(text));
+///     }
+/// });
+/// let bbow = bbow.freeze();
+/// assert_eq!(4, bbow.match_count("fish"));
+/// assert_eq!(5, bbow.len());
+/// ```
+#[derive(Debug)]
+pub struct ConcurrentBbow<'a> {
+    shards: Vec<Mutex<Shard<'a>>>,
+    /// The options texts are split into words with.
+    builder: BbowBuilder,
+}
+
+impl Default for ConcurrentBbow<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> ConcurrentBbow<'a> {
+    /// Make a new empty BBOW with a few shards for each
+    /// thread the machine can run at once.
+    pub fn new() -> Self {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_shards(4 * threads)
+    }
+
+    /// Make a new empty BBOW with `shards` shards, at least
+    /// one.
+    pub fn with_shards(shards: usize) -> Self {
+        Self {
+            shards: (0..shards.max(1)).map(|_| Mutex::default()).collect(),
+            builder: BbowBuilder::new(),
+        }
+    }
+
+    /// Make a new empty BBOW, as [`ConcurrentBbow::new`]
+    /// does, that splits texts into words with the options
+    /// of `builder`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::{Bbow, ConcurrentBbow};
+    /// let bbow = ConcurrentBbow::with_builder(Bbow::builder().numbers(true));
+    /// bbow.extend_from_text("in 2024");
+    /// let bbow = bbow.freeze();
+    /// assert_eq!(1, bbow.match_count("2024"));
+    /// assert_eq!(2, bbow.extend_from_text("2024").match_count("2024"));
+    /// ```
+    pub fn with_builder(builder: BbowBuilder) -> Self {
+        Self {
+            builder,
+            ..Self::new()
+        }
+    }
+
+    /// The index of the shard holding `word`.
+    fn shard_of(&self, word: &str) -> usize {
+        let hasher = BuildHasherDefault::<DefaultHasher>::default();
+        (hasher.hash_one(word) % self.shards.len() as u64) as usize
+    }
+
+    fn lock(&self, shard: usize) -> MutexGuard<'_, Shard<'a>> {
+        // A thread that panicked while holding the lock
+        // cannot have left the counts inconsistent.
+        self.shards[shard]
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Parse the `target` text and add the sequence of
+    /// valid words contained in it to this BBOW, as
+    /// [`Bbow::extend_from_text`] does with the options of
+    /// this BBOW.
+    pub fn extend_from_text(&self, target: &'a str) {
+        self.merge(&self.builder.build().extend_from_text(target));
+    }
+
+    /// Add the words of `other`, with their counts, to this
+    /// BBOW. As with [`Bbow::merge`], the words are added as
+    /// `other` holds them: if it was built with other
+    /// options, a word these options would not give, such as
+    /// a number, is kept and frozen, but not found by
+    /// [`ConcurrentBbow::match_count`] or by
+    /// [`Bbow::match_count`] on the frozen BBOW.
+    pub fn merge(&self, other: &Bbow<'a>) {
+        let mut by_shard: Vec<Vec<(&Word<'a>, usize)>> = vec![Vec::new(); self.shards.len()];
+        for (word, &count) in &other.counts {
+            by_shard[self.shard_of(word)].push((word, count));
+        }
+        for (shard, words) in by_shard.into_iter().enumerate() {
+            if words.is_empty() {
+                continue;
+            }
+            let mut shard = self.lock(shard);
+            for (word, count) in words {
+                *entry_for(&mut shard, word) += count;
+            }
+        }
+    }
+
+    /// Report the number of occurrences of the given
+    /// `keyword` in this BBOW, as [`Bbow::match_count`]
+    /// does with the options of this BBOW.
+    pub fn match_count(&self, keyword: &str) -> usize {
+        if !self.builder.is_token(keyword) {
+            return 0;
+        }
+        let shard = self.lock(self.shard_of(keyword));
+        shard.get(keyword).copied().unwrap_or(0)
+    }
+
+    /// Count the overall number of words contained in this
+    /// BBOW: multiple occurrences are considered separate.
+    pub fn count(&self) -> usize {
+        (0..self.shards.len())
+            .map(|shard| self.lock(shard).values().sum::<usize>())
+            .sum()
+    }
+
+    /// Count the number of unique words contained in this
+    /// BBOW.
+    pub fn len(&self) -> usize {
+        (0..self.shards.len())
+            .map(|shard| self.lock(shard).len())
+            .sum()
+    }
+
+    /// Is this BBOW empty?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// An ordinary BBOW of all the words added, with the
+    /// options of this BBOW.
+    pub fn freeze(self) -> Bbow<'a> {
+        let mut bbow = self.builder.build();
+        for shard in self.shards {
+            let shard = shard
+                .into_inner()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            bbow.counts.extend(shard);
+        }
+        bbow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concurrent_matches_bbow() {
+        let texts: Vec<String> = (0..64)
+            .map(|i| {
+                format!(
+                    "It ain't over untïl it ain't, over. Word{} {}",
+                    i % 7,
+                    "x ".repeat(i)
+                )
+            })
+            .collect();
+        let concurrent = ConcurrentBbow::with_shards(3);
+        thread::scope(|s| {
+            for chunk in texts.chunks(8) {
+                let concurrent = &concurrent;
+                s.spawn(move || {
+                    for text in chunk {
+                        concurrent.extend_from_text(text);
+                    }
+                });
+            }
+        });
+        let mut expected = Bbow::new();
+        for text in &texts {
+            expected = expected.extend_from_text(text);
+        }
+        assert_eq!(expected.len(), concurrent.len());
+        assert_eq!(expected.count(), concurrent.count());
+        assert_eq!(128, concurrent.match_count("over"));
+        assert_eq!(0, concurrent.match_count("Over"));
+        assert_eq!(expected, concurrent.freeze());
+    }
+
+    #[test]
+    fn test_concurrent_empty() {
+        let concurrent = ConcurrentBbow::with_shards(0);
+        assert!(concurrent.is_empty());
+        concurrent.extend_from_text("one");
+        assert_eq!(1, concurrent.freeze().len());
+    }
+
+    #[test]
+    fn test_concurrent_merge_options() {
+        let numbers = Bbow::builder().numbers(true).build();
+        let concurrent = ConcurrentBbow::new();
+        concurrent.merge(&numbers.extend_from_text("fish in 2024"));
+        assert_eq!(1, concurrent.match_count("fish"));
+        assert_eq!(0, concurrent.match_count("2024"));
+        let frozen = concurrent.freeze();
+        assert_eq!(0, frozen.match_count("2024"));
+        assert!(frozen.contains("2024"));
+        let mut bytes = Vec::new();
+        frozen.write_binary(&mut bytes).unwrap();
+        assert_eq!(frozen, Bbow::read_binary(bytes.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_concurrent_builder() {
+        let builder = Bbow::builder().numbers(true).case_sensitive(true);
+        let concurrent = ConcurrentBbow::with_builder(builder.clone());
+        thread::scope(|s| {
+            for text in ["Fish in 2024", "fish in 2025"] {
+                let concurrent = &concurrent;
+                s.spawn(move || concurrent.extend_from_text(text));
+            }
+        });
+        assert_eq!(1, concurrent.match_count("Fish"));
+        assert_eq!(1, concurrent.match_count("2024"));
+        let frozen = concurrent.freeze();
+        let expected = builder
+            .build()
+            .extend_from_text("Fish in 2024")
+            .extend_from_text("fish in 2025");
+        assert_eq!(expected, frozen);
+        let frozen = frozen.extend_from_text("Fish 2024");
+        assert_eq!(2, frozen.match_count("Fish"));
+        assert_eq!(2, frozen.match_count("2024"));
+    }
+}
@@ -0,0 +1,309 @@
+//! Word co-occurrence counts within a sliding window.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::{words_of, Bbow};
+
+/// Counts of how often pairs of words occur near each other
+/// in some texts.
+///
+/// Two word occurrences co-occur when they are at most
+/// `window` words apart in the same text, so a window of 1
+/// counts adjacent words only. Co-occurrence is symmetric:
+/// the order in which the two words appear does not matter.
+#[derive(Debug, Clone)]
+pub struct Cooccurrence<'a> {
+    window: usize,
+    words: Bbow<'a>,
+    /// For each word, the co-occurrence count of each of its
+    /// neighbors. Every pair is recorded under both words.
+    neighbors: PairCounts<'a>,
+    /// For each word, the number of times each other word
+    /// follows it within the window.
+    following: PairCounts<'a>,
+    /// Total number of co-occurring pairs seen.
+    pairs: usize,
+}
+
+type PairCounts<'a> = BTreeMap<Cow<'a, str>, BTreeMap<Cow<'a, str>, usize>>;
+
+fn record<'a>(counts: &mut PairCounts<'a>, word: Cow<'a, str>, neighbor: Cow<'a, str>) {
+    *counts.entry(word).or_default().entry(neighbor).or_insert(0) += 1;
+}
+
+/// A square matrix of counts in coordinate form: only the
+/// nonzero entries are stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseMatrix<'c> {
+    vocabulary: Vec<&'c str>,
+    entries: Vec<(usize, usize, usize)>,
+}
+
+impl<'c> SparseMatrix<'c> {
+    /// The words labeling the rows and columns, in
+    /// alphabetical order: row and column `i` are for word
+    /// `i`.
+    pub fn vocabulary(&self) -> &[&'c str] {
+        &self.vocabulary
+    }
+
+    /// The nonzero entries of the matrix as `(row, column,
+    /// count)` triples, in row-major order.
+    pub fn entries(&self) -> &[(usize, usize, usize)] {
+        &self.entries
+    }
+
+    /// The entry at `row` and `column`.
+    pub fn get(&self, row: usize, column: usize) -> usize {
+        self.entries
+            .binary_search_by(|&(r, c, _)| (r, c).cmp(&(row, column)))
+            .map_or(0, |i| self.entries[i].2)
+    }
+}
+
+impl<'a> Cooccurrence<'a> {
+    /// Make a new empty co-occurrence table with the given
+    /// `window` size.
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            words: Bbow::new(),
+            neighbors: BTreeMap::new(),
+            following: BTreeMap::new(),
+            pairs: 0,
+        }
+    }
+
+    /// Parse the `target` text and add the co-occurrences of
+    /// the words in it to this table. Words in different
+    /// texts never co-occur.
+    ///
+    /// This is a "builder method", like
+    /// [`Bbow::extend_from_text`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Cooccurrence;
+    /// let cooc = Cooccurrence::new(2).extend_from_text("red fish blue fish");
+    /// assert_eq!(2, cooc.cooccurrence_count("blue", "fish"));
+    /// assert_eq!(1, cooc.cooccurrence_count("fish", "red"));
+    /// assert_eq!(1, cooc.cooccurrence_count("fish", "fish"));
+    /// ```
+    pub fn extend_from_text(mut self, target: &'a str) -> Self {
+        let mut recent: VecDeque<Cow<'a, str>> = VecDeque::with_capacity(self.window);
+        for word in words_of(target) {
+            for prior in &recent {
+                self.pairs += 1;
+                record(&mut self.neighbors, prior.clone(), word.clone());
+                if prior != &word {
+                    record(&mut self.neighbors, word.clone(), prior.clone());
+                }
+                record(&mut self.following, prior.clone(), word.clone());
+            }
+            if self.window > 0 {
+                if recent.len() == self.window {
+                    recent.pop_front();
+                }
+                recent.push_back(word.clone());
+            }
+            self.words.add(word, 1);
+        }
+        self
+    }
+
+    /// The BBOW of all the words seen by this table.
+    pub fn words(&self) -> &Bbow<'a> {
+        &self.words
+    }
+
+    /// Number of times words `a` and `b` co-occur, in either
+    /// order.
+    pub fn cooccurrence_count(&self, a: &str, b: &str) -> usize {
+        self.neighbors
+            .get(a)
+            .and_then(|neighbors| neighbors.get(b))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Number of times word `b` occurs within the window
+    /// after word `a`. Unlike
+    /// [`Cooccurrence::cooccurrence_count`], this depends on
+    /// the order of the words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Cooccurrence;
+    /// let cooc = Cooccurrence::new(1).extend_from_text("new york and new jersey");
+    /// assert_eq!(1, cooc.pair_count("new", "york"));
+    /// assert_eq!(0, cooc.pair_count("york", "new"));
+    /// assert_eq!(1, cooc.cooccurrence_count("york", "new"));
+    /// ```
+    pub fn pair_count(&self, a: &str, b: &str) -> usize {
+        self.following
+            .get(a)
+            .and_then(|following| following.get(b))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The co-occurrence counts of all pairs of words as a
+    /// symmetric sparse matrix, for use with linear algebra
+    /// or embedding tools.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Cooccurrence;
+    /// let cooc = Cooccurrence::new(1).extend_from_text("red fish blue fish");
+    /// let matrix = cooc.to_sparse_matrix();
+    /// assert_eq!(&["blue", "fish", "red"], matrix.vocabulary());
+    /// assert_eq!(2, matrix.get(0, 1));
+    /// assert_eq!(2, matrix.get(1, 0));
+    /// assert_eq!(0, matrix.get(0, 2));
+    /// ```
+    pub fn to_sparse_matrix(&self) -> SparseMatrix<'_> {
+        let vocabulary: Vec<&str> = self.words.words().collect();
+        let index = |word: &str| vocabulary.binary_search(&word).unwrap();
+        let mut entries = Vec::new();
+        for (word, neighbors) in &self.neighbors {
+            let row = index(word);
+            for (neighbor, &count) in neighbors {
+                entries.push((row, index(neighbor), count));
+            }
+        }
+        SparseMatrix {
+            vocabulary,
+            entries,
+        }
+    }
+
+    /// Pointwise mutual information, in bits, of words `a`
+    /// and `b`: `log2(p(a, b) / (p(a) * p(b)))`, where `p(a,
+    /// b)` is the fraction of co-occurring pairs that are
+    /// `a` and `b`, and `p(a)` the fraction of words that are
+    /// `a`. Positive values mean that the words occur
+    /// together more often than their frequencies alone
+    /// suggest. Returns `None` if the words never co-occur.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Cooccurrence;
+    /// let cooc = Cooccurrence::new(1)
+    ///     .extend_from_text("new york is big and new york is old and rome is old");
+    /// let ny = cooc.pmi("new", "york").unwrap();
+    /// let old = cooc.pmi("is", "old").unwrap();
+    /// assert!(ny > old);
+    /// assert_eq!(None, cooc.pmi("rome", "york"));
+    /// ```
+    pub fn pmi(&self, a: &str, b: &str) -> Option<f64> {
+        let count = self.cooccurrence_count(a, b);
+        if count == 0 {
+            return None;
+        }
+        let n = self.words.count() as f64;
+        let p_ab = count as f64 / self.pairs as f64;
+        let p_a = self.words.match_count(a) as f64 / n;
+        let p_b = self.words.match_count(b) as f64 / n;
+        Some((p_ab / (p_a * p_b)).log2())
+    }
+
+    /// All the words that co-occur with `word`, with their
+    /// PMI with it, strongest first. Words with equal PMI are
+    /// given in alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Cooccurrence;
+    /// let cooc = Cooccurrence::new(1)
+    ///     .extend_from_text("salt and pepper and salt and vinegar");
+    /// let collocates: Vec<&str> = cooc.collocates("salt").iter().map(|&(w, _)| w).collect();
+    /// assert_eq!(vec!["and"], collocates);
+    /// ```
+    pub fn collocates(&self, word: &str) -> Vec<(&str, f64)> {
+        let Some(neighbors) = self.neighbors.get(word) else {
+            return Vec::new();
+        };
+        let mut collocates: Vec<(&str, f64)> = neighbors
+            .keys()
+            .filter_map(|neighbor| Some((neighbor.as_ref(), self.pmi(word, neighbor)?)))
+            .collect();
+        collocates.sort_by(|(w1, p1), (w2, p2)| p2.total_cmp(p1).then(w1.cmp(w2)));
+        collocates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_sizes() {
+        let text = "a b c d";
+        let cooc = Cooccurrence::new(0).extend_from_text(text);
+        assert_eq!(0, cooc.cooccurrence_count("a", "b"));
+        let cooc = Cooccurrence::new(1).extend_from_text(text);
+        assert_eq!(1, cooc.cooccurrence_count("a", "b"));
+        assert_eq!(0, cooc.cooccurrence_count("a", "c"));
+        let cooc = Cooccurrence::new(3).extend_from_text(text);
+        assert_eq!(1, cooc.cooccurrence_count("d", "a"));
+        assert_eq!(6, cooc.pairs);
+    }
+
+    #[test]
+    fn test_texts_are_separate() {
+        let cooc = Cooccurrence::new(1)
+            .extend_from_text("a b")
+            .extend_from_text("c d");
+        assert_eq!(0, cooc.cooccurrence_count("b", "c"));
+        assert_eq!(4, cooc.words().count());
+    }
+
+    #[test]
+    fn test_pair_count_order() {
+        let cooc = Cooccurrence::new(2).extend_from_text("a b a c");
+        assert_eq!(
+            (1, 1),
+            (cooc.pair_count("a", "b"), cooc.pair_count("b", "a"))
+        );
+        assert_eq!(
+            (1, 0),
+            (cooc.pair_count("a", "c"), cooc.pair_count("c", "a"))
+        );
+        assert_eq!(1, cooc.pair_count("a", "a"));
+        assert_eq!(2, cooc.cooccurrence_count("b", "a"));
+        assert_eq!(0, cooc.pair_count("a", "zebra"));
+    }
+
+    #[test]
+    fn test_sparse_matrix() {
+        let cooc = Cooccurrence::new(1).extend_from_text("x x y");
+        let matrix = cooc.to_sparse_matrix();
+        assert_eq!(vec![(0, 0, 1), (0, 1, 1), (1, 0, 1)], matrix.entries());
+        let empty = Cooccurrence::new(1).extend_from_text("alone");
+        assert!(empty.to_sparse_matrix().entries().is_empty());
+        assert_eq!(&["alone"], empty.to_sparse_matrix().vocabulary());
+    }
+
+    #[test]
+    fn test_pmi_values() {
+        let cooc = Cooccurrence::new(1).extend_from_text("a b a b a b a b");
+        let pmi = cooc.pmi("a", "b").unwrap();
+        assert!((pmi - 2.0).abs() < 1e-9, "{pmi}");
+        assert_eq!(None, cooc.pmi("a", "a"));
+        assert_eq!(None, cooc.pmi("a", "zebra"));
+    }
+
+    #[test]
+    fn test_collocates_order() {
+        let cooc = Cooccurrence::new(1).extend_from_text("x rare x common y common z common");
+        let collocates: Vec<&str> = cooc.collocates("x").iter().map(|&(w, _)| w).collect();
+        assert_eq!(vec!["rare", "common"], collocates);
+        assert!(cooc.collocates("absent").is_empty());
+    }
+}
@@ -0,0 +1,394 @@
+//! Corpus: a collection of named documents, each with its
+//! own BBOW.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use crate::Bbow;
+
+/// One document of a [`Corpus`]: a name and the BBOW of its
+/// text.
+#[derive(Debug, Clone)]
+pub struct Document<'a> {
+    name: Cow<'a, str>,
+    bbow: Bbow<'a>,
+}
+
+impl<'a> Document<'a> {
+    /// The name of this document.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The BBOW of this document.
+    pub fn bbow(&self) -> &Bbow<'a> {
+        &self.bbow
+    }
+}
+
+/// A sequence of [`Document`]s. Documents are identified by
+/// their index in the sequence, in the order they were
+/// added.
+#[derive(Debug, Default, Clone)]
+pub struct Corpus<'a> {
+    documents: Vec<Document<'a>>,
+}
+
+/// For each word of a [`Corpus`], the documents it occurs
+/// in with its count in each, made by
+/// [`Corpus::inverted_index`]. Finding the documents with a
+/// word is then a single lookup, rather than a look at
+/// every document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InvertedIndex<'c> {
+    postings: BTreeMap<&'c str, Vec<(usize, usize)>>,
+    /// The number of words in each document, for ranking.
+    pub(crate) lengths: Vec<usize>,
+    /// The sum of `lengths`.
+    pub(crate) total_length: usize,
+}
+
+impl<'c> InvertedIndex<'c> {
+    /// The documents containing `word`, as `(document index,
+    /// count)` pairs in order of index. The word must be
+    /// given as it is stored, as for [`Bbow::match_count`].
+    pub fn postings(&self, word: &str) -> &[(usize, usize)] {
+        self.postings.get(word).map_or(&[], Vec::as_slice)
+    }
+
+    /// The number of documents containing `word`.
+    pub fn document_frequency(&self, word: &str) -> usize {
+        self.postings(word).len()
+    }
+
+    /// The indices of the documents containing every one of
+    /// `words`, in order. No words match every document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Corpus;
+    /// let mut corpus = Corpus::new();
+    /// corpus.add_text("a", "red fish");
+    /// corpus.add_text("b", "blue fish");
+    /// corpus.add_text("c", "red bird");
+    /// let index = corpus.inverted_index();
+    /// assert_eq!(vec![0], index.containing_all(&["red", "fish"]));
+    /// assert_eq!(vec![0, 2], index.containing_all(&["red"]));
+    /// assert!(index.containing_all(&["green"]).is_empty());
+    /// ```
+    pub fn containing_all(&self, words: &[&str]) -> Vec<usize> {
+        let mut lists: Vec<&[(usize, usize)]> = words.iter().map(|w| self.postings(w)).collect();
+        // Start from the shortest list, so that the fewest
+        // documents are checked.
+        lists.sort_unstable_by_key(|l| l.len());
+        let Some((first, rest)) = lists.split_first() else {
+            return Vec::new();
+        };
+        first
+            .iter()
+            .map(|&(id, _)| id)
+            .filter(|id| {
+                rest.iter()
+                    .all(|l| l.binary_search_by_key(id, |&(i, _)| i).is_ok())
+            })
+            .collect()
+    }
+
+    /// The indices of the documents containing any of
+    /// `words`, in order.
+    pub fn containing_any(&self, words: &[&str]) -> Vec<usize> {
+        let mut ids: Vec<usize> = words
+            .iter()
+            .flat_map(|w| self.postings(w).iter().map(|&(id, _)| id))
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    /// The words in the index, in alphabetical order.
+    pub fn words(&self) -> impl Iterator<Item = &'c str> + '_ {
+        self.postings.keys().copied()
+    }
+}
+
+/// Is `line` a heading? If so, return its title.
+///
+/// Markdown headers (`# Title` through `###### Title`) are
+/// headings, as are lines that are just "Chapter", "Book" or
+/// "Part", in title case or upper case, and an Arabic or
+/// Roman numeral, optionally followed by a title after a
+/// `.`, `:` or `—`, such as `CHAPTER XII. The Trial`. Prose
+/// such as "part I never understood" is not a heading.
+fn heading(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let hashes = line.len() - line.trim_start_matches('#').len();
+    if (1..=6).contains(&hashes) && line[hashes..].starts_with(char::is_whitespace) {
+        let title = line[hashes..].trim().trim_end_matches('#').trim_end();
+        return (!title.is_empty()).then_some(title);
+    }
+    let (first, rest) = line.split_once(char::is_whitespace)?;
+    let rest = rest.trim_start();
+    let (number, title) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let is_division = ["Chapter", "Book", "Part"]
+        .iter()
+        .any(|&d| first == d || first == d.to_uppercase());
+    let (number, separated) = match number.strip_suffix(['.', ':']) {
+        Some(number) => (number, true),
+        None => (number, title.is_empty() || title.starts_with('—')),
+    };
+    let is_number = !number.is_empty()
+        && (number.chars().all(|c| c.is_ascii_digit())
+            || number.chars().all(|c| "IVXLCDM".contains(c))
+            || number.chars().all(|c| "ivxlcdm".contains(c)));
+    (is_division && is_number && separated).then_some(line)
+}
+
+/// Split `text` into `(title, body)` sections at its
+/// headings. Any text before the first heading that
+/// contains more than whitespace becomes a section with an
+/// empty title.
+fn sections(text: &str) -> Vec<(&str, &str)> {
+    let mut sections = Vec::new();
+    let mut title = "";
+    let mut start = 0;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if let Some(next_title) = heading(line) {
+            let body = &text[start..offset];
+            if !(title.is_empty() && body.trim().is_empty()) {
+                sections.push((title, body));
+            }
+            title = next_title;
+            start = offset + line.len();
+        }
+        offset += line.len();
+    }
+    let body = &text[start..];
+    if !(title.is_empty() && body.trim().is_empty()) {
+        sections.push((title, body));
+    }
+    sections
+}
+
+impl<'a> Corpus<'a> {
+    /// Make a new empty corpus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make a corpus from a long text such as a book, with
+    /// one document per section of the text. Sections begin
+    /// at headings: Markdown headers, or lines such as
+    /// `CHAPTER IV` or `Part 2: The Return`. Each document is
+    /// named by its heading line; text before the first
+    /// heading, if any, becomes a first document with an
+    /// empty name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Corpus;
+    /// let book = "CHAPTER I\nCall me Ishmael.\n\nCHAPTER II\nThe whale.\n";
+    /// let corpus = Corpus::from_headings(book);
+    /// assert_eq!(2, corpus.len());
+    /// assert_eq!("CHAPTER II", corpus.get(1).unwrap().name());
+    /// assert_eq!(1, corpus.get(1).unwrap().bbow().match_count("whale"));
+    /// ```
+    pub fn from_headings(text: &'a str) -> Self {
+        let mut corpus = Self::new();
+        for (title, body) in sections(text) {
+            corpus.add_text(title, body);
+        }
+        corpus
+    }
+
+    /// Add a document with the given `name` and `bbow` to
+    /// this corpus, returning its index.
+    pub fn add<N: Into<Cow<'a, str>>>(&mut self, name: N, bbow: Bbow<'a>) -> usize {
+        self.documents.push(Document {
+            name: name.into(),
+            bbow,
+        });
+        self.documents.len() - 1
+    }
+
+    /// Add a document with the given `name` made from the
+    /// words of `text` to this corpus, returning its index.
+    pub fn add_text<N: Into<Cow<'a, str>>>(&mut self, name: N, text: &'a str) -> usize {
+        self.add(name, Bbow::new().extend_from_text(text))
+    }
+
+    /// A single BBOW combining the words of every document
+    /// in this corpus, with the options of the first
+    /// document, so that the words of a corpus of documents
+    /// with the same options are found in it as in them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Corpus;
+    /// let mut corpus = Corpus::new();
+    /// corpus.add_text("a", "red fish");
+    /// corpus.add_text("b", "blue fish");
+    /// assert_eq!(2, corpus.total().match_count("fish"));
+    /// ```
+    pub fn total(&self) -> Bbow<'a> {
+        let mut total = self
+            .documents
+            .first()
+            .map_or_else(Bbow::new, |document| document.bbow.options.build());
+        for document in &self.documents {
+            total.merge(&document.bbow);
+        }
+        total
+    }
+
+    /// The document with index `id`, if any.
+    pub fn get(&self, id: usize) -> Option<&Document<'a>> {
+        self.documents.get(id)
+    }
+
+    /// Iterate over the documents of this corpus in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Document<'a>> {
+        self.documents.iter()
+    }
+
+    /// Number of documents in this corpus.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Is this corpus empty?
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// An [`InvertedIndex`] of the words of the documents in
+    /// this corpus.
+    pub fn inverted_index(&self) -> InvertedIndex<'_> {
+        let mut postings: BTreeMap<&str, Vec<(usize, usize)>> = BTreeMap::new();
+        let mut lengths = Vec::with_capacity(self.len());
+        for (id, document) in self.documents.iter().enumerate() {
+            for (word, count) in document.bbow.iter() {
+                postings.entry(word).or_default().push((id, count));
+            }
+            lengths.push(document.bbow.count());
+        }
+        let total_length = lengths.iter().sum();
+        InvertedIndex {
+            postings,
+            lengths,
+            total_length,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heading() {
+        assert_eq!(Some("Intro"), heading("## Intro ##\n"));
+        assert_eq!(
+            Some("CHAPTER XII. The Trial"),
+            heading("  CHAPTER XII. The Trial\n")
+        );
+        assert_eq!(Some("Chapter 3"), heading("Chapter 3"));
+        assert_eq!(Some("Part iv: Home"), heading("Part iv: Home"));
+        assert_eq!(None, heading("#hashtag"));
+        assert_eq!(None, heading("#"));
+        assert_eq!(None, heading("Chapter one was dull."));
+        assert_eq!(None, heading("Part of me stayed."));
+        assert_eq!(Some("Book 2 — Winter"), heading("Book 2 — Winter"));
+        assert_eq!(Some("PART III:"), heading("PART III:"));
+        for prose in [
+            "part I never understood why.",
+            "chapter 3 of the report says otherwise",
+            "Chapter 3 of the report says otherwise",
+            "Part I never understood why.",
+            "CHAPTER 3 AND 4",
+            "cHAPTER 3",
+            "Chapter 3.5",
+        ] {
+            assert_eq!(None, heading(prose), "{prose}");
+        }
+    }
+
+    #[test]
+    fn test_sections() {
+        let text = "Title page\n# One\nfirst\n# Two\nsecond\n";
+        assert_eq!(
+            vec![
+                ("", "Title page\n"),
+                ("One", "first\n"),
+                ("Two", "second\n")
+            ],
+            sections(text),
+        );
+        assert_eq!(vec![("One", "body")], sections("\n\n# One\nbody"));
+        assert_eq!(vec![("", "no headings")], sections("no headings"));
+        assert!(sections("").is_empty());
+    }
+
+    #[test]
+    fn test_from_headings() {
+        let book = "CHAPTER 1\nA cat.\nCHAPTER 2\nA cat and a cat.\n";
+        let corpus = Corpus::from_headings(book);
+        let counts: Vec<usize> = corpus.iter().map(|d| d.bbow().match_count("cat")).collect();
+        assert_eq!(vec![1, 2], counts);
+    }
+
+    #[test]
+    fn test_add_text_shares_words() {
+        let mut corpus = Corpus::new();
+        corpus.add_text("a", "Whale ahoy");
+        corpus.add_text("b", "WHALE");
+        let key = |i: usize| {
+            corpus.documents[i]
+                .bbow
+                .counts
+                .keys()
+                .last()
+                .unwrap()
+                .as_ptr()
+        };
+        assert_eq!(key(0), key(1));
+    }
+
+    #[test]
+    fn test_total_options() {
+        let builder = Bbow::builder().numbers(true).case_sensitive(true);
+        let mut corpus = Corpus::new();
+        corpus.add("a", builder.build().extend_from_text("Rome in 2024"));
+        corpus.add("b", builder.build().extend_from_text("Rome in 2025"));
+        let total = corpus.total();
+        assert_eq!(2, total.match_count("Rome"));
+        assert_eq!(1, total.match_count("2024"));
+        assert_eq!(3, total.extend_from_text("Rome").match_count("Rome"));
+        assert!(Corpus::new().total().is_empty());
+    }
+
+    #[test]
+    fn test_inverted_index() {
+        let mut corpus = Corpus::new();
+        corpus.add_text("a", "a cat, a mat");
+        corpus.add_text("b", "");
+        corpus.add_text("c", "the cat sat");
+        let index = corpus.inverted_index();
+        assert_eq!(&[(0, 1), (2, 1)], index.postings("cat"));
+        assert_eq!(&[(0, 2)], index.postings("a"));
+        assert!(index.postings("Cat").is_empty());
+        assert_eq!(2, index.document_frequency("cat"));
+        assert_eq!(vec![2], index.containing_all(&["sat", "cat"]));
+        assert!(index.containing_all(&[]).is_empty());
+        assert!(index.containing_all(&["mat", "sat"]).is_empty());
+        assert_eq!(vec![0, 2], index.containing_any(&["mat", "sat", "dog"]));
+        assert_eq!(
+            vec!["a", "cat", "mat", "sat", "the"],
+            index.words().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Saving and loading whole corpora, so that a large corpus
+//! need not be split into words again on every run.
+//!
+//! A corpus file starts with the magic bytes `BCRP` and a
+//! format version byte. Then comes the vocabulary: every
+//! word of the corpus with the number of documents
+//! containing it, stored as in [`Bbow::write_binary`]. Then
+//! come the number of documents and each document in turn:
+//! the length of its name, the name in UTF-8, its options,
+//! the number of its words, and each word as the difference
+//! between its index in the vocabulary and that of the word
+//! before, followed by its count. The options are a byte 1
+//! followed by the options as in [`Bbow::write_binary`], or
+//! a byte 0 if they are those of the document before, or
+//! for the first document the default ones. All numbers are
+//! unsigned LEB128 varints.
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::binary::{
+    invalid, read_entries, read_header, read_usize, write_entries, write_header, write_varint,
+};
+use crate::{BbowBuilder, Corpus};
+
+const MAGIC: &[u8; 4] = b"BCRP";
+const VERSION: u8 = 1;
+
+impl Corpus<'_> {
+    /// Save this corpus to the file at `path`, replacing
+    /// any file already there, for [`Corpus::load`] to read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Corpus;
+    /// let mut corpus = Corpus::new();
+    /// corpus.add_text("a", "red fish");
+    /// corpus.add_text("b", "blue fish");
+    /// let path = std::env::temp_dir().join(format!("bbow-doc-{}.corpus", std::process::id()));
+    /// corpus.save(&path).unwrap();
+    /// let loaded = Corpus::load(&path).unwrap();
+    /// assert_eq!("b", loaded.get(1).unwrap().name());
+    /// assert_eq!(2, loaded.total().match_count("fish"));
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut w = BufWriter::new(File::create(path)?);
+        self.write_corpus(&mut w)?;
+        w.flush()
+    }
+
+    /// Load a corpus saved by [`Corpus::save`] from the file
+    /// at `path`. The BBOWs of the documents have the
+    /// options they were saved with, except for their token
+    /// filters. A file that is not in that format is an
+    /// [`io::ErrorKind::InvalidData`] error.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Corpus<'static>> {
+        Corpus::read_corpus(BufReader::new(File::open(path)?))
+    }
+
+    fn write_corpus<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write_header(w, MAGIC, VERSION)?;
+        let index = self.inverted_index();
+        let vocabulary: Vec<(&str, usize)> = index
+            .words()
+            .map(|word| (word, index.document_frequency(word)))
+            .collect();
+        write_entries(w, vocabulary.len(), vocabulary.iter().copied())?;
+        write_varint(w, self.len() as u64)?;
+        let mut previous_options = Vec::new();
+        BbowBuilder::new().write_options(&mut previous_options)?;
+        for document in self.iter() {
+            let name = document.name().as_bytes();
+            write_varint(w, name.len() as u64)?;
+            w.write_all(name)?;
+            let bbow = document.bbow();
+            let mut options = Vec::new();
+            bbow.options.write_options(&mut options)?;
+            if options == previous_options {
+                w.write_all(&[0])?;
+            } else {
+                w.write_all(&[1])?;
+                w.write_all(&options)?;
+                previous_options = options;
+            }
+            write_varint(w, bbow.len() as u64)?;
+            // Both the vocabulary and the words of the
+            // document are in alphabetical order, so each
+            // word's index is found by walking forward.
+            let mut previous = 0;
+            let mut position = 0;
+            for (word, count) in bbow.iter() {
+                while vocabulary[position].0 != word {
+                    position += 1;
+                }
+                write_varint(w, (position - previous) as u64)?;
+                write_varint(w, count as u64)?;
+                previous = position;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_corpus<R: Read>(mut r: R) -> io::Result<Corpus<'static>> {
+        read_header(
+            &mut r,
+            MAGIC,
+            "not a BBOW corpus",
+            VERSION,
+            "unsupported BBOW corpus version",
+        )?;
+        let mut vocabulary = Vec::new();
+        read_entries(&mut r, |word, documents| {
+            vocabulary.push((word, documents));
+            Ok(())
+        })?;
+        let mut seen = vec![0; vocabulary.len()];
+        let n = read_usize(&mut r)?;
+        let mut corpus = Corpus::new();
+        let mut builder = BbowBuilder::new();
+        for _ in 0..n {
+            let length = read_usize(&mut r)?;
+            let mut name = Vec::new();
+            (&mut r).take(length as u64).read_to_end(&mut name)?;
+            if name.len() != length {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            let name = String::from_utf8(name).map_err(|_| invalid("name is not UTF-8"))?;
+            let mut options = [0];
+            r.read_exact(&mut options)?;
+            match options[0] {
+                0 => {}
+                1 => builder = BbowBuilder::read_options(&mut r)?,
+                _ => return Err(invalid("bad options marker")),
+            }
+            let mut bbow = builder.build();
+            let mut position: usize = 0;
+            for i in 0..read_usize(&mut r)? {
+                let gap = read_usize(&mut r)?;
+                if i > 0 && gap == 0 {
+                    return Err(invalid("words out of order"));
+                }
+                position = position
+                    .checked_add(gap)
+                    .filter(|&p| p < vocabulary.len())
+                    .ok_or_else(|| invalid("word not in vocabulary"))?;
+                let count = read_usize(&mut r)?;
+                if count == 0 {
+                    return Err(invalid("zero count"));
+                }
+                bbow.insert(vocabulary[position].0.clone(), count);
+                seen[position] += 1;
+            }
+            corpus.add(name, bbow);
+        }
+        if vocabulary.iter().zip(&seen).any(|(&(_, n), &m)| n != m) {
+            return Err(invalid("wrong document frequency"));
+        }
+        Ok(corpus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bbow;
+
+    fn round_trip(corpus: &Corpus) -> io::Result<Corpus<'static>> {
+        let mut bytes = Vec::new();
+        corpus.write_corpus(&mut bytes)?;
+        Corpus::read_corpus(bytes.as_slice())
+    }
+
+    #[test]
+    fn test_corpus_round_trip() {
+        let mut corpus = Corpus::new();
+        corpus.add_text("Chapter Ï", "It ain't over untïl it ain't, over.");
+        corpus.add_text("", "");
+        corpus.add_text("ch. 3", "Over and over: zebras, aardvarks.");
+        let loaded = round_trip(&corpus).unwrap();
+        assert_eq!(corpus.len(), loaded.len());
+        for (a, b) in corpus.iter().zip(loaded.iter()) {
+            assert_eq!(a.name(), b.name());
+            assert_eq!(a.bbow(), b.bbow());
+        }
+        assert!(round_trip(&Corpus::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_corpus_round_trip_options() {
+        let numbers = Bbow::builder().numbers(true);
+        let mut corpus = Corpus::new();
+        corpus.add_text("plain", "in twenty twenty-four");
+        corpus.add("a", numbers.build().extend_from_text("in 2024"));
+        corpus.add("b", numbers.build().extend_from_text("2024 and 2025"));
+        corpus.add("c", Bbow::new().extend_from_text("in 2026"));
+        let mut bytes = Vec::new();
+        corpus.write_corpus(&mut bytes).unwrap();
+        let loaded = Corpus::read_corpus(bytes.as_slice()).unwrap();
+        for (a, b) in corpus.iter().zip(loaded.iter()) {
+            assert_eq!(a.bbow(), b.bbow());
+        }
+        assert_eq!(1, loaded.get(1).unwrap().bbow().match_count("2024"));
+        assert_eq!(1, loaded.get(2).unwrap().bbow().match_count("2025"));
+        let c = loaded
+            .get(3)
+            .unwrap()
+            .bbow()
+            .clone()
+            .extend_from_text("2026");
+        assert_eq!(0, c.match_count("2026"));
+        // The options are written once for "a" and "b", and
+        // not at all for the default ones.
+        let mut options = Vec::new();
+        numbers.write_options(&mut options).unwrap();
+        let marked = [&[1][..], &options].concat();
+        let count = bytes.windows(marked.len()).filter(|w| *w == marked).count();
+        assert_eq!(1, count);
+    }
+
+    #[test]
+    fn test_corpus_round_trip_foreign_words() {
+        let mut bbow = Bbow::new().extend_from_text("a cat");
+        bbow.insert("Hello", 2);
+        bbow.merge(
+            &Bbow::builder()
+                .numbers(true)
+                .build()
+                .extend_from_text("2024"),
+        );
+        let mut corpus = Corpus::new();
+        corpus.add("mixed", bbow);
+        let loaded = round_trip(&corpus).unwrap();
+        assert_eq!(corpus.get(0).unwrap().bbow(), loaded.get(0).unwrap().bbow());
+    }
+
+    #[test]
+    fn test_read_corpus_errors() {
+        let bad: [&[u8]; 10] = [
+            b"BBOW\x01\x00\x00",
+            b"BCRP\x02\x00\x00",
+            b"BCRP\x01\x01\x00\x01a\x01\x01\x00\x02\x01\x00\x02",
+            b"BCRP\x01\x01\x00\x01a\x01\x01\x00\x01",
+            b"BCRP\x01\x01\x00\x01a\x01\x01\x00\x00\x01\x01\x01",
+            b"BCRP\x01\x01\x00\x01a\x01\x01\x00\x00\x01\x00\x00",
+            b"BCRP\x01\x01\x00\x01a\x01\x01\x00\x00\x02\x00\x01\x00\x01",
+            b"BCRP\x01\x01\x00\x01a\x02\x01\x00\x00\x01\x00\x01",
+            b"BCRP\x01\x00\x01\x02\xff\xfe\x00\x00",
+            b"BCRP\x01\x01\x00\x01a\x01\x01\x05ab",
+        ];
+        for bytes in bad {
+            assert!(Corpus::read_corpus(bytes).is_err(), "{bytes:?}");
+        }
+        let good = b"BCRP\x01\x01\x00\x01A\x01\x01\x00\x00\x01\x00\x02";
+        let corpus = Corpus::read_corpus(&good[..]).unwrap();
+        let words: Vec<_> = corpus.get(0).unwrap().bbow().iter().collect();
+        assert_eq!(vec![("A", 2)], words);
+    }
+
+    #[test]
+    fn test_save_load() {
+        let path = std::env::temp_dir().join(format!("bbow-corpus-{}", std::process::id()));
+        let corpus = Corpus::from_headings("# One\nfirst words\n# Two\nsecond words\n");
+        corpus.save(&path).unwrap();
+        let loaded = Corpus::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(2, loaded.unwrap().total().match_count("words"));
+        assert!(Corpus::load(&path).is_err());
+    }
+}
@@ -0,0 +1,279 @@
+//! CSV import and export of BBOWs.
+//!
+//! The format is a `word,count` header line followed by one
+//! `word,count` line per word. Words containing commas,
+//! double quotes or line breaks are quoted as usual for CSV,
+//! so a quoted word may run over several lines.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use crate::escape::csv_field;
+use crate::{Bbow, BbowBuilder};
+
+const HEADER: &str = "word,count";
+
+/// Why a CSV record could not be split into fields.
+#[derive(Debug, PartialEq, Eq)]
+enum Quoting {
+    /// A quoted field is still open at the end: the record
+    /// goes on on the next line.
+    Open,
+    /// Something other than a comma follows a quoted field.
+    Bad,
+}
+
+/// Split a CSV `record` into its (unquoted) fields.
+fn fields(record: &str) -> Result<Vec<String>, Quoting> {
+    let mut fields = Vec::new();
+    let mut chars = record.chars().peekable();
+    loop {
+        let mut field = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next().ok_or(Quoting::Open)? {
+                    '"' if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    '"' => break,
+                    c => field.push(c),
+                }
+            }
+            if !matches!(chars.peek(), None | Some(',')) {
+                return Err(Quoting::Bad);
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c == ',' {
+                    break;
+                }
+                field.push(c);
+                chars.next();
+            }
+        }
+        fields.push(field);
+        if chars.next().is_none() {
+            return Ok(fields);
+        }
+    }
+}
+
+fn invalid(line: usize, msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("line {line}: {msg}"))
+}
+
+impl Bbow<'_> {
+    /// Write this BBOW to `w` as CSV, one `word,count` line
+    /// per word in alphabetical order after a header line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("b a b");
+    /// let mut csv = Vec::new();
+    /// bbow.to_csv(&mut csv).unwrap();
+    /// assert_eq!("word,count\na,1\nb,2\n", String::from_utf8(csv).unwrap());
+    /// ```
+    pub fn to_csv<W: Write>(&self, mut w: W) -> io::Result<()> {
+        writeln!(w, "{HEADER}")?;
+        for (word, count) in &self.counts {
+            writeln!(w, "{},{count}", csv_field(word))?;
+        }
+        Ok(())
+    }
+
+    /// Read a BBOW with the default options from CSV in the
+    /// format written by [`Bbow::to_csv`]. The header line is
+    /// optional, blank lines are ignored, and the counts of
+    /// repeated words are added together. A line that is not
+    /// a word and a count, or whose count is 0 or makes the
+    /// total count of its word too large, is an
+    /// [`io::ErrorKind::InvalidData`] error. Words are kept
+    /// as written, as by [`Bbow::insert`]. A BBOW built with
+    /// other options is read back with [`Bbow::from_csv_with`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::from_csv("word,count\nfish,2\ncat,1\n".as_bytes()).unwrap();
+    /// assert_eq!(2, bbow.match_count("fish"));
+    /// assert_eq!(3, bbow.count());
+    /// assert!(Bbow::from_csv("fish,two\n".as_bytes()).is_err());
+    /// ```
+    pub fn from_csv<R: Read>(r: R) -> io::Result<Bbow<'static>> {
+        Bbow::from_csv_with(r, &BbowBuilder::new())
+    }
+
+    /// Read a BBOW with the options of `builder` from CSV,
+    /// as [`Bbow::from_csv`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::{Apostrophes, Bbow};
+    /// let builder = Bbow::builder().numbers(true).apostrophes(Apostrophes::Keep);
+    /// let csv = "word,count\n2024,3\nain't,2\n";
+    /// let bbow = Bbow::from_csv_with(csv.as_bytes(), &builder).unwrap();
+    /// assert_eq!(3, bbow.match_count("2024"));
+    /// assert_eq!(2, bbow.match_count("ain't"));
+    /// ```
+    pub fn from_csv_with<R: Read>(r: R, builder: &BbowBuilder) -> io::Result<Bbow<'static>> {
+        let mut bbow = builder.build();
+        // The record being read, and the index of its first
+        // line, while a quoted word in it runs over lines.
+        let mut open: Option<(String, usize)> = None;
+        let mut r = BufReader::new(r);
+        for j in 0.. {
+            // Not `lines`, which would drop a carriage return
+            // inside a quoted word.
+            let mut line = String::new();
+            if r.read_line(&mut line)? == 0 {
+                break;
+            }
+            if line.ends_with('\n') {
+                line.pop();
+            }
+            let (record, i) = match open.take() {
+                Some((mut record, i)) => {
+                    record.push('\n');
+                    record.push_str(&line);
+                    (record, i)
+                }
+                None => (line, j),
+            };
+            let line = record.trim_end_matches('\r');
+            if line.trim().is_empty() || (i == 0 && line == HEADER) {
+                continue;
+            }
+            let fields = match fields(line) {
+                Ok(fields) => fields,
+                Err(Quoting::Open) => {
+                    open = Some((record, i));
+                    continue;
+                }
+                Err(Quoting::Bad) => return Err(invalid(i + 1, "bad quoting")),
+            };
+            let [word, count] = <[String; 2]>::try_from(fields)
+                .map_err(|_| invalid(i + 1, "expected two fields"))?;
+            if word.is_empty() {
+                return Err(invalid(i + 1, "empty word"));
+            }
+            let count: usize = count
+                .trim()
+                .parse()
+                .map_err(|e| invalid(i + 1, &format!("bad count: {e}")))?;
+            if count == 0 {
+                return Err(invalid(i + 1, "zero count"));
+            }
+            let total = match bbow.counts.get(word.as_str()) {
+                Some(&total) => total.checked_add(count),
+                None => Some(count),
+            };
+            let total = total.ok_or_else(|| invalid(i + 1, "count too large"))?;
+            bbow.insert(word, total);
+        }
+        if let Some((_, i)) = open {
+            return Err(invalid(i + 1, "unterminated quote"));
+        }
+        Ok(bbow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields() {
+        assert_eq!(Ok(vec!["a".to_string(), "1".to_string()]), fields("a,1"));
+        assert_eq!(
+            Ok(vec!["a,\"b".to_string(), "2".to_string()]),
+            fields("\"a,\"\"b\",2"),
+        );
+        assert_eq!(Ok(vec!["".to_string()]), fields(""));
+        assert_eq!(Err(Quoting::Open), fields("\"unterminated,1"));
+        assert_eq!(Err(Quoting::Bad), fields("\"a\"b,1"));
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let bbow = Bbow::new().extend_from_text("It ain't over untïl it ain't, over.");
+        let mut csv = Vec::new();
+        bbow.to_csv(&mut csv).unwrap();
+        let read = Bbow::from_csv(csv.as_slice()).unwrap();
+        assert_eq!(bbow.counts, read.counts);
+        // Words can hold commas, quotes and line breaks only
+        // with options that let them.
+        let builder = Bbow::builder().inner_chars([',', '"', '\n', '\r']);
+        let mut bbow = builder.build().extend_from_text("odd,\"word\"");
+        for word in ["two\nlines", "\n\nblank\r\n", "cr\r", "\r\nword,count"] {
+            bbow.insert(word, 2);
+        }
+        let mut csv = Vec::new();
+        bbow.to_csv(&mut csv).unwrap();
+        let read = Bbow::from_csv_with(csv.as_slice(), &builder).unwrap();
+        assert_eq!(bbow.counts, read.counts);
+        assert_eq!(1, read.match_count("odd,\"word\""));
+    }
+
+    #[test]
+    fn test_csv_round_trip_foreign_words() {
+        let mut bbow = Bbow::new().extend_from_text("a cat");
+        bbow.insert("Hello", 2);
+        bbow.merge(
+            &Bbow::builder()
+                .numbers(true)
+                .build()
+                .extend_from_text("2024"),
+        );
+        let mut csv = Vec::new();
+        bbow.to_csv(&mut csv).unwrap();
+        let read = Bbow::from_csv(csv.as_slice()).unwrap();
+        assert_eq!(bbow, read);
+        assert!(read.contains("Hello") && read.contains("2024"));
+    }
+
+    #[test]
+    fn test_csv_round_trip_options() {
+        let builder = Bbow::builder()
+            .numbers(true)
+            .apostrophes(crate::Apostrophes::Keep);
+        let bbow = builder.build().extend_from_text("2024 2024 ain't so");
+        let mut csv = Vec::new();
+        bbow.to_csv(&mut csv).unwrap();
+        let read = Bbow::from_csv_with(csv.as_slice(), &builder).unwrap();
+        assert_eq!(bbow, read);
+        assert_eq!(2, read.match_count("2024"));
+        assert_eq!(1, read.match_count("ain't"));
+        assert_eq!(3, read.extend_from_text("2024").match_count("2024"));
+    }
+
+    #[test]
+    fn test_from_csv_errors() {
+        for bad in [
+            "a\n",
+            "a,1,2\n",
+            ",3\n",
+            "a,-1\n",
+            "a,0\n",
+            "word,count\nword,count\n",
+            "\"open\n,1\n",
+            "a,1\n\"b\nc\"d,1\n",
+        ] {
+            let err = Bbow::from_csv(bad.as_bytes()).unwrap_err();
+            assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        }
+    }
+
+    #[test]
+    fn test_from_csv_merges_repeats() {
+        let bbow = Bbow::from_csv("a,1\r\n\r\na,2\r\n".as_bytes()).unwrap();
+        assert_eq!(3, bbow.match_count("a"));
+        assert_eq!(1, bbow.len());
+        let err = Bbow::from_csv("word,count\na,18446744073709551615\na,1\n".as_bytes());
+        assert_eq!("line 3: count too large", err.unwrap_err().to_string());
+    }
+}
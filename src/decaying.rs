@@ -0,0 +1,159 @@
+//! A BBOW whose counts fade with time, for finding the
+//! words that are trending in a stream of texts.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use crate::{is_word, words_of};
+
+/// A BBOW of weights rather than counts, where each
+/// occurrence of a word weighs 1 when it is added and then
+/// decays exponentially, halving every `half_life` ticks.
+/// A tick is any measure of time the caller chooses, such
+/// as seconds or the number of texts seen, and must not go
+/// backwards.
+///
+/// Each word's weight is brought up to date only when the
+/// word is added or asked about, so adding text costs no
+/// more than for an ordinary BBOW.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::DecayingBbow;
+/// let bbow = DecayingBbow::new(10.0)
+///     .extend_from_text("old news, old news", 0)
+///     .extend_from_text("fresh news", 20);
+/// assert_eq!(0.5, bbow.weight("old", 20));
+/// assert_eq!(1.0, bbow.weight("fresh", 20));
+/// assert_eq!(vec![("news", 1.5), ("fresh", 1.0)], bbow.top_n(2, 20));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DecayingBbow<'a> {
+    half_life: f64,
+    /// Each word's weight as of the tick it was last added.
+    words: BTreeMap<Cow<'a, str>, (f64, u64)>,
+}
+
+impl<'a> DecayingBbow<'a> {
+    /// Make a new empty BBOW whose weights halve every
+    /// `half_life` ticks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `half_life` is not positive and finite: a
+    /// half-life of 0 would make every weight NaN, and a
+    /// negative one would make weights grow.
+    pub fn new(half_life: f64) -> Self {
+        assert!(
+            half_life > 0.0 && half_life.is_finite(),
+            "half-life must be positive and finite, not {half_life}"
+        );
+        Self {
+            half_life,
+            words: BTreeMap::new(),
+        }
+    }
+
+    /// `weight` as of `then`, decayed to `now`.
+    fn decayed(&self, (weight, then): (f64, u64), now: u64) -> f64 {
+        let elapsed = now.saturating_sub(then) as f64;
+        weight * (-elapsed / self.half_life).exp2()
+    }
+
+    /// Parse the `target` text and add its words, as
+    /// [`crate::Bbow::extend_from_text`] does, each with a
+    /// weight of 1 at tick `now`.
+    ///
+    /// This is a "builder method", like
+    /// [`crate::Bbow::extend_from_text`].
+    pub fn extend_from_text(mut self, target: &'a str, now: u64) -> Self {
+        for word in words_of(target) {
+            let (weight, then) = match self.words.get(&word) {
+                Some(&(weight, then)) => (self.decayed((weight, then), now), then.max(now)),
+                None => (0.0, now),
+            };
+            self.words.insert(word, (weight + 1.0, then));
+        }
+        self
+    }
+
+    /// The weight of `keyword` at tick `now`, or 0 if it is
+    /// not in this BBOW. The keyword is matched as by
+    /// [`crate::Bbow::match_count`].
+    pub fn weight(&self, keyword: &str, now: u64) -> f64 {
+        if !is_word(keyword) {
+            return 0.0;
+        }
+        self.words
+            .get(keyword)
+            .map_or(0.0, |&w| self.decayed(w, now))
+    }
+
+    /// The `n` words with the highest weights at tick `now`,
+    /// with their weights, highest first. Words with equal
+    /// weights are given in alphabetical order.
+    pub fn top_n(&self, n: usize, now: u64) -> Vec<(&str, f64)> {
+        let mut words: Vec<(&str, f64)> = self
+            .words
+            .iter()
+            .map(|(word, &w)| (word.as_ref(), self.decayed(w, now)))
+            .collect();
+        words.sort_by(|(w1, s1), (w2, s2)| s2.total_cmp(s1).then(w1.cmp(w2)));
+        words.truncate(n);
+        words
+    }
+
+    /// Remove the words whose weight at tick `now` has
+    /// fallen below `min_weight`, to keep the BBOW from
+    /// growing without end on a stream.
+    pub fn prune_below(&mut self, min_weight: f64, now: u64) {
+        let mut words = std::mem::take(&mut self.words);
+        words.retain(|_, &mut w| self.decayed(w, now) >= min_weight);
+        self.words = words;
+    }
+
+    /// Count the number of unique words contained in this
+    /// BBOW.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Is this BBOW empty?
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decaying() {
+        let mut bbow = DecayingBbow::new(2.0)
+            .extend_from_text("a a b", 0)
+            .extend_from_text("a", 2);
+        assert_eq!(2.0, bbow.weight("a", 2));
+        assert_eq!(0.5, bbow.weight("b", 2));
+        assert_eq!(0.5, bbow.weight("a", 6));
+        assert_eq!(2.0, bbow.weight("a", 1));
+        assert_eq!(0.0, bbow.weight("A", 2));
+        // A tick earlier than the last does not undo decay.
+        bbow = bbow.extend_from_text("a", 1);
+        assert_eq!(3.0, bbow.weight("a", 2));
+        assert_eq!(vec![("a", 3.0), ("b", 0.5)], bbow.top_n(5, 2));
+        bbow.prune_below(0.3, 4);
+        assert_eq!(1, bbow.len());
+        assert!(!bbow.is_empty());
+    }
+
+    #[test]
+    fn test_bad_half_life() {
+        for half_life in [0.0, -0.0, -2.0, f64::NAN, f64::INFINITY] {
+            let made = std::panic::catch_unwind(|| DecayingBbow::new(half_life));
+            assert!(made.is_err(), "{half_life}");
+        }
+        assert_eq!(0.0, DecayingBbow::new(f64::MIN_POSITIVE).weight("a", 0));
+    }
+}
@@ -0,0 +1,151 @@
+//! Incremental snapshots of BBOWs. Rather than writing
+//! out a large, slowly changing BBOW in full each time, a
+//! snapshot can be just the [`BbowDiff`] from the previous
+//! one, applied in turn to rebuild the latest BBOW.
+//!
+//! The format is that of [`Bbow::write_binary`] with the
+//! magic bytes `BDLT` and without the options: each entry is
+//! a word that changed, with its new count, or 0 if the word
+//! was removed.
+
+use std::borrow::Cow;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+
+use crate::binary::{read_entries, read_header, write_entries, write_header};
+use crate::{Bbow, BbowDiff};
+
+const MAGIC: &[u8; 4] = b"BDLT";
+const VERSION: u8 = 1;
+
+impl BbowDiff<'_> {
+    /// Write this difference to `w` in a compact binary
+    /// format that [`Bbow::apply_delta`] can read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let monday = Bbow::new().extend_from_text("red fish blue fish");
+    /// let tuesday = Bbow::new().extend_from_text("red fish one fish two fish");
+    /// let mut delta = Vec::new();
+    /// monday.diff(&tuesday).write_delta(&mut delta).unwrap();
+    /// let mut restored = monday.clone();
+    /// restored.apply_delta(delta.as_slice()).unwrap();
+    /// assert_eq!(tuesday, restored);
+    /// ```
+    pub fn write_delta<W: Write>(&self, w: W) -> io::Result<()> {
+        let mut entries: Vec<(&str, usize)> = self
+            .added()
+            .iter()
+            .copied()
+            .chain(self.removed().iter().map(|&(word, _)| (word, 0)))
+            .chain(self.changed().iter().map(|&(word, _, count)| (word, count)))
+            .collect();
+        entries.sort_unstable();
+        let mut w = BufWriter::new(w);
+        write_header(&mut w, MAGIC, VERSION)?;
+        write_entries(&mut w, entries.len(), entries.into_iter())?;
+        w.flush()
+    }
+}
+
+impl Bbow<'_> {
+    /// Read a difference written by
+    /// [`BbowDiff::write_delta`] from `r` and apply it to
+    /// this BBOW, setting the count of each word it lists:
+    /// a count of 0 removes the word. Words are kept as
+    /// written, as by [`Bbow::insert`]. Data that is not in
+    /// that format is an [`io::ErrorKind::InvalidData`]
+    /// error, and leaves this BBOW unchanged.
+    pub fn apply_delta<R: Read>(&mut self, r: R) -> io::Result<()> {
+        let mut r = BufReader::new(r);
+        read_header(
+            &mut r,
+            MAGIC,
+            "not a BBOW delta",
+            VERSION,
+            "unsupported BBOW delta version",
+        )?;
+        let mut entries = Vec::new();
+        read_entries(&mut r, |word, count| {
+            entries.push((word, count));
+            Ok(())
+        })?;
+        for (word, count) in entries {
+            self.insert(Cow::Owned(word), count);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delta_snapshots() {
+        let texts = ["a b b c", "b c c d", "", "über alles über"];
+        let mut previous = Bbow::new();
+        let mut restored = Bbow::new();
+        let mut current = Bbow::new();
+        for text in texts {
+            current = current.extend_from_text(text);
+            current.prune_below(2);
+            let mut delta = Vec::new();
+            previous.diff(&current).write_delta(&mut delta).unwrap();
+            restored.apply_delta(delta.as_slice()).unwrap();
+            assert_eq!(current, restored);
+            previous = current.clone();
+        }
+        let mut delta = Vec::new();
+        current.diff(&current).write_delta(&mut delta).unwrap();
+        assert_eq!(b"BDLT\x01\x00", delta.as_slice());
+    }
+
+    #[test]
+    fn test_apply_delta_errors() {
+        let mut bbow = Bbow::new().extend_from_text("a b");
+        let bad: [&[u8]; 5] = [
+            b"BBOW\x01\x00",
+            b"BDLT\x01\x02\x00\x01b\x00\x00\x01a\x01",
+            b"BDLT\x01\x02\x00\x01c\x00\x00",
+            b"BDLT\x02\x00",
+            b"BDLT\x01\x01\x00\x05c",
+        ];
+        for bytes in bad {
+            assert!(bbow.apply_delta(bytes).is_err(), "{bytes:?}");
+        }
+        assert_eq!(Bbow::new().extend_from_text("a b"), bbow);
+    }
+
+    #[test]
+    fn test_delta_options() {
+        let builder = Bbow::builder().numbers(true);
+        let old = builder.build().extend_from_text("in 2023");
+        let new = builder.build().extend_from_text("in 2024 2024");
+        let mut delta = Vec::new();
+        old.diff(&new).write_delta(&mut delta).unwrap();
+        let mut restored = old.clone();
+        restored.apply_delta(delta.as_slice()).unwrap();
+        assert_eq!(new, restored);
+        assert_eq!(2, restored.match_count("2024"));
+    }
+
+    #[test]
+    fn test_delta_foreign_words() {
+        let old = Bbow::new().extend_from_text("a cat");
+        let mut new = old.clone();
+        new.insert("Hello", 2);
+        new.merge(
+            &Bbow::builder()
+                .numbers(true)
+                .build()
+                .extend_from_text("2024"),
+        );
+        let mut delta = Vec::new();
+        old.diff(&new).write_delta(&mut delta).unwrap();
+        let mut restored = old.clone();
+        restored.apply_delta(delta.as_slice()).unwrap();
+        assert_eq!(new, restored);
+    }
+}
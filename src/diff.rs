@@ -0,0 +1,125 @@
+//! Differences between two BBOWs, such as successive
+//! snapshots of a growing collection of texts.
+
+use std::cmp::Ordering;
+use std::iter::Peekable;
+
+use crate::Bbow;
+
+/// The differences from one BBOW to another, from
+/// [`Bbow::diff`]. Each list is in alphabetical order of
+/// word.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BbowDiff<'b> {
+    added: Vec<(&'b str, usize)>,
+    removed: Vec<(&'b str, usize)>,
+    changed: Vec<(&'b str, usize, usize)>,
+}
+
+impl<'b> BbowDiff<'b> {
+    /// The words only in the new BBOW, with their counts.
+    pub fn added(&self) -> &[(&'b str, usize)] {
+        &self.added
+    }
+
+    /// The words only in the old BBOW, with their counts.
+    pub fn removed(&self) -> &[(&'b str, usize)] {
+        &self.removed
+    }
+
+    /// The words in both BBOWs with different counts, as
+    /// `(word, old count, new count)`.
+    pub fn changed(&self) -> &[(&'b str, usize, usize)] {
+        &self.changed
+    }
+
+    /// Are the two BBOWs the same?
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// The next entry of either `old` or `new`, in alphabetical
+/// order, as `(word, old count, new count)` with a count of
+/// 0 for a word missing from one side.
+fn next_pair<'b>(
+    old: &mut Peekable<impl Iterator<Item = (&'b str, usize)>>,
+    new: &mut Peekable<impl Iterator<Item = (&'b str, usize)>>,
+) -> Option<(&'b str, usize, usize)> {
+    let order = match (old.peek(), new.peek()) {
+        (None, None) => return None,
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (Some((o, _)), Some((n, _))) => o.cmp(n),
+    };
+    Some(match order {
+        Ordering::Less => {
+            let (word, count) = old.next()?;
+            (word, count, 0)
+        }
+        Ordering::Greater => {
+            let (word, count) = new.next()?;
+            (word, 0, count)
+        }
+        Ordering::Equal => {
+            let (word, old_count) = old.next()?;
+            let (_, new_count) = new.next()?;
+            (word, old_count, new_count)
+        }
+    })
+}
+
+impl Bbow<'_> {
+    /// The differences from this BBOW to the `other`, newer
+    /// one: the words added, the words removed, and the words
+    /// whose counts changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let monday = Bbow::new().extend_from_text("red fish blue fish");
+    /// let tuesday = Bbow::new().extend_from_text("red fish one fish two fish");
+    /// let diff = monday.diff(&tuesday);
+    /// assert_eq!(&[("one", 1), ("two", 1)], diff.added());
+    /// assert_eq!(&[("blue", 1)], diff.removed());
+    /// assert_eq!(&[("fish", 2, 3)], diff.changed());
+    /// assert!(monday.diff(&monday).is_empty());
+    /// ```
+    pub fn diff<'b>(&'b self, other: &'b Bbow) -> BbowDiff<'b> {
+        let mut diff = BbowDiff::default();
+        let mut old = self.iter().peekable();
+        let mut new = other.iter().peekable();
+        while let Some((word, old_count, new_count)) = next_pair(&mut old, &mut new) {
+            match (old_count, new_count) {
+                (0, count) => diff.added.push((word, count)),
+                (count, 0) => diff.removed.push((word, count)),
+                (o, n) if o != n => diff.changed.push((word, o, n)),
+                _ => (),
+            }
+        }
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let a = Bbow::new().extend_from_text("a b b c");
+        let empty = Bbow::new();
+        let diff = empty.diff(&a);
+        assert_eq!(&[("a", 1), ("b", 2), ("c", 1)], diff.added());
+        assert!(diff.removed().is_empty() && diff.changed().is_empty());
+        let diff = a.diff(&empty);
+        assert_eq!(&[("a", 1), ("b", 2), ("c", 1)], diff.removed());
+        let b = Bbow::new().extend_from_text("b c c d");
+        let diff = a.diff(&b);
+        assert_eq!(&[("d", 1)], diff.added());
+        assert_eq!(&[("a", 1)], diff.removed());
+        assert_eq!(&[("b", 2, 1), ("c", 1, 2)], diff.changed());
+        assert!(empty.diff(&empty).is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+//! Document-term matrices of corpora, for clustering, topic
+//! modeling and other work on many documents at once.
+
+use crate::{Corpus, Vocabulary};
+
+/// A matrix with a row for each document of a corpus and a
+/// column for each word of a [`Vocabulary`], holding the
+/// count of the word in the document. It is stored in
+/// compressed sparse row (CSR) form, as the three arrays
+/// that `scipy.sparse.csr_matrix` and similar libraries
+/// take: only the nonzero counts are kept.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::{Corpus, Vocabulary};
+/// let mut corpus = Corpus::new();
+/// corpus.add_text("a", "red fish red");
+/// corpus.add_text("b", "blue fish");
+/// let vocabulary = Vocabulary::from_words(["fish", "red", "blue"]);
+/// let matrix = corpus.to_document_term_matrix(&vocabulary);
+/// assert_eq!((2, 3), matrix.shape());
+/// assert_eq!(&[0, 2, 4], matrix.row_offsets());
+/// assert_eq!(&[0, 1, 0, 2], matrix.columns());
+/// assert_eq!(&[1, 2, 1, 1], matrix.values());
+/// assert_eq!(2, matrix.get(0, 1));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentTermMatrix {
+    shape: (usize, usize),
+    row_offsets: Vec<usize>,
+    columns: Vec<usize>,
+    values: Vec<usize>,
+}
+
+impl DocumentTermMatrix {
+    /// The number of rows (documents) and columns (words).
+    pub fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    /// Where each row starts in [`DocumentTermMatrix::columns`]
+    /// and [`DocumentTermMatrix::values`], followed by the
+    /// number of nonzero entries: row `i` is at
+    /// `row_offsets[i]..row_offsets[i + 1]`.
+    pub fn row_offsets(&self) -> &[usize] {
+        &self.row_offsets
+    }
+
+    /// The column of each nonzero entry, in order of column
+    /// within each row.
+    pub fn columns(&self) -> &[usize] {
+        &self.columns
+    }
+
+    /// The value of each nonzero entry.
+    pub fn values(&self) -> &[usize] {
+        &self.values
+    }
+
+    /// The nonzero entries of row `row` as `(column, count)`
+    /// pairs. A row out of range is empty.
+    pub fn row(&self, row: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let range = if row < self.shape.0 {
+            self.row_offsets[row]..self.row_offsets[row + 1]
+        } else {
+            0..0
+        };
+        self.columns[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.values[range].iter().copied())
+    }
+
+    /// The entry at `row` and `column`.
+    pub fn get(&self, row: usize, column: usize) -> usize {
+        self.row(row)
+            .find(|&(c, _)| c == column)
+            .map_or(0, |(_, count)| count)
+    }
+
+    /// The nonzero entries as `(row, column, count)`
+    /// triples, in row-major order, for libraries that take
+    /// coordinate form instead.
+    pub fn triplets(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        (0..self.shape.0).flat_map(move |row| self.row(row).map(move |(c, v)| (row, c, v)))
+    }
+
+    /// The number of nonzero entries.
+    pub fn nonzero(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl Corpus<'_> {
+    /// The [`DocumentTermMatrix`] of the documents of this
+    /// corpus over `vocabulary`. Words not in the vocabulary
+    /// are left out.
+    pub fn to_document_term_matrix(&self, vocabulary: &Vocabulary) -> DocumentTermMatrix {
+        let mut matrix = DocumentTermMatrix {
+            shape: (self.len(), vocabulary.len()),
+            row_offsets: vec![0],
+            ..Default::default()
+        };
+        for document in self.iter() {
+            let mut row: Vec<(usize, usize)> = document
+                .bbow()
+                .iter()
+                .filter_map(|(word, count)| Some((vocabulary.index_of(word)?, count)))
+                .collect();
+            row.sort_unstable();
+            for (column, count) in row {
+                matrix.columns.push(column);
+                matrix.values.push(count);
+            }
+            matrix.row_offsets.push(matrix.values.len());
+        }
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_term_matrix() {
+        let mut corpus = Corpus::new();
+        corpus.add_text("a", "b a b");
+        corpus.add_text("empty", "");
+        corpus.add_text("c", "c d");
+        let vocabulary = corpus.vocabulary(1);
+        let matrix = corpus.to_document_term_matrix(&vocabulary);
+        assert_eq!((3, 4), matrix.shape());
+        assert_eq!(4, matrix.nonzero());
+        assert_eq!(&[0, 2, 2, 4], matrix.row_offsets());
+        assert_eq!(0, matrix.row(1).count());
+        assert_eq!(0, matrix.row(3).count());
+        assert_eq!(0, matrix.get(0, 2));
+        assert_eq!(
+            vec![(0, 0, 1), (0, 1, 2), (2, 2, 1), (2, 3, 1)],
+            matrix.triplets().collect::<Vec<_>>()
+        );
+        let matrix = corpus.to_document_term_matrix(&Vocabulary::from_words(["d"]));
+        assert_eq!(vec![(2, 0, 1)], matrix.triplets().collect::<Vec<_>>());
+        let matrix = Corpus::new().to_document_term_matrix(&vocabulary);
+        assert_eq!(&[0], matrix.row_offsets());
+    }
+}
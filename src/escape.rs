@@ -0,0 +1,54 @@
+//! Quoting of words for the text formats that BBOWs are
+//! written in. The `bbow` tool compiles its own copy of this
+//! module, so these helpers need not be public.
+
+use std::borrow::Cow;
+
+/// `field` quoted for CSV if it needs it: if it holds a
+/// comma, a double quote or a line break.
+pub(crate) fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field.into()
+    }
+}
+
+/// `s` quoted as a JSON string.
+#[cfg_attr(not(feature = "serde_json"), allow(dead_code))]
+pub(crate) fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field() {
+        assert!(matches!(csv_field("plain"), Cow::Borrowed("plain")));
+        assert_eq!("\"a,\"\"b\"\"\"", csv_field("a,\"b\""));
+        assert_eq!("\"a\nb\"", csv_field("a\nb"));
+        assert_eq!("\"\r\"", csv_field("\r"));
+        assert_eq!("\"\"\"\"", csv_field("\""));
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!("\"\"", json_string(""));
+        assert_eq!(r#""ain't \"so\"""#, json_string("ain't \"so\""));
+        assert_eq!(r#""a\\b""#, json_string("a\\b"));
+        assert_eq!(r#""line\u000aüber""#, json_string("line\nüber"));
+    }
+}
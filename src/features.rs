@@ -0,0 +1,233 @@
+//! Feature selection: ranking the words of a labeled corpus
+//! by how much they say about the labels, to choose a
+//! vocabulary for a classifier such as [`crate::NaiveBayes`].
+
+use std::collections::BTreeMap;
+
+use crate::Corpus;
+
+/// How many labeled documents there are, with each label
+/// and with each word and label.
+struct Contingency<'c> {
+    /// Number of labeled documents.
+    documents: usize,
+    /// Number of documents with each label.
+    labels: BTreeMap<&'c str, usize>,
+    /// For each word, the number of documents containing it,
+    /// and how many of those have each label.
+    words: BTreeMap<&'c str, (usize, BTreeMap<&'c str, usize>)>,
+}
+
+impl<'c> Contingency<'c> {
+    fn new(corpus: &'c Corpus, labels: &[&'c str]) -> Self {
+        let mut table = Contingency {
+            documents: 0,
+            labels: BTreeMap::new(),
+            words: BTreeMap::new(),
+        };
+        for (document, &label) in corpus.iter().zip(labels) {
+            table.documents += 1;
+            *table.labels.entry(label).or_insert(0) += 1;
+            for word in document.bbow().words() {
+                let (total, by_label) = table.words.entry(word).or_default();
+                *total += 1;
+                *by_label.entry(label).or_insert(0) += 1;
+            }
+        }
+        table
+    }
+
+    /// For each word, the highest value of `score` over the
+    /// labels, highest first, then in alphabetical order.
+    /// `score` is given the counts of documents with the
+    /// word and label, with the word but not the label,
+    /// with the label but not the word, and with neither.
+    fn rank(&self, score: impl Fn(f64, f64, f64, f64) -> f64) -> Vec<(&'c str, f64)> {
+        let n = self.documents;
+        let mut ranked: Vec<(&str, f64)> = self
+            .words
+            .iter()
+            .map(|(&word, (with_word, by_label))| {
+                let best = self
+                    .labels
+                    .iter()
+                    .map(|(label, &with_label)| {
+                        let both = by_label.get(label).copied().unwrap_or(0);
+                        let word_only = with_word - both;
+                        let label_only = with_label - both;
+                        let neither = n - both - word_only - label_only;
+                        score(
+                            both as f64,
+                            word_only as f64,
+                            label_only as f64,
+                            neither as f64,
+                        )
+                    })
+                    .fold(0.0, f64::max);
+                (word, best)
+            })
+            .collect();
+        ranked.sort_by(|(w1, s1), (w2, s2)| s2.total_cmp(s1).then(w1.cmp(w2)));
+        ranked
+    }
+}
+
+/// The chi-square statistic of the 2×2 table of document
+/// counts `a` (word and label), `b` (word only), `c` (label
+/// only) and `d` (neither).
+fn chi2(a: f64, b: f64, c: f64, d: f64) -> f64 {
+    let denominator = (a + b) * (c + d) * (a + c) * (b + d);
+    if denominator == 0.0 {
+        return 0.0;
+    }
+    let n = a + b + c + d;
+    n * (a * d - b * c).powi(2) / denominator
+}
+
+/// The expected mutual information, in bits, between having
+/// the word and having the label, from the same table as
+/// [`chi2`]. Empty cells add nothing.
+fn mutual_information(a: f64, b: f64, c: f64, d: f64) -> f64 {
+    let n = a + b + c + d;
+    // Each cell, with the totals of its row and column.
+    let cells = [
+        (a, a + b, a + c),
+        (b, a + b, b + d),
+        (c, c + d, a + c),
+        (d, c + d, b + d),
+    ];
+    cells
+        .iter()
+        .filter(|&&(cell, _, _)| cell > 0.0)
+        .map(|&(cell, row, column)| cell / n * (n * cell / (row * column)).log2())
+        .sum::<f64>()
+        .max(0.0)
+}
+
+impl Corpus<'_> {
+    /// The words of this corpus ranked by the chi-square
+    /// statistic of their association with the document
+    /// `labels`, most associated first. Document `i` has
+    /// label `labels[i]`; documents without a label are left
+    /// out. A word scores the highest statistic over all
+    /// labels of the table of how many documents have or do
+    /// not have the word and the label.
+    ///
+    /// Keeping only the first words gives a smaller
+    /// vocabulary that still tells the labels apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Corpus;
+    /// let mut corpus = Corpus::new();
+    /// corpus.add_text("1", "win free money");
+    /// corpus.add_text("2", "free money today");
+    /// corpus.add_text("3", "meeting today");
+    /// corpus.add_text("4", "lunch meeting");
+    /// let ranked = corpus.chi2_features(&["spam", "spam", "ham", "ham"]);
+    /// assert_eq!(("free", 4.0), ranked[0]);
+    /// assert_eq!(("today", 0.0), ranked[ranked.len() - 1]);
+    /// ```
+    pub fn chi2_features<'c>(&'c self, labels: &[&'c str]) -> Vec<(&'c str, f64)> {
+        Contingency::new(self, labels).rank(chi2)
+    }
+
+    /// The words of this corpus ranked by the expected
+    /// mutual information, in bits, between containing the
+    /// word and having a label, most informative first. The
+    /// `labels` are as for [`Corpus::chi2_features`], and a
+    /// word scores its highest mutual information with any
+    /// one label. Unlike the chi-square statistic, mutual
+    /// information gives little weight to rare words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Corpus;
+    /// let mut corpus = Corpus::new();
+    /// corpus.add_text("1", "win free money");
+    /// corpus.add_text("2", "free money today");
+    /// corpus.add_text("3", "meeting today");
+    /// corpus.add_text("4", "lunch meeting");
+    /// let ranked = corpus.mutual_information(&["spam", "spam", "ham", "ham"]);
+    /// assert_eq!(("free", 1.0), ranked[0]);
+    /// assert_eq!(("today", 0.0), ranked[ranked.len() - 1]);
+    /// ```
+    pub fn mutual_information<'c>(&'c self, labels: &[&'c str]) -> Vec<(&'c str, f64)> {
+        Contingency::new(self, labels).rank(mutual_information)
+    }
+
+    /// The pointwise mutual information, in bits, of `word`
+    /// and `label` over the documents with `labels`, as for
+    /// [`Corpus::mutual_information`]: how much more often
+    /// than by chance a document with the word has the
+    /// label. Positive values mean the word suggests the
+    /// label. `None` if no document has both.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Corpus;
+    /// let mut corpus = Corpus::new();
+    /// corpus.add_text("1", "free money");
+    /// corpus.add_text("2", "free lunch");
+    /// corpus.add_text("3", "lunch meeting");
+    /// corpus.add_text("4", "team meeting");
+    /// let labels = ["spam", "spam", "ham", "ham"];
+    /// assert_eq!(Some(1.0), corpus.pointwise_mutual_information("free", "spam", &labels));
+    /// assert_eq!(Some(0.0), corpus.pointwise_mutual_information("lunch", "spam", &labels));
+    /// assert_eq!(None, corpus.pointwise_mutual_information("free", "ham", &labels));
+    /// ```
+    pub fn pointwise_mutual_information(
+        &self,
+        word: &str,
+        label: &str,
+        labels: &[&str],
+    ) -> Option<f64> {
+        let table = Contingency::new(self, labels);
+        let (with_word, by_label) = table.words.get(word)?;
+        let both = *by_label.get(label)?;
+        let with_label = table.labels[label];
+        let n = table.documents as f64;
+        Some((n * both as f64 / (*with_word as f64 * with_label as f64)).log2())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chi2() {
+        assert_eq!(0.0, chi2(0.0, 0.0, 0.0, 0.0));
+        assert_eq!(0.0, chi2(1.0, 1.0, 1.0, 1.0));
+        assert_eq!(2.0, chi2(1.0, 0.0, 0.0, 1.0));
+        assert!((chi2(10.0, 20.0, 30.0, 40.0) - 0.7937).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_mutual_information() {
+        assert_eq!(0.0, mutual_information(1.0, 1.0, 1.0, 1.0));
+        assert_eq!(1.0, mutual_information(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(1.0, mutual_information(0.0, 1.0, 1.0, 0.0));
+        let mi = mutual_information(49.0, 27_652.0, 141.0, 774_106.0);
+        assert!((mi - 0.0001105).abs() < 1e-6, "{mi}");
+    }
+
+    #[test]
+    fn test_chi2_features() {
+        let mut corpus = Corpus::new();
+        corpus.add_text("1", "a b");
+        corpus.add_text("2", "a c");
+        corpus.add_text("3", "b c");
+        corpus.add_text("unlabeled", "d");
+        let ranked = corpus.chi2_features(&["x", "x", "y"]);
+        assert_eq!(vec![("a", 3.0), ("b", 0.75), ("c", 0.75)], ranked);
+        assert!(corpus.chi2_features(&[]).is_empty());
+        assert_eq!(
+            vec![("a", 0.0), ("b", 0.0), ("c", 0.0)],
+            corpus.chi2_features(&["x", "x", "x"])
+        );
+    }
+}
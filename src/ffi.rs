@@ -0,0 +1,104 @@
+//! A C interface, so that a BBOW can be used from C or C++.
+//! The declarations are in `include/bbow.h`; link against
+//! the `bbow` shared library built with the `ffi` feature.
+//!
+//! A BBOW is an opaque pointer made by `bbow_new` and
+//! released by `bbow_free`. Strings are NUL-terminated
+//! UTF-8, and are copied, so the caller may free them as
+//! soon as a call returns.
+
+use std::ffi::{c_char, c_int, CStr};
+
+use crate::Bbow;
+
+/// The BBOW behind a `bbow_t *`.
+pub type BbowHandle = Bbow<'static>;
+
+/// Make a new empty BBOW, to be released with `bbow_free`.
+#[no_mangle]
+pub extern "C" fn bbow_new() -> *mut BbowHandle {
+    Box::into_raw(Box::new(Bbow::new()))
+}
+
+/// Add the words of the NUL-terminated `text` to `bbow`.
+/// Returns 0 on success, or -1 if either pointer is null or
+/// `text` is not UTF-8, in which case `bbow` is unchanged.
+///
+/// # Safety
+///
+/// `bbow` must be null or come from `bbow_new` and not yet
+/// have been freed, and `text` must be null or point to a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn bbow_extend_text(bbow: *mut BbowHandle, text: *const c_char) -> c_int {
+    if bbow.is_null() || text.is_null() {
+        return -1;
+    }
+    let Ok(text) = CStr::from_ptr(text).to_str() else {
+        return -1;
+    };
+    let words = Bbow::new().extend_from_text(text).into_owned();
+    (*bbow).merge(&words);
+    0
+}
+
+/// The number of occurrences of the NUL-terminated `keyword`
+/// in `bbow`, as [`Bbow::match_count`] gives it. Returns 0
+/// if either pointer is null or `keyword` is not UTF-8.
+///
+/// # Safety
+///
+/// As for [`bbow_extend_text`].
+#[no_mangle]
+pub unsafe extern "C" fn bbow_match_count(
+    bbow: *const BbowHandle,
+    keyword: *const c_char,
+) -> usize {
+    if bbow.is_null() || keyword.is_null() {
+        return 0;
+    }
+    match CStr::from_ptr(keyword).to_str() {
+        Ok(keyword) => (*bbow).match_count(keyword),
+        Err(_) => 0,
+    }
+}
+
+/// Release `bbow`. Null is ignored.
+///
+/// # Safety
+///
+/// `bbow` must be null or come from `bbow_new` and not yet
+/// have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn bbow_free(bbow: *mut BbowHandle) {
+    if !bbow.is_null() {
+        drop(Box::from_raw(bbow));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+    use std::ptr;
+
+    #[test]
+    fn test_ffi() {
+        let text = CString::new("It ain't over untïl it ain't, over.").unwrap();
+        let over = CString::new("over").unwrap();
+        unsafe {
+            let bbow = bbow_new();
+            assert_eq!(0, bbow_extend_text(bbow, text.as_ptr()));
+            assert_eq!(0, bbow_extend_text(bbow, text.as_ptr()));
+            assert_eq!(4, bbow_match_count(bbow, over.as_ptr()));
+            assert_eq!(-1, bbow_extend_text(bbow, ptr::null()));
+            assert_eq!(-1, bbow_extend_text(ptr::null_mut(), text.as_ptr()));
+            let invalid = [0xffu8 as c_char, 0];
+            assert_eq!(-1, bbow_extend_text(bbow, invalid.as_ptr()));
+            assert_eq!(0, bbow_match_count(bbow, invalid.as_ptr()));
+            assert_eq!(0, bbow_match_count(ptr::null(), over.as_ptr()));
+            bbow_free(bbow);
+            bbow_free(ptr::null_mut());
+        }
+    }
+}
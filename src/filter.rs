@@ -0,0 +1,118 @@
+//! Steps that transform or drop each word as it is counted,
+//! for composing a pipeline with
+//! [`BbowBuilder::filter`](crate::BbowBuilder::filter).
+
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::panic::RefUnwindSafe;
+
+use crate::builder::map_cow;
+use crate::lowercase;
+
+/// One step of the pipeline that each word goes through
+/// before it is counted. A filter may rewrite the word, or
+/// drop it by returning `None`. Filters are added to a BBOW
+/// with [`BbowBuilder::filter`](crate::BbowBuilder::filter),
+/// and run in the order they were added. Filters must be
+/// [`RefUnwindSafe`], so that a BBOW can still be used
+/// across [`std::panic::catch_unwind`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::borrow::Cow;
+/// # use bbow::{Bbow, TokenFilter};
+/// /// Count British spellings as American ones.
+/// #[derive(Debug)]
+/// struct Americanize;
+///
+/// impl TokenFilter for Americanize {
+///     fn apply<'t>(&self, word: Cow<'t, str>) -> Option<Cow<'t, str>> {
+///         match word.as_ref() {
+///             "colour" => Some(Cow::Borrowed("color")),
+///             _ => Some(word),
+///         }
+///     }
+/// }
+///
+/// let bbow = Bbow::builder()
+///     .filter(Americanize)
+///     .build()
+///     .extend_from_text("Colour, color.");
+/// assert_eq!(2, bbow.match_count("color"));
+/// ```
+pub trait TokenFilter: Debug + Send + Sync + RefUnwindSafe {
+    /// `word` as it is to be counted, or `None` if it is to
+    /// be left out.
+    fn apply<'t>(&self, word: Cow<'t, str>) -> Option<Cow<'t, str>>;
+}
+
+/// Lowercase each word, for a BBOW built case sensitive
+/// so that the filters before this one see each word as it
+/// was written.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Lowercase;
+
+impl TokenFilter for Lowercase {
+    fn apply<'t>(&self, word: Cow<'t, str>) -> Option<Cow<'t, str>> {
+        Some(map_cow(word, lowercase))
+    }
+}
+
+/// Drop words shorter than the given number of characters.
+/// Unlike [`min_word_len`](crate::BbowBuilder::min_word_len),
+/// this can go anywhere in the pipeline, for example after
+/// stemming.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::{Bbow, MinLen, Stemmer};
+/// let bbow = Bbow::builder()
+///     .filter(Stemmer::english())
+///     .filter(MinLen(3))
+///     .build()
+///     .extend_from_text("He is running to the ponies");
+/// assert_eq!(vec!["poni", "run", "the"], bbow.words().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MinLen(pub usize);
+
+impl TokenFilter for MinLen {
+    fn apply<'t>(&self, word: Cow<'t, str>) -> Option<Cow<'t, str>> {
+        (word.chars().count() >= self.0).then_some(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bbow;
+
+    #[test]
+    fn test_filters() {
+        assert_eq!(Some(Cow::Borrowed("abc")), Lowercase.apply("ABC".into()));
+        assert_eq!(None, MinLen(4).apply("año".into()));
+        assert_eq!(Some(Cow::Borrowed("año")), MinLen(3).apply("año".into()));
+        let bbow = Bbow::builder()
+            .case_sensitive(true)
+            .filter(MinLen(2))
+            .filter(Lowercase)
+            .build()
+            .extend_from_text("A Dog, a dog, I do");
+        assert_eq!(vec![("dog", 2), ("do", 1)], bbow.top_n(10));
+    }
+
+    #[test]
+    fn test_unwind_safe() {
+        fn unwind_safe<T: std::panic::UnwindSafe + RefUnwindSafe>() {}
+        unwind_safe::<Bbow>();
+        unwind_safe::<crate::BbowBuilder>();
+        let bbow = Bbow::builder()
+            .filter(MinLen(2))
+            .build()
+            .extend_from_text("a bb");
+        let len = std::panic::catch_unwind(|| bbow.len());
+        assert_eq!(1, len.unwrap());
+    }
+}
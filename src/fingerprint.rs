@@ -0,0 +1,78 @@
+//! Stable fingerprints of BBOWs.
+
+use crate::Bbow;
+
+/// The 64-bit FNV-1a hash of `word` followed by `count`.
+fn fnv1a(word: &str, count: usize) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let mut hash = OFFSET;
+    // The 0xff byte cannot occur in UTF-8, so it separates
+    // the word from the count unambiguously.
+    for &byte in word.as_bytes().iter().chain(&[0xff]) {
+        hash = (hash ^ u64::from(byte)).wrapping_mul(PRIME);
+    }
+    for byte in (count as u64).to_le_bytes() {
+        hash = (hash ^ u64::from(byte)).wrapping_mul(PRIME);
+    }
+    hash
+}
+
+/// Spread the bits of `hash`, as the SplitMix64 finalizer
+/// does, so that sums of the hashes of similar entries do
+/// not collide easily.
+fn mix(mut hash: u64) -> u64 {
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
+}
+
+impl Bbow<'_> {
+    /// A 64-bit fingerprint of the words and counts of this
+    /// BBOW. Unlike [`std::hash::Hash`], the fingerprint is
+    /// the same in every process, machine and version of
+    /// this crate, so it can be stored, or used to compare
+    /// BBOWs held in different places or as a cache key.
+    /// Each word and count is hashed separately and the
+    /// hashes are added up, so the fingerprint does not
+    /// depend on the order in which words were counted, but
+    /// does change with any count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let a = Bbow::new().extend_from_text("one fish two fish");
+    /// let b = Bbow::new().extend_from_text("fish two fish one");
+    /// let c = Bbow::new().extend_from_text("one fish two fish fish");
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    /// assert_ne!(a.fingerprint(), c.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        self.iter()
+            .map(|(word, count)| mix(fnv1a(word, count)))
+            .fold(0, u64::wrapping_add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(0, Bbow::new().fingerprint());
+        // A fixed value, which must not change between
+        // versions.
+        let fish = Bbow::new().extend_from_text("one fish two fish");
+        assert_eq!(0xf05d_3ccd_f650_a2bf, fish.fingerprint());
+        let a = Bbow::new().extend_from_text("ab c");
+        let b = Bbow::new().extend_from_text("a bc");
+        let c = Bbow::new().extend_from_text("ab ab");
+        assert_ne!(a.fingerprint(), b.fingerprint());
+        assert_ne!(a.fingerprint(), c.fingerprint());
+        let mut merged = Bbow::new().extend_from_text("c");
+        merged.merge(&Bbow::new().extend_from_text("ab"));
+        assert_eq!(a.fingerprint(), merged.fingerprint());
+    }
+}
@@ -0,0 +1,178 @@
+//! A read-only BBOW that is cheap to share between threads.
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use crate::{Bbow, BbowBuilder};
+
+/// The words and counts of a [`FrozenBbow`].
+#[derive(Debug)]
+struct Frozen {
+    /// All the words, in alphabetical order, one after the
+    /// other.
+    text: String,
+    /// For each word in alphabetical order, the end of the
+    /// word in `text` and its count.
+    entries: Vec<(usize, usize)>,
+    /// The overall number of words.
+    count: usize,
+    /// The options of the BBOW this was made from.
+    options: BbowBuilder,
+}
+
+/// A BBOW that can no longer change, made by
+/// [`Bbow::freeze`]. The words are kept in one string and
+/// looked up by binary search in a sorted array, which is
+/// compact and fast to read. Clones share the words, so
+/// handing a copy to each thread of a server costs only a
+/// reference count.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::Bbow;
+/// let frozen = Bbow::new().extend_from_text("one fish two fish").freeze();
+/// let shared = frozen.clone();
+/// let count = std::thread::spawn(move || shared.match_count("fish")).join().unwrap();
+/// assert_eq!(2, count);
+/// assert_eq!(vec![("fish", 2), ("one", 1), ("two", 1)], frozen.iter().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrozenBbow(Arc<Frozen>);
+
+impl FrozenBbow {
+    /// The `i`th word in alphabetical order.
+    fn word(&self, i: usize) -> &str {
+        let start = if i == 0 { 0 } else { self.0.entries[i - 1].0 };
+        &self.0.text[start..self.0.entries[i].0]
+    }
+
+    /// The index of `word`, if it is here.
+    fn find(&self, word: &str) -> Option<usize> {
+        let (mut low, mut high) = (0, self.0.entries.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.word(middle).cmp(word) {
+                Ordering::Less => low = middle + 1,
+                Ordering::Greater => high = middle,
+                Ordering::Equal => return Some(middle),
+            }
+        }
+        None
+    }
+
+    /// Report the number of occurrences of the given
+    /// `keyword` in this BBOW, as [`Bbow::match_count`]
+    /// does under the options of the BBOW this was made from.
+    pub fn match_count(&self, keyword: &str) -> usize {
+        if !self.0.options.is_token(keyword) {
+            return 0;
+        }
+        self.find(keyword).map_or(0, |i| self.0.entries[i].1)
+    }
+
+    /// The words in this BBOW with their counts, in
+    /// alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        (0..self.len()).map(|i| (self.word(i), self.0.entries[i].1))
+    }
+
+    /// The words in this BBOW, in alphabetical order.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        (0..self.len()).map(|i| self.word(i))
+    }
+
+    /// Count the overall number of words contained in this
+    /// BBOW: multiple occurrences are considered separate.
+    pub fn count(&self) -> usize {
+        self.0.count
+    }
+
+    /// Count the number of unique words contained in this
+    /// BBOW.
+    pub fn len(&self) -> usize {
+        self.0.entries.len()
+    }
+
+    /// Is this BBOW empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.entries.is_empty()
+    }
+
+    /// An ordinary BBOW of the same words, counts and
+    /// options, which can be changed.
+    pub fn thaw(&self) -> Bbow<'static> {
+        let mut bbow = self.0.options.build();
+        for (word, count) in self.iter() {
+            bbow.insert(word.to_string(), count);
+        }
+        bbow
+    }
+}
+
+impl Bbow<'_> {
+    /// A read-only copy of the words and counts of this BBOW
+    /// for sharing between threads.
+    pub fn freeze(&self) -> FrozenBbow {
+        let mut text = String::with_capacity(self.counts.keys().map(|w| w.len()).sum());
+        let mut entries = Vec::with_capacity(self.len());
+        for (word, count) in self.iter() {
+            text.push_str(word);
+            entries.push((text.len(), count));
+        }
+        FrozenBbow(Arc::new(Frozen {
+            text,
+            entries,
+            count: self.count(),
+            options: self.options.clone(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_frozen_matches_bbow() {
+        assert_send_sync::<FrozenBbow>();
+        let bbow = Bbow::new().extend_from_text("It ain't over untïl it ain't, over. a b ab ba");
+        let frozen = bbow.freeze();
+        assert_eq!(bbow.len(), frozen.len());
+        assert_eq!(bbow.count(), frozen.count());
+        for (word, count) in bbow.iter() {
+            assert_eq!(count, frozen.match_count(word), "{word}");
+        }
+        assert_eq!(0, frozen.match_count("Over"));
+        assert_eq!(0, frozen.match_count("abc"));
+        assert_eq!(0, frozen.match_count("aa"));
+        assert_eq!(bbow, frozen.thaw());
+        assert_eq!(
+            bbow.words().collect::<Vec<_>>(),
+            frozen.words().collect::<Vec<_>>()
+        );
+        let empty = Bbow::new().freeze();
+        assert!(empty.is_empty());
+        assert_eq!(0, empty.match_count("a"));
+    }
+
+    #[test]
+    fn test_frozen_keeps_options() {
+        let bbow = Bbow::builder()
+            .numbers(true)
+            .build()
+            .extend_from_text("2024 and 2024");
+        let frozen = bbow.freeze();
+        assert_eq!(2, frozen.match_count("2024"));
+        assert_eq!(2, frozen.thaw().match_count("2024"));
+        assert_eq!(
+            0,
+            Bbow::new()
+                .extend_from_text("2024")
+                .freeze()
+                .match_count("2024")
+        );
+    }
+}
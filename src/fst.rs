@@ -0,0 +1,94 @@
+//! Export of BBOWs as finite-state transducers, a compact
+//! read-only form that can be searched by automata.
+
+use std::string::FromUtf8Error;
+
+use fst::{Map, MapBuilder, Streamer};
+
+use crate::Bbow;
+
+impl Bbow<'_> {
+    /// This BBOW as an [`fst::Map`] from each word to its
+    /// count. The transducer shares both the beginnings and
+    /// the endings of words, so it is usually much smaller
+    /// than the BBOW, and it can be written out and searched
+    /// in place with the queries of the `fst` crate: ranges
+    /// of words, or any [`fst::Automaton`], such as a prefix
+    /// or a regular expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// use fst::{Automaton, IntoStreamer, Streamer};
+    ///
+    /// let bbow = Bbow::new().extend_from_text("one fish two fish red fish blue fish");
+    /// let map = bbow.to_fst();
+    /// assert_eq!(Some(4), map.get("fish"));
+    ///
+    /// let mut range = map.range().ge("f").lt("s").into_stream();
+    /// let mut words = Vec::new();
+    /// while let Some((word, count)) = range.next() {
+    ///     words.push((String::from_utf8(word.to_vec()).unwrap(), count));
+    /// }
+    /// assert_eq!(vec![("fish".to_string(), 4), ("one".to_string(), 1), ("red".to_string(), 1)], words);
+    ///
+    /// let prefix = fst::automaton::Str::new("t").starts_with();
+    /// assert_eq!(1, map.search(prefix).into_stream().into_str_keys().unwrap().len());
+    /// ```
+    pub fn to_fst(&self) -> Map<Vec<u8>> {
+        let mut builder = MapBuilder::memory();
+        for (word, count) in self.iter() {
+            builder
+                .insert(word, count as u64)
+                .expect("the words of a BBOW are in order and distinct");
+        }
+        builder.into_map()
+    }
+
+    /// A BBOW of the words and counts of an [`fst::Map`],
+    /// such as one made by [`Bbow::to_fst`]. A key that is
+    /// not UTF-8 is an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("b a b");
+    /// assert_eq!(bbow, Bbow::from_fst(&bbow.to_fst()).unwrap());
+    /// ```
+    pub fn from_fst<D: AsRef<[u8]>>(map: &Map<D>) -> Result<Bbow<'static>, FromUtf8Error> {
+        let mut bbow = Bbow::new();
+        let mut entries = map.stream();
+        while let Some((word, count)) = entries.next() {
+            let count = usize::try_from(count).unwrap_or(usize::MAX);
+            bbow.insert(String::from_utf8(word.to_vec())?, count);
+        }
+        Ok(bbow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fst_round_trip() {
+        let bbow = Bbow::new().extend_from_text("It ain't over untïl it ain't, over. Ça va");
+        let map = bbow.to_fst();
+        assert_eq!(bbow.len(), map.len());
+        assert_eq!(Some(1), map.get("untïl"));
+        assert_eq!(None, map.get("ain't"));
+        assert_eq!(bbow, Bbow::from_fst(&map).unwrap());
+        let bytes = map.into_fst().into_inner();
+        let read = Map::new(bytes).unwrap();
+        assert_eq!(bbow, Bbow::from_fst(&read).unwrap());
+        assert!(Bbow::new().to_fst().is_empty());
+    }
+
+    #[test]
+    fn test_from_fst_invalid_utf8() {
+        let map = Map::from_iter([(&[0xff_u8][..], 1)]).unwrap();
+        assert!(Bbow::from_fst(&map).is_err());
+    }
+}
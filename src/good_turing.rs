@@ -0,0 +1,216 @@
+//! Good-Turing estimates of word probabilities, which set
+//! aside some probability for words not yet seen.
+
+use std::collections::BTreeMap;
+
+use crate::Bbow;
+
+/// Simple Good-Turing estimates (Gale and Sampson, 1995)
+/// from the [frequency spectrum](Bbow::frequency_spectrum)
+/// of a BBOW, from [`Bbow::good_turing`].
+///
+/// A word seen `r` times gets the adjusted count `r*`, usually
+/// less than `r`: the count taken from the seen words is
+/// the probability of meeting a new word, estimated as the
+/// fraction of the text made of words seen once. For small
+/// `r` the adjusted count is Turing's `(r + 1) N(r + 1) /
+/// N(r)`, where `N(r)` is the number of words seen `r`
+/// times. Once that becomes unreliable, it is taken instead
+/// from a line fitted to `log N(r)` against `log r`.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::Bbow;
+/// let bbow = Bbow::new().extend_from_text("a a a b b c d e f");
+/// let estimates = bbow.good_turing();
+/// // Four of the nine words are seen once.
+/// assert_eq!(4.0 / 9.0, estimates.unseen_probability());
+/// assert!(estimates.adjusted_count(1).unwrap() < 1.0);
+/// let total = estimates.unseen_probability()
+///     + 4.0 * estimates.probability(1).unwrap()
+///     + estimates.probability(2).unwrap()
+///     + estimates.probability(3).unwrap();
+/// assert!((total - 1.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoodTuring {
+    unseen: f64,
+    /// Each count seen, with its adjusted count and the
+    /// probability of each word with that count.
+    estimates: BTreeMap<usize, (f64, f64)>,
+}
+
+impl GoodTuring {
+    /// The probability that the next word is one not yet
+    /// seen, shared among all such words: the fraction of
+    /// the text made of words seen once. This is 0 for an
+    /// empty BBOW.
+    pub fn unseen_probability(&self) -> f64 {
+        self.unseen
+    }
+
+    /// The adjusted count `r*` of the words seen `count`
+    /// times, or `None` if no word is seen that many times.
+    pub fn adjusted_count(&self, count: usize) -> Option<f64> {
+        self.estimates.get(&count).map(|&(adjusted, _)| adjusted)
+    }
+
+    /// The probability of each one of the words seen `count`
+    /// times, or `None` if no word is seen that many times.
+    /// These probabilities are scaled so that, with
+    /// [`GoodTuring::unseen_probability`], they sum to 1 over
+    /// all the words.
+    pub fn probability(&self, count: usize) -> Option<f64> {
+        self.estimates
+            .get(&count)
+            .map(|&(_, probability)| probability)
+    }
+}
+
+/// The fit `(intercept, slope)` of `log z` against `log r`
+/// by least squares, or `None` with fewer than two points.
+fn fit(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let sxy: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let slope = sxy / sxx;
+    Some((mean_y - slope * mean_x, slope))
+}
+
+impl Bbow<'_> {
+    /// Simple Good-Turing estimates of the probabilities of
+    /// the words of this BBOW, and of a word not in it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat saw the dog");
+    /// let estimates = bbow.good_turing();
+    /// assert_eq!(0.6, estimates.unseen_probability());
+    /// assert_eq!(None, estimates.probability(3));
+    /// ```
+    pub fn good_turing(&self) -> GoodTuring {
+        let spectrum: Vec<(usize, usize)> = self.frequency_spectrum().into_iter().collect();
+        let total = self.count() as f64;
+        if spectrum.is_empty() {
+            return GoodTuring {
+                unseen: 0.0,
+                estimates: BTreeMap::new(),
+            };
+        }
+        let frequency = |r: usize| {
+            spectrum
+                .binary_search_by_key(&r, |&(r, _)| r)
+                .map_or(0.0, |i| spectrum[i].1 as f64)
+        };
+
+        // Average each N(r) over the gap to its neighbouring
+        // counts, so that the missing counts are allowed for
+        // in the fit.
+        let points: Vec<(f64, f64)> = spectrum
+            .iter()
+            .enumerate()
+            .map(|(i, &(r, n))| {
+                let q = if i == 0 { 0 } else { spectrum[i - 1].0 };
+                let t = spectrum.get(i + 1).map_or(2 * r - q, |&(t, _)| t);
+                let z = 2.0 * n as f64 / (t - q) as f64;
+                ((r as f64).ln(), z.ln())
+            })
+            .collect();
+        let slope = fit(&points).map(|(_, slope)| slope);
+
+        let mut smoothing = false;
+        let mut adjusted = Vec::with_capacity(spectrum.len());
+        for &(r, n) in &spectrum {
+            let r = r as f64;
+            let n = n as f64;
+            let fitted = slope.map_or(r, |b| (r + 1.0) * (1.0 + 1.0 / r).powf(b));
+            let next = frequency(r as usize + 1);
+            if !smoothing && next > 0.0 {
+                let turing = (r + 1.0) * next / n;
+                let deviation =
+                    1.96 * ((r + 1.0).powi(2) * next / (n * n) * (1.0 + next / n)).sqrt();
+                if (turing - fitted).abs() > deviation {
+                    adjusted.push(turing);
+                    continue;
+                }
+            }
+            smoothing = true;
+            adjusted.push(fitted);
+        }
+
+        let unseen = frequency(1) / total;
+        let adjusted_total: f64 = spectrum
+            .iter()
+            .zip(&adjusted)
+            .map(|(&(_, n), r)| n as f64 * r)
+            .sum();
+        let estimates = spectrum
+            .iter()
+            .zip(adjusted)
+            .map(|(&(r, _), adjusted)| {
+                let probability = (1.0 - unseen) * adjusted / adjusted_total;
+                (r, (adjusted, probability))
+            })
+            .collect();
+        GoodTuring { unseen, estimates }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_good_turing() {
+        assert_eq!(0.0, Bbow::new().good_turing().unseen_probability());
+        assert_eq!(None, Bbow::new().good_turing().adjusted_count(1));
+
+        // 300 words seen once, 60 twice, 30 three times and
+        // 5 seven times.
+        let mut bbow = Bbow::new();
+        let counts = [(1, 300), (2, 60), (3, 30), (7, 5)];
+        for (i, count) in counts
+            .iter()
+            .flat_map(|&(r, n)| std::iter::repeat_n(r, n))
+            .enumerate()
+        {
+            bbow.insert(format!("w{i}"), count);
+        }
+        let estimates = bbow.good_turing();
+        assert_eq!(300.0 / 545.0, estimates.unseen_probability());
+        // Turing's estimate for the words seen once, which
+        // is far enough from the fitted line to use.
+        assert_eq!(Some(0.4), estimates.adjusted_count(1));
+        let adjusted = [1, 2, 3, 7].map(|r| estimates.adjusted_count(r).unwrap());
+        assert!(adjusted.windows(2).all(|w| w[0] < w[1]), "{adjusted:?}");
+        let total: f64 = estimates.unseen_probability()
+            + bbow
+                .iter()
+                .map(|(_, count)| estimates.probability(count).unwrap())
+                .sum::<f64>();
+        assert!((total - 1.0).abs() < 1e-12, "{total}");
+        assert_eq!(None, estimates.probability(4));
+    }
+
+    #[test]
+    fn test_good_turing_one_count() {
+        // With a single count there is no line to fit, and
+        // every word is seen once, so there is no probability
+        // left for the seen words.
+        let estimates = Bbow::new().extend_from_text("a b c").good_turing();
+        assert_eq!(1.0, estimates.unseen_probability());
+        assert_eq!(Some(1.0), estimates.adjusted_count(1));
+        assert_eq!(Some(0.0), estimates.probability(1));
+    }
+}
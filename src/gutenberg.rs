@@ -0,0 +1,121 @@
+//! Project Gutenberg boilerplate removal.
+
+/// Is `line` the marker that ends the Gutenberg header?
+fn is_start_marker(line: &str) -> bool {
+    let line = line.trim().to_ascii_uppercase();
+    (line.starts_with("***") && line.contains("START OF") && line.contains("PROJECT GUTENBERG"))
+        || line.starts_with("*END*THE SMALL PRINT")
+}
+
+/// Is `line` the marker that starts the Gutenberg footer?
+fn is_end_marker(line: &str) -> bool {
+    let line = line.trim().to_ascii_uppercase();
+    (line.starts_with("***") && line.contains("END OF") && line.contains("PROJECT GUTENBERG"))
+        || line.starts_with("END OF THE PROJECT GUTENBERG")
+        || line.starts_with("END OF PROJECT GUTENBERG")
+}
+
+/// Is `paragraph` a production credit of the kind that
+/// usually follows the Gutenberg header?
+fn is_credit(paragraph: &str) -> bool {
+    let paragraph = paragraph.trim_start().to_ascii_lowercase();
+    paragraph.starts_with("produced by") || paragraph.starts_with("this ebook was produced by")
+}
+
+/// Return the body of a Project Gutenberg ebook `text`,
+/// without the header, the license footer, or a production
+/// credit paragraph at the start of the body.
+///
+/// The header ends at the `*** START OF THE PROJECT
+/// GUTENBERG EBOOK ... ***` line (or the older `*END*THE
+/// SMALL PRINT!` line) and the footer begins at the
+/// matching `*** END OF ...` line (or an older `End of the
+/// Project Gutenberg EBook ...` line). A missing marker
+/// leaves that end of the text as it is, so text that is
+/// not from Project Gutenberg is returned unchanged apart
+/// from surrounding whitespace.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::strip_gutenberg;
+/// let ebook = "The Project Gutenberg eBook of Emma\n\
+///              *** START OF THE PROJECT GUTENBERG EBOOK EMMA ***\n\
+///              Produced by volunteers.\n\
+///              \n\
+///              Emma Woodhouse, handsome, clever, and rich.\n\
+///              *** END OF THE PROJECT GUTENBERG EBOOK EMMA ***\n\
+///              Section 1. General Terms of Use\n";
+/// assert_eq!(
+///     "Emma Woodhouse, handsome, clever, and rich.",
+///     strip_gutenberg(ebook),
+/// );
+/// ```
+pub fn strip_gutenberg(text: &str) -> &str {
+    let mut start = 0;
+    let mut end = text.len();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if is_start_marker(line) {
+            start = offset + line.len();
+            break;
+        }
+        offset += line.len();
+    }
+    let mut offset = start;
+    for line in text[start..].split_inclusive('\n') {
+        if is_end_marker(line) {
+            end = offset;
+            break;
+        }
+        offset += line.len();
+    }
+    let mut body = text[start..end].trim();
+    if is_credit(body) {
+        body = match body.find("\n\n").or_else(|| body.find("\r\n\r\n")) {
+            Some(i) => body[i..].trim(),
+            None => "",
+        };
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_gutenberg_plain_text() {
+        assert_eq!("Just a text.", strip_gutenberg("\n Just a text.\n"));
+    }
+
+    #[test]
+    fn test_strip_gutenberg_old_markers() {
+        let ebook = concat!(
+            "Legal stuff\n",
+            "*END*THE SMALL PRINT! FOR PUBLIC DOMAIN ETEXTS*Ver.04.29.93*END*\n",
+            "Call me Ishmael.\n",
+            "End of Project Gutenberg's Moby Dick, by Herman Melville\n",
+            "More legal stuff\n",
+        );
+        assert_eq!("Call me Ishmael.", strip_gutenberg(ebook));
+    }
+
+    #[test]
+    fn test_strip_gutenberg_missing_end() {
+        let ebook = "Header\r\n*** START OF THIS PROJECT GUTENBERG EBOOK X ***\r\nBody\r\n";
+        assert_eq!("Body", strip_gutenberg(ebook));
+    }
+
+    #[test]
+    fn test_strip_gutenberg_counts() {
+        let ebook = "Project Gutenberg Project Gutenberg\n\
+                     *** START OF THE PROJECT GUTENBERG EBOOK T ***\n\
+                     the end\n\
+                     *** END OF THE PROJECT GUTENBERG EBOOK T ***\n\
+                     Project Gutenberg license\n";
+        let bbow = crate::Bbow::new().extend_from_text(strip_gutenberg(ebook));
+        assert_eq!(0, bbow.match_count("gutenberg"));
+        assert_eq!(2, bbow.count());
+    }
+}
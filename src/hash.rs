@@ -0,0 +1,172 @@
+//! A BBOW kept in a hash table, for fast counting when the
+//! words need not be kept in order.
+//!
+//! The table hashes with the standard library's SipHash,
+//! which resists hash flooding by hostile input. With the
+//! `fxhash` feature it uses the much faster Fx hash of the
+//! Rust compiler instead: words are short keys, and hashing
+//! them is most of the cost of counting.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::{words_of, Bbow};
+
+#[cfg(feature = "fxhash")]
+type WordHasher = rustc_hash::FxBuildHasher;
+#[cfg(not(feature = "fxhash"))]
+type WordHasher = std::collections::hash_map::RandomState;
+
+/// A BBOW whose words are kept in a hash table rather than
+/// in alphabetical order. Counting words is faster, and
+/// space can be set aside ahead of time, but the words come
+/// out in no particular order: [`HashBbow::to_bbow`] gives
+/// an ordinary BBOW of the same words for everything else.
+/// Words are split as by [`Bbow::new`].
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::HashBbow;
+/// let bbow = HashBbow::with_capacity(1000).extend_from_text("one fish two fish");
+/// assert_eq!(2, bbow.match_count("fish"));
+/// assert_eq!(vec!["fish", "one", "two"], bbow.to_bbow().words().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct HashBbow<'a>(HashMap<Cow<'a, str>, usize, WordHasher>);
+
+impl<'a> HashBbow<'a> {
+    /// Make a new empty BBOW.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make a new empty BBOW with room for at least
+    /// `capacity` distinct words before it must grow.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(HashMap::with_capacity_and_hasher(
+            capacity,
+            WordHasher::default(),
+        ))
+    }
+
+    /// Number of distinct words this BBOW can hold before it
+    /// must grow.
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Make room for at least `additional` more distinct
+    /// words.
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
+    }
+
+    /// Give back as much unused room as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit();
+    }
+
+    /// Parse the `target` text and add the sequence of
+    /// valid words contained in it to this BBOW, as
+    /// [`Bbow::extend_from_text`] does.
+    ///
+    /// This is a "builder method", like
+    /// [`Bbow::extend_from_text`].
+    pub fn extend_from_text(mut self, target: &'a str) -> Self {
+        for word in words_of(target) {
+            *self.0.entry(word).or_insert(0) += 1;
+        }
+        self
+    }
+
+    /// Add the words of `other`, with their counts, to this
+    /// BBOW.
+    pub fn merge(&mut self, other: &Bbow<'a>) {
+        for (word, &count) in &other.counts {
+            match self.0.get_mut(word.as_ref()) {
+                Some(total) => *total += count,
+                None => {
+                    self.0.insert(word.clone().into(), count);
+                }
+            }
+        }
+    }
+
+    /// Report the number of occurrences of the given
+    /// `keyword` in this BBOW. The keyword is looked up as
+    /// given, so the words of a BBOW built with other options
+    /// are found after [`HashBbow::merge`].
+    pub fn match_count(&self, keyword: &str) -> usize {
+        self.0.get(keyword).copied().unwrap_or(0)
+    }
+
+    /// The words in this BBOW with their counts, in no
+    /// particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.0.iter().map(|(w, &c)| (w.as_ref(), c))
+    }
+
+    /// Count the overall number of words contained in this
+    /// BBOW: multiple occurrences are considered separate.
+    pub fn count(&self) -> usize {
+        self.0.values().sum()
+    }
+
+    /// Count the number of unique words contained in this
+    /// BBOW.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Is this BBOW empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// An ordinary BBOW of the same words and counts.
+    pub fn to_bbow(&self) -> Bbow<'a> {
+        let mut bbow = Bbow::new();
+        for (word, &count) in &self.0 {
+            bbow.add(word.clone(), count);
+        }
+        bbow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_matches_bbow() {
+        let text = "It ain't over untïl it ain't, over. Over!";
+        let mut hash = HashBbow::new().extend_from_text(text);
+        let bbow = Bbow::new().extend_from_text(text);
+        assert_eq!(bbow, hash.to_bbow());
+        assert_eq!(bbow.len(), hash.len());
+        assert_eq!(bbow.count(), hash.count());
+        assert_eq!(3, hash.match_count("over"));
+        assert_eq!(0, hash.match_count("Over"));
+        hash.merge(&bbow);
+        assert_eq!(6, hash.match_count("over"));
+        hash.merge(
+            &Bbow::builder()
+                .numbers(true)
+                .build()
+                .extend_from_text("2024"),
+        );
+        assert_eq!(1, hash.match_count("2024"));
+    }
+
+    #[test]
+    fn test_capacity() {
+        let hash = HashBbow::with_capacity(100);
+        assert!(hash.capacity() >= 100);
+        let mut hash = hash.extend_from_text("a b");
+        hash.shrink_to_fit();
+        assert!(hash.capacity() >= 2 && hash.capacity() < 100);
+        hash.reserve(50);
+        assert!(hash.capacity() >= 52);
+    }
+}
@@ -0,0 +1,61 @@
+//! Text histograms of word counts, for a quick look at a
+//! distribution in a terminal.
+
+use crate::Bbow;
+
+impl Bbow<'_> {
+    /// A histogram of the words of this BBOW, most frequent
+    /// first as with [`Bbow::top_n`], one word per line. Each
+    /// word is followed by a bar of `#` characters whose
+    /// length is proportional to its count, with the most
+    /// frequent word's bar `width` characters long, and then
+    /// by its count. Every word gets a bar at least one
+    /// character long. Prune a copy of a large BBOW with
+    /// [`Bbow::prune_below`] to show only the top of its
+    /// distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a rose is a rose is a rose by any name");
+    /// let mut top = bbow.clone();
+    /// top.prune_below(2);
+    /// assert_eq!(
+    ///     "a    ###### 3\nrose ###### 3\nis   #### 2\n",
+    ///     top.render_histogram(6),
+    /// );
+    /// ```
+    pub fn render_histogram(&self, width: usize) -> String {
+        let entries = self.top_n(self.len());
+        let Some(&(_, max_count)) = entries.first() else {
+            return String::new();
+        };
+        let word_width = entries
+            .iter()
+            .map(|(word, _)| word.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut histogram = String::new();
+        for (word, count) in entries {
+            let bar = (count as f64 / max_count as f64 * width as f64).round() as usize;
+            let bar = "#".repeat(bar.max(1));
+            histogram.push_str(&format!("{word:word_width$} {bar} {count}\n"));
+        }
+        histogram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_histogram() {
+        assert_eq!("", Bbow::new().render_histogram(10));
+        let text = format!("{}b", "a ".repeat(100));
+        let bbow = Bbow::new().extend_from_text(&text);
+        assert_eq!("a ### 100\nb # 1\n", bbow.render_histogram(3));
+        assert_eq!("a # 100\nb # 1\n", bbow.render_histogram(0));
+    }
+}
@@ -0,0 +1,192 @@
+//! Sharing of the words that BBOWs have to copy.
+//!
+//! Most words are borrowed from the texts added to a BBOW,
+//! but some, such as lowercased words, must be copied. Each
+//! such word is kept once in a pool shared by every BBOW,
+//! which uses the one copy there both as a key of its map
+//! and as a key of what it tracks. Copies that no BBOW
+//! holds any longer are dropped from the pool as it grows.
+
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// A word as a BBOW keeps it: borrowed from a text, or a
+/// copy shared through the pool. Words compare,
+/// order and hash as the strings they are.
+#[derive(Clone)]
+pub(crate) enum Word<'a> {
+    Borrowed(&'a str),
+    Shared(Arc<str>),
+}
+
+impl Word<'_> {
+    /// This word, shared through the pool if borrowed.
+    pub(crate) fn into_owned(self) -> Word<'static> {
+        match self {
+            Word::Borrowed(word) => Word::Shared(intern(word)),
+            Word::Shared(word) => Word::Shared(word),
+        }
+    }
+}
+
+impl Deref for Word<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            Word::Borrowed(word) => word,
+            Word::Shared(word) => word,
+        }
+    }
+}
+
+impl AsRef<str> for Word<'_> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl Borrow<str> for Word<'_> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl PartialEq for Word<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for Word<'_> {}
+
+impl PartialOrd for Word<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Word<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl Hash for Word<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+impl fmt::Debug for Word<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<'a> From<&'a str> for Word<'a> {
+    fn from(word: &'a str) -> Self {
+        Word::Borrowed(word)
+    }
+}
+
+impl<'a> From<Word<'a>> for Cow<'a, str> {
+    fn from(word: Word<'a>) -> Self {
+        match word {
+            Word::Borrowed(word) => Cow::Borrowed(word),
+            Word::Shared(word) => Cow::Owned(word.to_string()),
+        }
+    }
+}
+
+/// The pool of copied words, shared by every BBOW, with the
+/// size it may grow to before unused words are dropped.
+struct Pool {
+    words: HashSet<Arc<str>>,
+    limit: usize,
+}
+
+/// The size below which the pool is never swept.
+const MIN_LIMIT: usize = 1024;
+
+/// The pool, made on first use.
+fn pool() -> &'static Mutex<Pool> {
+    static POOL: OnceLock<Mutex<Pool>> = OnceLock::new();
+    POOL.get_or_init(|| {
+        Mutex::new(Pool {
+            words: HashSet::new(),
+            limit: MIN_LIMIT,
+        })
+    })
+}
+
+/// Drop from `pool` the words no BBOW holds any longer,
+/// and let it grow to twice the size of what is left.
+fn sweep(pool: &mut Pool) {
+    pool.words.retain(|word| Arc::strong_count(word) > 1);
+    pool.limit = (2 * pool.words.len()).max(MIN_LIMIT);
+}
+
+/// The copy of `word` in the pool, added if there is none
+/// yet.
+pub(crate) fn intern(word: &str) -> Arc<str> {
+    // A thread that panicked while holding the lock cannot
+    // have left the pool inconsistent.
+    let mut pool = pool()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(shared) = pool.words.get(word) {
+        return Arc::clone(shared);
+    }
+    if pool.words.len() >= pool.limit {
+        sweep(&mut pool);
+    }
+    let shared: Arc<str> = Arc::from(word);
+    pool.words.insert(Arc::clone(&shared));
+    shared
+}
+
+/// `word` as a BBOW keeps it: still borrowed if it is, and
+/// otherwise the copy in the pool.
+pub(crate) fn word(word: Cow<'_, str>) -> Word<'_> {
+    match word {
+        Cow::Borrowed(word) => Word::Borrowed(word),
+        Cow::Owned(word) => Word::Shared(intern(&word)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let a = intern("hello");
+        let b = intern(&String::from("hello"));
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &intern("world")));
+        let shared = word(Cow::Owned("hello".to_string()));
+        assert!(matches!(&shared, Word::Shared(w) if Arc::ptr_eq(w, &a)));
+        assert!(matches!(word(Cow::Borrowed("x")), Word::Borrowed("x")));
+        assert!(Word::Borrowed("a") < Word::Shared(Arc::from("b")));
+        assert_eq!(Word::Borrowed("b"), Word::Shared(Arc::from("b")));
+    }
+
+    #[test]
+    fn test_sweep() {
+        let kept = intern("test-sweep-kept");
+        drop(intern("test-sweep-dropped"));
+        let mut pool = pool().lock().unwrap();
+        sweep(&mut pool);
+        assert!(pool.words.contains("test-sweep-kept"));
+        assert!(!pool.words.contains("test-sweep-dropped"));
+        assert!(pool.limit >= MIN_LIMIT);
+        drop(pool);
+        assert!(Arc::ptr_eq(&kept, &intern("test-sweep-kept")));
+    }
+}
@@ -0,0 +1,202 @@
+//! JSON import and export of BBOWs, as an object mapping
+//! each word to its count, and export of word-cloud
+//! weights.
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+
+use serde_json::{json, Value};
+
+use crate::escape::json_string;
+use crate::{Bbow, BbowBuilder};
+
+/// How [`Bbow::to_wordcloud_json`] turns counts into
+/// weights.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CloudScale {
+    /// Weights in proportion to the counts.
+    #[default]
+    Linear,
+    /// Weights in proportion to the logarithms of one more
+    /// than the counts, so that a few very frequent words do
+    /// not dwarf the rest.
+    Log,
+}
+
+impl Bbow<'_> {
+    /// This BBOW as a JSON object mapping each word to its
+    /// count, with the words in alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("b a b");
+    /// assert_eq!(r#"{"a":1,"b":2}"#, bbow.to_json_string());
+    /// ```
+    pub fn to_json_string(&self) -> String {
+        let mut json = String::from("{");
+        for (i, (word, count)) in self.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str(&json_string(word));
+            json.push_str(&format!(":{count}"));
+        }
+        json.push('}');
+        json
+    }
+
+    /// Read a BBOW with the default options from a JSON
+    /// object mapping words to counts, as written by
+    /// [`Bbow::to_json_string`]. Any other JSON value, or a
+    /// count that is not a positive integer, is an error.
+    /// Words are kept as written, as by [`Bbow::insert`]. A
+    /// BBOW built with other options is read back with
+    /// [`Bbow::from_json_str_with`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::from_json_str(r#"{ "fish": 2, "cat": 1 }"#).unwrap();
+    /// assert_eq!(2, bbow.match_count("fish"));
+    /// assert!(Bbow::from_json_str(r#"{ "fish": -2 }"#).is_err());
+    /// assert!(Bbow::from_json_str(r#"{ "fish": 0 }"#).is_err());
+    /// ```
+    pub fn from_json_str(json: &str) -> serde_json::Result<Bbow<'static>> {
+        Bbow::from_json_str_with(json, &BbowBuilder::new())
+    }
+
+    /// Read a BBOW with the options of `builder` from JSON,
+    /// as [`Bbow::from_json_str`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let builder = Bbow::builder().numbers(true);
+    /// let bbow = builder.build().extend_from_text("in 2024, 2024 words");
+    /// let read = Bbow::from_json_str_with(&bbow.to_json_string(), &builder).unwrap();
+    /// assert_eq!(2, read.match_count("2024"));
+    /// ```
+    pub fn from_json_str_with(
+        json: &str,
+        builder: &BbowBuilder,
+    ) -> serde_json::Result<Bbow<'static>> {
+        let words: BTreeMap<String, NonZeroUsize> = serde_json::from_str(json)?;
+        let mut bbow = builder.build();
+        for (word, count) in words {
+            bbow.insert(word, count.get());
+        }
+        Ok(bbow)
+    }
+
+    /// The `max_words` most frequent words of this BBOW, in
+    /// the order of [`Bbow::top_n`], as the JSON array of
+    /// `{"text", "weight"}` objects that word-cloud
+    /// renderers expect. The weights are scaled by `scale`
+    /// so that the most frequent word weighs 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::{Bbow, CloudScale};
+    /// let bbow = Bbow::new().extend_from_text("sun sun sun sun moon moon star");
+    /// assert_eq!(
+    ///     r#"[{"text":"sun","weight":1.0},{"text":"moon","weight":0.5}]"#,
+    ///     bbow.to_wordcloud_json(2, CloudScale::Linear),
+    /// );
+    /// ```
+    pub fn to_wordcloud_json(&self, max_words: usize, scale: CloudScale) -> String {
+        let top = self.top_n(max_words);
+        let weigh = |count: usize| match scale {
+            CloudScale::Linear => count as f64,
+            CloudScale::Log => (count as f64).ln_1p(),
+        };
+        let max = top.first().map_or(1.0, |&(_, count)| weigh(count));
+        let cloud: Vec<Value> = top
+            .into_iter()
+            .map(|(word, count)| json!({ "text": word, "weight": weigh(count) / max }))
+            .collect();
+        Value::Array(cloud).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_round_trip() {
+        let bbow = Bbow::new().extend_from_text("It ain't over untïl it \"ain't\", over.");
+        let read = Bbow::from_json_str(&bbow.to_json_string()).unwrap();
+        assert_eq!(bbow.counts, read.counts);
+    }
+
+    #[test]
+    fn test_json_round_trip_options() {
+        let builder = Bbow::builder()
+            .numbers(true)
+            .apostrophes(crate::Apostrophes::Keep)
+            .case_sensitive(true);
+        let bbow = builder.build().extend_from_text("2024 2024 Ain't it grand");
+        let json = bbow.to_json_string();
+        let read = Bbow::from_json_str_with(&json, &builder).unwrap();
+        assert_eq!(bbow, read);
+        assert_eq!(2, read.match_count("2024"));
+        assert_eq!(1, read.match_count("Ain't"));
+        assert_eq!(3, read.extend_from_text("2024").match_count("2024"));
+    }
+
+    #[test]
+    fn test_json_round_trip_foreign_words() {
+        let mut bbow = Bbow::new().extend_from_text("a cat");
+        bbow.insert("Hello", 2);
+        bbow.merge(
+            &Bbow::builder()
+                .numbers(true)
+                .build()
+                .extend_from_text("2024"),
+        );
+        let read = Bbow::from_json_str(&bbow.to_json_string()).unwrap();
+        assert_eq!(bbow, read);
+        assert!(read.contains("Hello") && read.contains("2024"));
+    }
+
+    #[test]
+    fn test_json_empty() {
+        assert_eq!("{}", Bbow::new().to_json_string());
+        assert!(Bbow::from_json_str("{}").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_wordcloud_json() {
+        let bbow = Bbow::new().extend_from_text("c c c b a a a");
+        let cloud: Value =
+            serde_json::from_str(&bbow.to_wordcloud_json(10, CloudScale::Log)).unwrap();
+        let weights: Vec<(&str, f64)> = cloud
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|o| (o["text"].as_str().unwrap(), o["weight"].as_f64().unwrap()))
+            .collect();
+        assert_eq!(vec![("a", 1.0), ("c", 1.0), ("b", 0.5)], weights);
+        assert_eq!("[]", bbow.to_wordcloud_json(0, CloudScale::Linear));
+        assert_eq!("[]", Bbow::new().to_wordcloud_json(5, CloudScale::Log));
+    }
+
+    #[test]
+    fn test_from_json_str_errors() {
+        for bad in [
+            "[]",
+            "{\"a\": 0}",
+            "{\"a\": 1.5}",
+            "{\"a\": \"1\"}",
+            "{\"a\": 1",
+            "null",
+        ] {
+            assert!(Bbow::from_json_str(bad).is_err(), "{bad}");
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Language identification by character trigram profiles,
+//! after Cavnar and Trenkle, "N-Gram-Based Text
+//! Categorization" (1994).
+//!
+//! A profile is the list of the most frequent character
+//! trigrams of a text, most frequent first. Each language
+//! has a profile made from a sample text bundled with the
+//! crate, and a text is identified as the language whose
+//! profile is closest to its own.
+
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+use crate::{words_of, Bbow};
+
+/// Number of trigrams kept in a profile. Each bundled
+/// sample has at least this many trigrams that occur more
+/// than once, so that its profile is not cut off among
+/// trigrams seen only by chance.
+const PROFILE_LEN: usize = 300;
+
+/// The bundled sample texts, by ISO 639-1 language code, of
+/// a few kilobytes each.
+const SAMPLES: &[(&str, &str)] = &[
+    ("de", include_str!("../data/languages/de.txt")),
+    ("en", include_str!("../data/languages/en.txt")),
+    ("es", include_str!("../data/languages/es.txt")),
+    ("fr", include_str!("../data/languages/fr.txt")),
+    ("it", include_str!("../data/languages/it.txt")),
+    ("nl", include_str!("../data/languages/nl.txt")),
+    ("pt", include_str!("../data/languages/pt.txt")),
+];
+
+/// The counts of the character trigrams of `text`. Each
+/// word is padded with a space at either end, so that
+/// trigrams at word starts and ends are told apart from
+/// those inside words.
+fn trigrams(text: &str) -> Bbow<'static> {
+    let mut trigrams = Bbow::new();
+    for word in words_of(text) {
+        let chars: Vec<char> = format!(" {word} ").chars().collect();
+        for trigram in chars.windows(3) {
+            let trigram: String = trigram.iter().collect();
+            trigrams.add(Cow::Owned(trigram), 1);
+        }
+    }
+    trigrams
+}
+
+/// The trigram profile of `text`.
+fn profile(text: &str) -> Vec<String> {
+    trigrams(text)
+        .top_n(PROFILE_LEN)
+        .into_iter()
+        .map(|(trigram, _)| trigram.to_string())
+        .collect()
+}
+
+/// The "out-of-place" distance from the profile `text` to
+/// the profile `language`: the sum over the trigrams of
+/// `text` of how far their ranks are from their ranks in
+/// `language`, with trigrams missing from `language` as far
+/// as possible.
+fn distance(text: &[String], language: &[String]) -> usize {
+    text.iter()
+        .enumerate()
+        .map(
+            |(rank, trigram)| match language.iter().position(|t| t == trigram) {
+                Some(other) => rank.abs_diff(other),
+                None => PROFILE_LEN,
+            },
+        )
+        .sum()
+}
+
+fn language_profiles() -> &'static [(&'static str, Vec<String>)] {
+    static PROFILES: OnceLock<Vec<(&str, Vec<String>)>> = OnceLock::new();
+    PROFILES.get_or_init(|| {
+        SAMPLES
+            .iter()
+            .map(|&(code, sample)| (code, profile(sample)))
+            .collect()
+    })
+}
+
+/// The ISO 639-1 code of the most likely language of
+/// `text`, out of the languages with bundled profiles:
+/// Dutch, English, French, German, Italian, Portuguese and
+/// Spanish. Returns `None` if `text` has no words. Short
+/// texts are identified less reliably than long ones.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::detect_language;
+/// assert_eq!(Some("en"), detect_language("The quick brown fox jumps over the lazy dog."));
+/// assert_eq!(Some("fr"), detect_language("Le renard brun saute par-dessus le chien paresseux."));
+/// assert_eq!(None, detect_language("1234 !!"));
+/// ```
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let text = profile(text);
+    if text.is_empty() {
+        return None;
+    }
+    language_profiles()
+        .iter()
+        .min_by_key(|(_, language)| distance(&text, language))
+        .map(|&(code, _)| code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile() {
+        assert_eq!(vec![" a ", " ab", "ab "], profile("ab A"));
+        assert!(profile("").is_empty());
+        for (code, sample) in SAMPLES {
+            let trigrams = trigrams(sample);
+            let top = trigrams.top_n(PROFILE_LEN);
+            assert_eq!(PROFILE_LEN, top.len(), "{code}");
+            assert!(top[PROFILE_LEN - 1].1 > 1, "{code}");
+        }
+    }
+
+    #[test]
+    fn test_distance() {
+        let p = |ts: &[&str]| ts.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        assert_eq!(0, distance(&p(&["abc", "bcd"]), &p(&["abc", "bcd"])));
+        assert_eq!(2, distance(&p(&["abc", "bcd"]), &p(&["bcd", "abc"])));
+        assert_eq!(PROFILE_LEN, distance(&p(&["xyz"]), &p(&["abc"])));
+    }
+
+    #[test]
+    fn test_detect_close_languages() {
+        let texts = [
+            ("nl", "Ik heb een huis."),
+            ("de", "Ich habe ein Haus."),
+            ("nl", "Het water is koud."),
+            ("de", "Das Wasser ist kalt."),
+            ("nl", "Wij zijn vrij."),
+            ("de", "Wir sind frei."),
+            ("nl", "De kinderen spelen in de tuin."),
+            ("de", "Die Kinder spielen im Garten."),
+            ("es", "La casa es grande."),
+            ("pt", "A casa é grande."),
+            ("es", "El niño come pan."),
+            ("pt", "O menino come pão."),
+            ("es", "Todos somos libres."),
+            ("pt", "Todos somos livres."),
+            ("es", "Los niños van a la escuela."),
+            ("pt", "As crianças vão para a escola."),
+        ];
+        for (code, text) in texts {
+            assert_eq!(Some(code), detect_language(text), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_detect_language() {
+        let texts = [
+            (
+                "de",
+                "Der Hund schläft im Garten, weil die Sonne heute so warm scheint.",
+            ),
+            (
+                "en",
+                "We walked along the river until the evening light began to fade.",
+            ),
+            (
+                "es",
+                "El perro duerme en el jardín porque hoy el sol calienta mucho.",
+            ),
+            (
+                "fr",
+                "Le chien dort dans le jardin parce que le soleil est très chaud.",
+            ),
+            (
+                "it",
+                "Il cane dorme nel giardino perché oggi il sole è molto caldo.",
+            ),
+            (
+                "nl",
+                "De hond slaapt in de tuin omdat de zon vandaag zo warm schijnt.",
+            ),
+            (
+                "pt",
+                "O cão dorme no jardim porque hoje o sol está muito quente.",
+            ),
+        ];
+        for (code, text) in texts {
+            assert_eq!(Some(code), detect_language(text), "{text}");
+        }
+    }
+}
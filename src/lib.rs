@@ -24,16 +24,241 @@
 //! `"untïl"`, `"it"`, `"over"`.
 //!
 //! Words in the bag containing uppercase letters will be
-//! represented by their lowercase equivalent.
+//! represented by their lowercase equivalent, unless the bag
+//! was made case-sensitive with [`BbowBuilder`].
+//!
+//! [`BbowBuilder`] makes bags that count words in other
+//! ways, for example with Unicode normalization or without
+//! stop words.
+//!
+//! # Optional features
+//!
+//! * `serde_json`: JSON import and export of bags, and
+//!   export of word-cloud weights.
+//! * `arrow`: export of bags and corpora as Apache Arrow
+//!   record batches.
+//! * `polars`: export of bags and corpora as Polars
+//!   DataFrames.
+//! * `rand`: random sampling of words in proportion to
+//!   their counts, random text from [`Bigrams`], and
+//!   `Reservoir`, a random sample of the words of a stream.
+//! * `sled`: [`PersistentBbow`], a bag stored on disk in a
+//!   sled database.
+//! * `cjk`: [`BbowBuilder::cjk_bigrams`], counting of Chinese
+//!   and Japanese text by character bigrams.
+//! * `fst`: export of bags as finite-state transducers,
+//!   for compact read-only storage and automaton queries.
+//! * `fxhash`: faster hashing of words in [`HashBbow`].
+//! * `wasm`: WebAssembly bindings for use from JavaScript.
+//! * `pyo3`: Python bindings.
+//! * `ffi`: a C interface, declared in `include/bbow.h`.
+
+mod algebra;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod ascii;
+mod bigrams;
+mod binary;
+mod bm25;
+mod bounded;
+mod builder;
+#[cfg(feature = "cjk")]
+mod cjk;
+mod classifier;
+mod concordance;
+mod concurrent;
+mod cooccurrence;
+mod corpus;
+mod corpus_file;
+mod csv;
+mod decaying;
+mod delta;
+mod diff;
+mod document_term;
+mod escape;
+mod features;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod filter;
+mod fingerprint;
+mod frozen;
+#[cfg(feature = "fst")]
+mod fst;
+mod good_turing;
+mod gutenberg;
+mod hash;
+mod histogram;
+mod intern;
+#[cfg(feature = "serde_json")]
+mod json;
+mod language;
+mod memory;
+#[cfg(feature = "sled")]
+mod persistent;
+#[cfg(feature = "polars")]
+mod polars;
+mod prefix;
+#[cfg(feature = "pyo3")]
+mod python;
+mod query;
+mod quotes;
+#[cfg(feature = "rand")]
+mod reservoir;
+#[cfg(feature = "rand")]
+mod sample;
+mod sentences;
+mod spelling;
+mod stats;
+mod stemmer;
+mod stopwords;
+mod substring;
+mod summarize;
+mod symbols;
+mod trie;
+mod vocabulary;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod windows;
+mod wordlist;
+
+pub use bigrams::Bigrams;
+pub use bounded::{BoundedBbow, Eviction};
+pub use builder::{Apostrophes, BbowBuilder, Hyphens, Normalization, Segmentation};
+pub use classifier::NaiveBayes;
+pub use concordance::Concordance;
+pub use concurrent::ConcurrentBbow;
+pub use cooccurrence::{Cooccurrence, SparseMatrix};
+pub use corpus::{Corpus, Document, InvertedIndex};
+pub use decaying::DecayingBbow;
+pub use diff::BbowDiff;
+pub use document_term::DocumentTermMatrix;
+pub use filter::{Lowercase, MinLen, TokenFilter};
+pub use frozen::FrozenBbow;
+pub use good_turing::GoodTuring;
+pub use gutenberg::strip_gutenberg;
+pub use hash::HashBbow;
+#[cfg(feature = "serde_json")]
+pub use json::CloudScale;
+pub use language::detect_language;
+pub use memory::MemoryUsage;
+#[cfg(feature = "sled")]
+pub use persistent::PersistentBbow;
+#[cfg(feature = "pyo3")]
+pub use python::PyBbow;
+pub use quotes::Quotation;
+#[cfg(feature = "rand")]
+pub use reservoir::Reservoir;
+#[cfg(feature = "rand")]
+pub use sample::Sampler;
+pub use sentences::sentences;
+pub use spelling::Speller;
+pub use stats::Stats;
+pub use stemmer::Stemmer;
+pub use stopwords::StopWords;
+pub use substring::SubstringIndex;
+pub use summarize::summarize;
+pub use trie::TrieBbow;
+pub use vocabulary::Vocabulary;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmBbow;
+pub use wordlist::WordList;
 
 use std::borrow::Cow;
-use std::collections::BTreeMap;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut, Range};
+
+use intern::Word;
 
 /// Each key in this struct's map is a word in some
 /// in-memory text document. The corresponding value is the
-/// count of occurrences.
+/// count of occurrences. The options the BBOW was built
+/// with say how text added to it is split into words.
+///
+/// Words that had to be copied, such as lowercased words,
+/// are kept once in a pool shared by every BBOW, so that
+/// many documents with the same words do not each hold
+/// copies of them.
 #[derive(Debug, Default, Clone)]
-pub struct Bbow<'a>(BTreeMap<Cow<'a, str>, usize>);
+pub struct Bbow<'a> {
+    /// The count of each word.
+    counts: BTreeMap<Word<'a>, usize>,
+    /// The options the BBOW was built with.
+    options: BbowBuilder,
+    /// What is recorded about the words beyond their counts.
+    tracking: Tracking<'a>,
+}
+
+/// What a BBOW records about its words beyond their counts,
+/// if its options ask for it.
+#[derive(Debug, Default, Clone)]
+struct Tracking<'a> {
+    /// The forms in which each word was written.
+    variants: BTreeMap<Word<'a>, BTreeSet<Cow<'a, str>>>,
+    /// The positions at which each word occurs.
+    positions: BTreeMap<Word<'a>, Vec<usize>>,
+    /// The position of the next word to be added.
+    next_position: usize,
+    /// The count of each word in each tagged text.
+    documents: BTreeMap<Word<'a>, BTreeMap<usize, usize>>,
+}
+
+impl<'a> Tracking<'a> {
+    /// Record the next `word`, written as `form`, as asked
+    /// for by `options`.
+    fn record(&mut self, options: &BbowBuilder, form: Cow<'a, str>, word: Word<'a>) {
+        if options.track_variants {
+            entry_for(&mut self.variants, &word).insert(form);
+        }
+        if options.track_positions {
+            self.positions
+                .entry(word)
+                .or_default()
+                .push(self.next_position);
+        }
+        self.next_position += 1;
+    }
+
+    /// Forget everything about `word`.
+    fn remove(&mut self, word: &str) {
+        self.variants.remove(word);
+        self.positions.remove(word);
+        self.documents.remove(word);
+    }
+
+    /// This tracking with every word copied, the keys into
+    /// the pool.
+    fn into_owned(self) -> Tracking<'static> {
+        Tracking {
+            variants: self
+                .variants
+                .into_iter()
+                .map(|(word, forms)| {
+                    let forms = forms.into_iter().map(owned).collect();
+                    (word.into_owned(), forms)
+                })
+                .collect(),
+            positions: self
+                .positions
+                .into_iter()
+                .map(|(word, positions)| (word.into_owned(), positions))
+                .collect(),
+            next_position: self.next_position,
+            documents: self
+                .documents
+                .into_iter()
+                .map(|(word, documents)| (word.into_owned(), documents))
+                .collect(),
+        }
+    }
+}
+
+/// `word`, copied if borrowed.
+fn owned(word: Cow<'_, str>) -> Cow<'static, str> {
+    Cow::Owned(word.into_owned())
+}
 
 fn is_word(word: &str) -> bool {
     !word.is_empty() && word.chars().all(|c| c.is_alphabetic())
@@ -43,6 +268,181 @@ fn has_uppercase(word: &str) -> bool {
     word.chars().any(char::is_uppercase)
 }
 
+/// `word` with any leading and trailing non-letters
+/// removed.
+pub(crate) fn trim_word(word: &str) -> &str {
+    word.trim_matches(|c: char| !c.is_alphabetic())
+}
+
+/// `word` in lowercase, copied only if needed.
+pub(crate) fn lowercase(word: &str) -> Cow<'_, str> {
+    if word.is_ascii() {
+        return ascii::lowercase(word);
+    }
+    if has_uppercase(word) {
+        Cow::Owned(word.to_lowercase())
+    } else {
+        Cow::Borrowed(word)
+    }
+}
+
+/// The count of a word in a [`Bbow`], which can be changed,
+/// given by [`Bbow::get_mut`]. If the count is 0 when this
+/// is dropped, the word is removed from the BBOW, as
+/// [`Bbow::insert`] removes a word given a count of 0.
+#[derive(Debug)]
+pub struct CountMut<'m, 'a> {
+    bbow: &'m mut Bbow<'a>,
+    word: &'m str,
+}
+
+impl Deref for CountMut<'_, '_> {
+    type Target = usize;
+
+    fn deref(&self) -> &usize {
+        &self.bbow.counts[self.word]
+    }
+}
+
+impl DerefMut for CountMut<'_, '_> {
+    fn deref_mut(&mut self) -> &mut usize {
+        self.bbow
+            .counts
+            .get_mut(self.word)
+            .expect("the word of a CountMut is in its BBOW")
+    }
+}
+
+impl Drop for CountMut<'_, '_> {
+    fn drop(&mut self) {
+        if **self == 0 {
+            self.bbow.counts.remove(self.word);
+            self.bbow.tracking.remove(self.word);
+        }
+    }
+}
+
+/// The value for `word` in `map`, inserting a default value
+/// first if there is none. [`BTreeMap::entry`] takes its key
+/// by value, so a word borrowed from another map must be
+/// cloned before it is looked up; this clones `word` only
+/// when it is new to `map`. A copied word is shared, not
+/// copied again, by the clone.
+pub(crate) fn entry_for<'m, 'a, V: Default>(
+    map: &'m mut BTreeMap<Word<'a>, V>,
+    word: &Word<'a>,
+) -> &'m mut V {
+    if !map.contains_key(word.as_ref()) {
+        map.insert(word.clone(), V::default());
+    }
+    map.get_mut(word.as_ref()).unwrap()
+}
+
+/// Add `n` occurrences of `word` to `words`. A copied word
+/// new to `words` is kept as the copy in the pool.
+fn add_word<'a>(words: &mut BTreeMap<Word<'a>, usize>, word: Cow<'a, str>, n: usize) {
+    match words.get_mut(word.as_ref()) {
+        Some(count) => *count += n,
+        None => {
+            words.insert(intern::word(word), n);
+        }
+    }
+}
+
+/// The key under which `word` is, or would be, kept in
+/// `words`: the key already there, or else the copy in the
+/// pool if the word was copied.
+pub(crate) fn key_for<'a, V>(words: &BTreeMap<Word<'a>, V>, word: Cow<'a, str>) -> Word<'a> {
+    match words.get_key_value(word.as_ref()) {
+        Some((key, _)) => key.clone(),
+        None => intern::word(word),
+    }
+}
+
+/// The sequence of valid words in `target`, lowercased
+/// where needed, as per the rules of BBOW.
+pub(crate) fn words_of(target: &str) -> impl Iterator<Item = Cow<'_, str>> {
+    let ascii = target.is_ascii();
+    ascii::split_whitespace(target, ascii).filter_map(move |token| {
+        if ascii {
+            ascii::scan_word(token)
+        } else {
+            scan_word(token)
+        }
+    })
+}
+
+/// The word in `token`, a piece of text between
+/// whitespace, lowercased, if there is one: the same as
+/// trimming it with [`trim_word`], checking it with
+/// [`is_word`] and lowercasing it with [`lowercase`], but
+/// looking at each character only once.
+fn scan_word(token: &str) -> Option<Cow<'_, str>> {
+    let start = token.find(char::is_alphabetic)?;
+    let (last, c) = token
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| c.is_alphabetic())?;
+    let word = &token[start..last + c.len_utf8()];
+    let mut upper = false;
+    for c in word.chars() {
+        if !c.is_alphabetic() {
+            return None;
+        }
+        upper |= c.is_uppercase();
+    }
+    Some(if upper {
+        Cow::Owned(word.to_lowercase())
+    } else {
+        Cow::Borrowed(word)
+    })
+}
+
+/// The words of `text` one at a time, as a default BBOW
+/// would count them: trimmed of punctuation, checked, and
+/// lowercased. Nothing is counted or stored, so this is
+/// cheaper than building a BBOW for callers that need only
+/// the sequence of words. Words are borrowed from `text`
+/// unless lowercasing changed them.
+///
+/// # Examples
+///
+/// ```
+/// let words: Vec<_> = bbow::tokens("It ain't over untïl it ain't, over.").collect();
+/// assert_eq!(vec!["it", "over", "untïl", "it", "over"], words);
+/// ```
+pub fn tokens(text: &str) -> impl Iterator<Item = Cow<'_, str>> {
+    words_of(text)
+}
+
+/// The words of `text`, as [`tokens`] gives them, each with
+/// the byte range it was found at in `text`. The word is
+/// given as it was written there, not lowercased, so that
+/// `&text[range]` is the word: for example, to highlight
+/// the words of a text that a BBOW counted.
+///
+/// # Examples
+///
+/// ```
+/// let text = "It ain't over, untïl...";
+/// let spans: Vec<_> = bbow::token_spans(text).collect();
+/// assert_eq!(vec![(0..2, "It"), (9..13, "over"), (15..21, "untïl")], spans);
+/// ```
+pub fn token_spans(text: &str) -> impl Iterator<Item = (Range<usize>, &str)> {
+    let ascii = text.is_ascii();
+    ascii::split_whitespace(text, ascii).filter_map(move |word| {
+        let word = if ascii {
+            let word = ascii::trim_non_letters(word);
+            (!word.is_empty() && ascii::is_alphabetic(word)).then_some(word)
+        } else {
+            let word = trim_word(word);
+            is_word(word).then_some(word)
+        }?;
+        let start = word.as_ptr() as usize - text.as_ptr() as usize;
+        Some((start..start + word.len(), word))
+    })
+}
+
 impl<'a> Bbow<'a> {
     /// Make a new empty target words list.
     pub fn new() -> Self {
@@ -72,20 +472,370 @@ impl<'a> Bbow<'a> {
     /// entirely into their lowercase version. It returns the modified instance
     /// of itself.
     pub fn extend_from_text(mut self, target: &'a str) -> Self {
-        for word in target.split_whitespace() {
-            let word = word.trim_matches(|c: char| !c.is_alphabetic());
-            if is_word(word) {
-                let word = if has_uppercase(word) {
-                    Cow::Owned(word.to_lowercase())
-                } else {
-                    Cow::Borrowed(word)
-                };
-                *self.0.entry(word).or_insert(0) += 1;
+        if self.options.track_variants || self.options.track_positions {
+            for (form, word) in self.options.words_with_forms(target) {
+                let word = key_for(&self.counts, word);
+                self.tracking.record(&self.options, form, word.clone());
+                *self.counts.entry(word).or_insert(0) += 1;
             }
+            return self;
+        }
+        for word in self.options.words(target) {
+            add_word(&mut self.counts, word, 1);
         }
         self
     }
 
+    /// Parse the `target` text as [`Bbow::extend_from_text`]
+    /// does, but pass each word through `map` before
+    /// counting it: `map` may return the word, another word
+    /// to count in its place, or `None` to drop it. This is
+    /// for one-off rewrites, such as mapping synonyms, that
+    /// do not deserve a [`TokenFilter`]. The words `map` is
+    /// given are already lowercased, and the words it returns
+    /// should be valid words, or [`Bbow::match_count`] will
+    /// not find them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text_with("A big cat, a large cat", |word| {
+    ///     match word.as_ref() {
+    ///         "a" => None,
+    ///         "large" => Some(Cow::Borrowed("big")),
+    ///         _ => Some(word),
+    ///     }
+    /// });
+    /// assert_eq!(vec![("big", 2), ("cat", 2)], bbow.top_n(10));
+    /// ```
+    pub fn extend_from_text_with<F>(mut self, target: &'a str, mut map: F) -> Self
+    where
+        F: FnMut(Cow<'a, str>) -> Option<Cow<'a, str>>,
+    {
+        let tracking = self.options.track_variants || self.options.track_positions;
+        for (form, word) in self.options.words_with_forms(target) {
+            let Some(word) = map(word) else {
+                continue;
+            };
+            if tracking {
+                let word = key_for(&self.counts, word);
+                self.tracking.record(&self.options, form, word.clone());
+                *self.counts.entry(word).or_insert(0) += 1;
+            } else {
+                add_word(&mut self.counts, word, 1);
+            }
+        }
+        self
+    }
+
+    /// Parse the `target` text and add its words to this
+    /// BBOW as [`Bbow::extend_from_text`] does, also
+    /// recording that they came from the text tagged
+    /// `doc_id`, for [`Bbow::count_in`]. A single BBOW can
+    /// then count the words of many documents both together
+    /// and one by one. Texts may share a tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new()
+    ///     .extend_from_text_tagged("red fish, blue fish", 7)
+    ///     .extend_from_text_tagged("one fish", 8);
+    /// assert_eq!(3, bbow.match_count("fish"));
+    /// assert_eq!(2, bbow.count_in("fish", 7));
+    /// assert_eq!(0, bbow.count_in("red", 8));
+    /// ```
+    pub fn extend_from_text_tagged(mut self, target: &'a str, doc_id: usize) -> Self {
+        for (form, word) in self.options.words_with_forms(target) {
+            let word = key_for(&self.counts, word);
+            self.tracking.record(&self.options, form, word.clone());
+            let documents = entry_for(&mut self.tracking.documents, &word);
+            *documents.entry(doc_id).or_insert(0) += 1;
+            *self.counts.entry(word).or_insert(0) += 1;
+        }
+        self
+    }
+
+    /// Add the words of `other`, with their counts, to
+    /// this BBOW.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("one fish");
+    /// bbow.merge(&Bbow::new().extend_from_text("two fish"));
+    /// assert_eq!(3, bbow.len());
+    /// assert_eq!(2, bbow.match_count("fish"));
+    /// ```
+    pub fn merge(&mut self, other: &Bbow<'a>) {
+        for (word, &count) in &other.counts {
+            *entry_for(&mut self.counts, word) += count;
+        }
+        for (word, forms) in &other.tracking.variants {
+            let variants = entry_for(&mut self.tracking.variants, word);
+            variants.extend(forms.iter().cloned());
+        }
+        let offset = self.tracking.next_position;
+        for (word, positions) in &other.tracking.positions {
+            let entry = entry_for(&mut self.tracking.positions, word);
+            entry.extend(positions.iter().map(|p| p + offset));
+        }
+        self.tracking.next_position += other.tracking.next_position;
+        for (word, documents) in &other.tracking.documents {
+            let entry = entry_for(&mut self.tracking.documents, word);
+            for (&id, &count) in documents {
+                *entry.entry(id).or_insert(0) += count;
+            }
+        }
+    }
+
+    /// Add the words of `other` to this BBOW, each with its
+    /// count multiplied by `weight` and rounded to the
+    /// nearest whole number, so that bags of texts of very
+    /// different lengths can be combined on an equal
+    /// footing. Words whose weighted count rounds to 0 are
+    /// not added. A negative `weight` adds nothing. The
+    /// variants of the added words are merged, but their
+    /// positions are not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("one fish");
+    /// bbow.merge_weighted(&Bbow::new().extend_from_text("two fish fish red"), 1.5);
+    /// assert_eq!(4, bbow.match_count("fish"));
+    /// assert_eq!(2, bbow.match_count("two"));
+    /// ```
+    pub fn merge_weighted(&mut self, other: &Bbow<'a>, weight: f64) {
+        for (word, &count) in &other.counts {
+            // Float-to-integer casts saturate, so negative
+            // and NaN weights give 0.
+            let count = (count as f64 * weight).round() as usize;
+            if count == 0 {
+                continue;
+            }
+            *entry_for(&mut self.counts, word) += count;
+            if let Some(forms) = other.tracking.variants.get(word) {
+                let variants = entry_for(&mut self.tracking.variants, word);
+                variants.extend(forms.iter().cloned());
+            }
+        }
+    }
+
+    /// Parse the `target` text and take the sequence of
+    /// valid words contained in it back out of this BBOW,
+    /// undoing an earlier [`Bbow::extend_from_text`] of the
+    /// same text. Words that were never added are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new()
+    ///     .extend_from_text("old news")
+    ///     .extend_from_text("new news");
+    /// bbow.remove_text("old news");
+    /// assert_eq!(vec!["new", "news"], bbow.words().collect::<Vec<_>>());
+    /// ```
+    pub fn remove_text(&mut self, target: &str) {
+        let words: Vec<Cow<str>> = self.options.words(target).collect();
+        for word in words {
+            self.decrement(&word, 1);
+        }
+    }
+
+    /// Set the count of `word` in this BBOW to `count`,
+    /// giving its previous count if it had one. A `count` of
+    /// 0 removes the word. The word is used as given: it is
+    /// not lowercased or checked against the options of this
+    /// BBOW.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("teh cat");
+    /// assert_eq!(Some(1), bbow.insert("teh", 0));
+    /// assert_eq!(None, bbow.insert("the", 1));
+    /// assert_eq!(vec!["cat", "the"], bbow.words().collect::<Vec<_>>());
+    /// ```
+    pub fn insert(&mut self, word: impl Into<Cow<'a, str>>, count: usize) -> Option<usize> {
+        let word = word.into();
+        if count == 0 {
+            self.tracking.remove(&word);
+            return self.counts.remove(word.as_ref());
+        }
+        if let Some(old) = self.counts.get_mut(word.as_ref()) {
+            return Some(std::mem::replace(old, count));
+        }
+        self.counts.insert(intern::word(word), count)
+    }
+
+    /// Add one occurrence of `word` to this BBOW. As with
+    /// [`Bbow::insert`], the word is used as given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new();
+    /// bbow.increment("once");
+    /// bbow.increment("once");
+    /// assert_eq!(2, bbow.match_count("once"));
+    /// ```
+    pub fn increment(&mut self, word: impl Into<Cow<'a, str>>) {
+        self.add(word.into(), 1);
+    }
+
+    /// Add `n` occurrences of `word`, as [`add_word`] does.
+    pub(crate) fn add(&mut self, word: Cow<'a, str>, n: usize) {
+        add_word(&mut self.counts, word, n);
+    }
+
+    /// A mutable reference to the count of `word`, if it is
+    /// in this BBOW. A word whose count is set to 0 through
+    /// it is removed when the reference is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("a a b");
+    /// if let Some(mut count) = bbow.get_mut("a") {
+    ///     *count *= 10;
+    /// }
+    /// assert_eq!(20, bbow.match_count("a"));
+    /// *bbow.get_mut("b").unwrap() = 0;
+    /// assert_eq!(vec!["a"], bbow.words().collect::<Vec<_>>());
+    /// assert!(bbow.get_mut("z").is_none());
+    /// ```
+    pub fn get_mut<'m>(&'m mut self, word: &'m str) -> Option<CountMut<'m, 'a>> {
+        if !self.counts.contains_key(word) {
+            return None;
+        }
+        Some(CountMut { bbow: self, word })
+    }
+
+    /// Take `n` occurrences of `word` out of this BBOW. A
+    /// word whose count reaches zero is removed entirely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("a a a b");
+    /// bbow.decrement("a", 2);
+    /// bbow.decrement("b", 5);
+    /// assert_eq!(1, bbow.match_count("a"));
+    /// assert_eq!(1, bbow.len());
+    /// ```
+    pub fn decrement(&mut self, word: &str, n: usize) {
+        if let Some(count) = self.counts.get_mut(word) {
+            if *count > n {
+                *count -= n;
+            } else {
+                self.counts.remove(word);
+                self.tracking.remove(word);
+            }
+        }
+    }
+
+    /// Keep only the words for which `keep(word, count)` is
+    /// true, as [`BTreeMap::retain`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("a big big dog and a cat");
+    /// bbow.retain(|word, count| word.len() > 1 && count == 1);
+    /// assert_eq!(vec!["and", "cat", "dog"], bbow.words().collect::<Vec<_>>());
+    /// ```
+    pub fn retain<F: FnMut(&str, usize) -> bool>(&mut self, mut keep: F) {
+        self.counts.retain(|word, &mut count| keep(word, count));
+        let words = &self.counts;
+        self.tracking
+            .variants
+            .retain(|word, _| words.contains_key(word));
+        self.tracking
+            .positions
+            .retain(|word, _| words.contains_key(word));
+        self.tracking
+            .documents
+            .retain(|word, _| words.contains_key(word));
+    }
+
+    /// Remove the words that occur fewer than `min_count`
+    /// times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("x y y z z z");
+    /// bbow.prune_below(2);
+    /// assert_eq!(vec!["y", "z"], bbow.words().collect::<Vec<_>>());
+    /// ```
+    pub fn prune_below(&mut self, min_count: usize) {
+        self.retain(|_, count| count >= min_count);
+    }
+
+    /// Remove the words that occur more than `max_count`
+    /// times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("x y y z z z");
+    /// bbow.prune_above(2);
+    /// assert_eq!(vec!["x", "y"], bbow.words().collect::<Vec<_>>());
+    /// ```
+    pub fn prune_above(&mut self, max_count: usize) {
+        self.retain(|_, count| count <= max_count);
+    }
+
+    /// Remove all the words from this BBOW, keeping the
+    /// options it was built with. Positions start again from
+    /// 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("some words");
+    /// bbow.clear();
+    /// assert!(bbow.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.counts.clear();
+        self.tracking = Tracking::default();
+    }
+
+    /// Remove all the words from this BBOW, as with
+    /// [`Bbow::clear`], and give them with their counts in
+    /// alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("b a b");
+    /// let entries: Vec<(String, usize)> = bbow.drain().map(|(w, c)| (w.into_owned(), c)).collect();
+    /// assert_eq!(vec![("a".to_string(), 1), ("b".to_string(), 2)], entries);
+    /// assert!(bbow.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> impl Iterator<Item = (Cow<'a, str>, usize)> {
+        let words = std::mem::take(&mut self.counts);
+        self.tracking = Tracking::default();
+        words.into_iter().map(|(word, count)| (word.into(), count))
+    }
+
     /// Report the number of occurrences of the given
     /// `keyword` that are indexed by this BBOW. The keyword
     /// should be lowercase and not contain punctuation, as
@@ -108,14 +858,250 @@ impl<'a> Bbow<'a> {
     /// to check if the keyword is a word and then looks
     /// up the keyword in the internal bbow, returning the count or 0
     pub fn match_count(&self, keyword: &str) -> usize {
-        if !is_word(keyword) {
+        if !self.options.is_token(keyword) {
             return 0;
         }
-        self.0.get(keyword).cloned().unwrap_or(0)
+        self.counts.get(keyword).cloned().unwrap_or(0)
+    }
+
+    /// Is `word` in this BBOW? The word is looked up as
+    /// given, like the words of [`Bbow::insert`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("Hello world");
+    /// assert!(bbow.contains("hello"));
+    /// assert!(!bbow.contains("Hello"));
+    /// ```
+    pub fn contains(&self, word: &str) -> bool {
+        self.counts.contains_key(word)
+    }
+
+    /// Report the number of occurrences of `keyword` put
+    /// in the form this BBOW counts words in: trimmed,
+    /// lowercased and otherwise processed as the text added
+    /// to it was. So unlike [`Bbow::match_count`], this finds
+    /// "hello" for the keyword "Hello!". Returns 0 if the
+    /// keyword is not exactly one word when processed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("Hello, hello world");
+    /// assert_eq!(0, bbow.match_count("Hello!"));
+    /// assert_eq!(2, bbow.match_count_normalized("Hello!"));
+    /// assert_eq!(0, bbow.match_count_normalized("hello world"));
+    /// ```
+    pub fn match_count_normalized(&self, keyword: &str) -> usize {
+        let mut words = self.options.words(keyword);
+        match (words.next(), words.next()) {
+            (Some(word), None) => self.counts.get(word.as_ref()).copied().unwrap_or(0),
+            _ => 0,
+        }
     }
 
     pub fn words(&'a self) -> impl Iterator<Item = &'a str> {
-        self.0.keys().map(|w| w.as_ref())
+        self.counts.keys().map(|w| w.as_ref())
+    }
+
+    /// The words of this BBOW with their counts, in
+    /// alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("b a b");
+    /// assert_eq!(vec![("a", 1), ("b", 2)], bbow.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.counts.iter().map(|(w, &c)| (w.as_ref(), c))
+    }
+
+    /// This BBOW with every word copied, so that it no
+    /// longer borrows from the texts it was made from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let text = String::from("short lived");
+    /// let bbow = Bbow::new().extend_from_text(&text).into_owned();
+    /// drop(text);
+    /// assert_eq!(1, bbow.match_count("lived"));
+    /// ```
+    pub fn into_owned(self) -> Bbow<'static> {
+        Bbow {
+            counts: self
+                .counts
+                .into_iter()
+                .map(|(w, c)| (w.into_owned(), c))
+                .collect(),
+            options: self.options,
+            tracking: self.tracking.into_owned(),
+        }
+    }
+
+    /// The forms in which `word` was written in the texts
+    /// added to this BBOW, in sorted order. Forms are only
+    /// recorded by a BBOW built with
+    /// [`BbowBuilder::track_variants`]; otherwise there are
+    /// none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::builder()
+    ///     .track_variants(true)
+    ///     .build()
+    ///     .extend_from_text("Hello! HELLO? hello, Hello.");
+    /// assert_eq!(4, bbow.match_count("hello"));
+    /// assert_eq!(vec!["HELLO", "Hello", "hello"], bbow.variants("hello").collect::<Vec<_>>());
+    /// ```
+    pub fn variants(&self, word: &str) -> impl Iterator<Item = &str> {
+        self.tracking
+            .variants
+            .get(word)
+            .into_iter()
+            .flat_map(|forms| forms.iter().map(|form| form.as_ref()))
+    }
+
+    /// The positions at which `word` occurs in the texts
+    /// added to this BBOW, in increasing order. The first
+    /// word added is at position 0, the next at position 1,
+    /// and so on through all the texts added. Positions are
+    /// only recorded by a BBOW built with
+    /// [`BbowBuilder::track_positions`]; otherwise there are
+    /// none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::builder()
+    ///     .track_positions(true)
+    ///     .build()
+    ///     .extend_from_text("to be or not to be")
+    ///     .extend_from_text("To sleep");
+    /// assert_eq!(&[0, 4, 6], bbow.positions("to"));
+    /// assert!(bbow.positions("dream").is_empty());
+    /// ```
+    pub fn positions(&self, word: &str) -> &[usize] {
+        self.tracking.positions.get(word).map_or(&[], Vec::as_slice)
+    }
+
+    /// The number of occurrences of `word` in the texts
+    /// added with [`Bbow::extend_from_text_tagged`] and the
+    /// tag `doc_id`. Words of texts added otherwise are in
+    /// no tagged text. As for [`Bbow::positions`], counts
+    /// lowered by [`Bbow::decrement`] are still recorded
+    /// here until the word is gone.
+    pub fn count_in(&self, word: &str, doc_id: usize) -> usize {
+        self.tracking
+            .documents
+            .get(word)
+            .and_then(|documents| documents.get(&doc_id))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The number of times the words of `phrase` occur one
+    /// right after the other in the texts added to this
+    /// BBOW. The phrase is split into words as the texts
+    /// were, so that case and punctuation do not matter, and
+    /// words left out of this BBOW, such as stopwords, are
+    /// skipped in both. As positions run on from one text to
+    /// the next, a phrase may span two texts. Like
+    /// [`Bbow::positions`], this needs a BBOW built with
+    /// [`BbowBuilder::track_positions`]; otherwise it is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::builder()
+    ///     .track_positions(true)
+    ///     .build()
+    ///     .extend_from_text("A big bag, a big red bag and a big bag.");
+    /// assert_eq!(2, bbow.phrase_count("big bag"));
+    /// assert_eq!(3, bbow.phrase_count("A big"));
+    /// assert_eq!(0, bbow.phrase_count("bag big"));
+    /// ```
+    pub fn phrase_count(&self, phrase: &str) -> usize {
+        let words: Vec<Cow<str>> = self.options.words(phrase).collect();
+        let Some((first, rest)) = words.split_first() else {
+            return 0;
+        };
+        self.positions(first)
+            .iter()
+            .filter(|&&start| {
+                rest.iter()
+                    .enumerate()
+                    .all(|(i, word)| self.positions(word).binary_search(&(start + i + 1)).is_ok())
+            })
+            .count()
+    }
+
+    /// The `n` most frequent words in this BBOW with their
+    /// counts, most frequent first. Words with equal
+    /// counts are given in alphabetical order.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new()
+    ///     .extend_from_text("a rose is a rose is a rose");
+    /// assert_eq!(vec![("a", 3), ("rose", 3)], bbow.top_n(2));
+    /// ```
+    pub fn top_n(&self, n: usize) -> Vec<(&str, usize)> {
+        if n == 0 {
+            return Vec::new();
+        }
+        // A min-heap of the best `n` words so far, whose top
+        // is the worst of them, so that the whole vocabulary
+        // never needs sorting.
+        let mut best = BinaryHeap::with_capacity(n + 1);
+        for (word, count) in self.iter() {
+            best.push(Reverse((count, Reverse(word))));
+            if best.len() > n {
+                best.pop();
+            }
+        }
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((count, Reverse(word)))| (word, count))
+            .collect()
+    }
+
+    /// The words of this BBOW with their counts, most
+    /// frequent first and in alphabetical order for equal
+    /// counts, as [`Bbow::top_n`] gives them, but produced
+    /// one at a time: taking the first `k` words costs time
+    /// linear in the number of words, plus logarithmic time
+    /// for each word taken.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a rose is a rose is a rose");
+    /// let mut top = bbow.top_k_iter();
+    /// assert_eq!(Some(("a", 3)), top.next());
+    /// assert_eq!(Some(("rose", 3)), top.next());
+    /// assert_eq!(Some(("is", 2)), top.next());
+    /// assert_eq!(None, top.next());
+    /// ```
+    pub fn top_k_iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        let mut heap: BinaryHeap<(usize, Reverse<&str>)> = self
+            .iter()
+            .map(|(word, count)| (count, Reverse(word)))
+            .collect();
+        std::iter::from_fn(move || heap.pop().map(|(count, Reverse(word))| (word, count)))
     }
 
     /// Count the overall number of words contained in this BBOW:
@@ -134,7 +1120,7 @@ impl<'a> Bbow<'a> {
     /// counts the number of contained words in the bbow including
     /// multi-occurance words
     pub fn count(&self) -> usize {
-        self.0.values().sum()
+        self.counts.values().sum()
     }
 
     /// Count the number of unique words contained in this BBOW,
@@ -152,7 +1138,7 @@ impl<'a> Bbow<'a> {
     /// ## len(&self) -> usize
     /// counts number of unique contained words
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.counts.len()
     }
 
     /// Is this BBOW empty?
@@ -160,7 +1146,66 @@ impl<'a> Bbow<'a> {
     /// ## is_empty(&self) -> bool
     /// determines whether or not a bbow is empty
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.counts.is_empty()
+    }
+}
+
+/// Two BBOWs are equal when they have the same words with
+/// the same counts, whatever options they were built with and
+/// whatever else they track.
+impl PartialEq for Bbow<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl Eq for Bbow<'_> {}
+
+/// Hashes the words and counts only, as equality compares
+/// them.
+impl Hash for Bbow<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.counts.hash(state);
+    }
+}
+
+/// A table of the words and their counts, one word per
+/// line, in alphabetical order. The alternate form `{:#}`
+/// lists the words most frequent first instead, as
+/// [`Bbow::top_n`] does.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::Bbow;
+/// let bbow = Bbow::new().extend_from_text("a rose is a rose is a rose");
+/// assert_eq!("a     3\nis    2\nrose  3", bbow.to_string());
+/// assert_eq!("a     3\nrose  3\nis    2", format!("{bbow:#}"));
+/// ```
+impl fmt::Display for Bbow<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries: Vec<(&str, usize)> = if f.alternate() {
+            self.top_n(self.len())
+        } else {
+            self.iter().collect()
+        };
+        let word_width = entries
+            .iter()
+            .map(|(w, _)| w.chars().count())
+            .max()
+            .unwrap_or(0);
+        let count_width = entries
+            .iter()
+            .map(|(_, c)| c.to_string().len())
+            .max()
+            .unwrap_or(0);
+        for (i, (word, count)) in entries.into_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{word:word_width$}  {count:>count_width$}")?;
+        }
+        Ok(())
     }
 }
 /// # Testing Area
@@ -216,6 +1261,83 @@ mod tests {
         assert_eq!(0, bbow.len());
         assert_eq!(0, bbow.count());
     }
+    /// tokens testing
+    #[test]
+    fn test_tokens() {
+        let text = "Hello, wörld! 123 Ça va?";
+        let words: Vec<Cow<str>> = tokens(text).collect();
+        assert_eq!(vec!["hello", "wörld", "ça", "va"], words);
+        assert!(matches!(words[1], Cow::Borrowed(_)));
+        assert!(matches!(words[2], Cow::Owned(_)));
+        let bbow = Bbow::new().extend_from_text(text);
+        assert_eq!(bbow.count(), tokens(text).count());
+        assert_eq!(0, tokens(" !@# ").count());
+    }
+    /// scan_word testing
+    #[test]
+    fn test_scan_word() {
+        for token in [
+            "", "¡¿", "Wörld!", "«Ça»", "ain't", "x1", "ŁÓDŹ", "-é-", "a-b", "ǅ",
+        ] {
+            let word = trim_word(token);
+            assert_eq!(
+                is_word(word).then(|| lowercase(word)),
+                scan_word(token),
+                "{token:?}"
+            );
+        }
+    }
+    /// token_spans testing
+    #[test]
+    fn test_token_spans() {
+        for text in [
+            "Hello, wörld! 123 Ça va?",
+            "\"Quoted,\" she said.\n--OK",
+            "",
+        ] {
+            let spans: Vec<_> = token_spans(text).collect();
+            for (range, word) in &spans {
+                assert_eq!(*word, &text[range.clone()]);
+            }
+            let lowered: Vec<String> = spans.iter().map(|(_, w)| w.to_lowercase()).collect();
+            assert_eq!(tokens(text).collect::<Vec<_>>(), lowered);
+        }
+        let spans: Vec<_> = token_spans("\"Quoted,\" she").collect();
+        assert_eq!(vec![(1..7, "Quoted"), (10..13, "she")], spans);
+    }
+    /// contains testing
+    #[test]
+    fn test_contains() {
+        let mut bbow = Bbow::new().extend_from_text("one two two");
+        assert!(bbow.contains("two"));
+        assert!(!bbow.contains("three"));
+        assert!(!bbow.contains(""));
+        bbow.decrement("one", 1);
+        assert!(!bbow.contains("one"));
+        bbow.insert("Three!", 1);
+        assert!(bbow.contains("Three!"));
+        assert_eq!(0, bbow.match_count("Three!"));
+    }
+    /// match_count_normalized testing
+    #[test]
+    fn test_match_count_normalized() {
+        let bbow = Bbow::builder()
+            .filter(crate::Stemmer::english())
+            .build()
+            .extend_from_text("Running runs; the RUNNER ran.");
+        assert_eq!(2, bbow.match_count_normalized("\"Running\""));
+        assert_eq!(0, bbow.match_count("Running"));
+        assert_eq!(1, bbow.match_count_normalized("runners"));
+        for keyword in ["", "!?", "run run", "123"] {
+            assert_eq!(0, bbow.match_count_normalized(keyword), "{keyword:?}");
+        }
+        let stop = Bbow::builder()
+            .stopwords(StopWords::english())
+            .build()
+            .extend_from_text("the end");
+        assert_eq!(0, stop.match_count_normalized("The"));
+        assert_eq!(1, stop.match_count_normalized("END."));
+    }
     /// match_count testing
     #[test]
     fn test_match_count_basic() {
@@ -262,4 +1384,298 @@ mod tests {
         assert_eq!(0, bbow.match_count("banana1"));
         assert_eq!(0, bbow.match_count(""));
     }
+
+    /// top_n testing
+    #[test]
+    fn test_top_n() {
+        let bbow = Bbow::new().extend_from_text("c b a b c c");
+        assert_eq!(vec![("c", 3), ("b", 2), ("a", 1)], bbow.top_n(10));
+        assert_eq!(vec![("c", 3)], bbow.top_n(1));
+        assert!(bbow.top_n(0).is_empty());
+    }
+
+    /// remove_text and decrement testing
+    #[test]
+    fn test_remove_text() {
+        let mut bbow = Bbow::new()
+            .extend_from_text("The cat sat.")
+            .extend_from_text("The dog sat down.");
+        bbow.remove_text("THE CAT SAT, and the bird flew");
+        assert_eq!(vec!["dog", "down", "sat"], bbow.words().collect::<Vec<_>>());
+        assert_eq!(3, bbow.count());
+        bbow.remove_text("The dog sat down.");
+        assert!(bbow.is_empty());
+    }
+
+    #[test]
+    fn test_decrement() {
+        let mut bbow = Bbow::new().extend_from_text("a a a");
+        bbow.decrement("a", 0);
+        assert_eq!(3, bbow.match_count("a"));
+        bbow.decrement("missing", 1);
+        bbow.decrement("a", 3);
+        assert!(bbow.is_empty());
+    }
+
+    /// retain testing
+    #[test]
+    fn test_retain() {
+        let mut bbow = Bbow::new().extend_from_text("x y y z z z");
+        bbow.retain(|_, count| count >= 2);
+        assert_eq!(vec![("z", 3), ("y", 2)], bbow.top_n(10));
+        bbow.retain(|word, _| word != "z");
+        assert_eq!(vec![("y", 2)], bbow.top_n(10));
+        bbow.retain(|_, _| false);
+        assert!(bbow.is_empty());
+    }
+
+    /// variants testing
+    #[test]
+    fn test_variants() {
+        let builder = Bbow::builder().track_variants(true);
+        let variants =
+            |bbow: &Bbow, word| bbow.variants(word).map(String::from).collect::<Vec<_>>();
+        let mut bbow = builder
+            .build()
+            .extend_from_text("Paris is in France. PARIS!");
+        assert_eq!(vec!["PARIS", "Paris"], variants(&bbow, "paris"));
+        assert_eq!(vec!["is"], variants(&bbow, "is"));
+        assert!(variants(&bbow, "Paris").is_empty());
+        bbow.merge(&builder.build().extend_from_text("paris"));
+        assert_eq!(vec!["PARIS", "Paris", "paris"], variants(&bbow, "paris"));
+        bbow.decrement("paris", 3);
+        assert!(variants(&bbow, "paris").is_empty());
+        bbow.retain(|word, _| word != "in");
+        assert!(variants(&bbow, "in").is_empty());
+        assert_eq!(vec!["France"], variants(&bbow, "france"));
+        let bbow = Bbow::new().extend_from_text("Paris");
+        assert!(variants(&bbow, "paris").is_empty());
+    }
+
+    /// positions testing
+    #[test]
+    fn test_positions() {
+        let builder = Bbow::builder()
+            .track_positions(true)
+            .stopwords(StopWords::new().with_words(["the"]));
+        let mut bbow = builder
+            .build()
+            .extend_from_text("The cat saw the other cat.");
+        assert_eq!(&[0, 3], bbow.positions("cat"));
+        assert!(bbow.positions("the").is_empty());
+        bbow.merge(&builder.build().extend_from_text("A cat"));
+        assert_eq!(&[0, 3, 5], bbow.positions("cat"));
+        bbow.decrement("cat", 3);
+        assert!(bbow.positions("cat").is_empty());
+        bbow.retain(|word, _| word != "saw");
+        assert!(bbow.positions("saw").is_empty());
+        assert_eq!(&[2], bbow.positions("other"));
+        assert!(Bbow::new()
+            .extend_from_text("cat")
+            .positions("cat")
+            .is_empty());
+    }
+
+    /// extend_from_text_tagged testing
+    #[test]
+    fn test_tagged() {
+        let mut bbow = Bbow::builder()
+            .track_positions(true)
+            .build()
+            .extend_from_text_tagged("A cat. A hat.", 1)
+            .extend_from_text("a cat")
+            .extend_from_text_tagged("The cat", 2);
+        assert_eq!(3, bbow.match_count("cat"));
+        assert_eq!(&[1, 5, 7], bbow.positions("cat"));
+        assert_eq!(1, bbow.count_in("cat", 1));
+        assert_eq!(2, bbow.count_in("a", 1));
+        assert_eq!(1, bbow.count_in("cat", 2));
+        assert_eq!(0, bbow.count_in("cat", 3));
+        bbow.merge(&Bbow::new().extend_from_text_tagged("cat", 2));
+        assert_eq!(2, bbow.count_in("cat", 2));
+        bbow.retain(|word, _| word != "a");
+        assert_eq!(0, bbow.count_in("a", 1));
+        let owned = bbow.clone().into_owned();
+        assert_eq!(1, owned.count_in("hat", 1));
+        bbow.clear();
+        assert_eq!(0, bbow.count_in("cat", 1));
+    }
+
+    /// top_n testing
+    #[test]
+    fn test_top_n_heap() {
+        let bbow = Bbow::new().extend_from_text("e d d c c c b b b a a a a z");
+        let mut sorted: Vec<(&str, usize)> = bbow.iter().collect();
+        sorted.sort_by(|(w1, c1), (w2, c2)| c2.cmp(c1).then(w1.cmp(w2)));
+        for n in 0..=bbow.len() + 1 {
+            assert_eq!(&sorted[..n.min(sorted.len())], bbow.top_n(n).as_slice());
+            assert_eq!(
+                &sorted[..n.min(sorted.len())],
+                bbow.top_k_iter().take(n).collect::<Vec<_>>()
+            );
+        }
+        assert_eq!(None, Bbow::new().top_k_iter().next());
+    }
+
+    /// extend_from_text_with testing
+    #[test]
+    fn test_extend_from_text_with() {
+        let mut seen = Vec::new();
+        let bbow = Bbow::builder()
+            .track_positions(true)
+            .build()
+            .extend_from_text_with("The Sea, the sea! Ocean", |word| {
+                seen.push(word.to_string());
+                match word.as_ref() {
+                    "the" => None,
+                    "ocean" => Some("sea".into()),
+                    _ => Some(word),
+                }
+            });
+        assert_eq!(vec!["the", "sea", "the", "sea", "ocean"], seen);
+        assert_eq!(vec![("sea", 3)], bbow.top_n(10));
+        assert_eq!(&[0, 1, 2], bbow.positions("sea"));
+        let same = Bbow::new().extend_from_text_with("a b a", Some);
+        assert_eq!(Bbow::new().extend_from_text("a b a"), same);
+    }
+
+    /// phrase_count testing
+    #[test]
+    fn test_phrase_count() {
+        let builder = Bbow::builder()
+            .track_positions(true)
+            .stopwords(StopWords::new().with_words(["the"]));
+        let bbow = builder
+            .build()
+            .extend_from_text("The cat saw the other cat. Cat saw cat saw")
+            .extend_from_text("Cat");
+        assert_eq!(3, bbow.phrase_count("cat saw"));
+        assert_eq!(1, bbow.phrase_count("CAT, SAW; OTHER!"));
+        assert_eq!(1, bbow.phrase_count("saw the other"));
+        assert_eq!(2, bbow.phrase_count("saw cat"));
+        assert_eq!(5, bbow.phrase_count("cat"));
+        assert_eq!(0, bbow.phrase_count(""));
+        assert_eq!(0, bbow.phrase_count("the"));
+        assert_eq!(0, bbow.phrase_count("cat dog"));
+        let untracked = Bbow::new().extend_from_text("cat saw");
+        assert_eq!(0, untracked.phrase_count("cat saw"));
+    }
+
+    /// prune_below and prune_above testing
+    #[test]
+    fn test_prune_band() {
+        let mut bbow = Bbow::new().extend_from_text("a b b c c c d d d d");
+        bbow.prune_below(0);
+        bbow.prune_above(usize::MAX);
+        assert_eq!(4, bbow.len());
+        bbow.prune_below(2);
+        bbow.prune_above(3);
+        assert_eq!(vec![("c", 3), ("b", 2)], bbow.top_n(10));
+        bbow.prune_below(4);
+        assert!(bbow.is_empty());
+    }
+
+    /// merge_weighted testing
+    #[test]
+    fn test_merge_weighted() {
+        let other = Bbow::new().extend_from_text("a b b b b");
+        let mut bbow = Bbow::new();
+        bbow.merge_weighted(&other, 0.25);
+        assert_eq!(vec![("b", 1)], bbow.top_n(10));
+        bbow.merge_weighted(&other, -1.0);
+        bbow.merge_weighted(&other, f64::NAN);
+        assert_eq!(vec![("b", 1)], bbow.top_n(10));
+        bbow.merge_weighted(&other, 1.0);
+        assert_eq!(vec![("b", 5), ("a", 1)], bbow.top_n(10));
+    }
+
+    /// equality and hashing testing
+    #[test]
+    // The pool of shared words in the options is mutable,
+    // but the options are not hashed.
+    #[allow(clippy::mutable_key_type)]
+    fn test_eq_hash() {
+        use std::collections::HashSet;
+        let text = String::from("Apple apple pear");
+        let borrowed = Bbow::new().extend_from_text(&text);
+        let mut owned = Bbow::builder().track_positions(true).build();
+        owned.insert("apple".to_string(), 2);
+        owned.insert("pear".to_string(), 1);
+        assert_eq!(borrowed, owned);
+        let bags: HashSet<Bbow> = [borrowed.clone(), owned, Bbow::new()].into_iter().collect();
+        assert_eq!(2, bags.len());
+        assert_ne!(borrowed, Bbow::new().extend_from_text("apple pear"));
+    }
+
+    /// Display testing
+    #[test]
+    fn test_display() {
+        assert_eq!("", Bbow::new().to_string());
+        let text = "b ".repeat(10);
+        let bbow = Bbow::new().extend_from_text(&text).extend_from_text("ça");
+        assert_eq!("b   10\nça   1", bbow.to_string());
+        assert_eq!(bbow.to_string(), format!("{bbow:#}"));
+    }
+
+    /// clear and drain testing
+    #[test]
+    fn test_clear_drain() {
+        let mut bbow = Bbow::builder()
+            .track_positions(true)
+            .build()
+            .extend_from_text("x y");
+        assert_eq!(2, bbow.drain().count());
+        bbow = bbow.extend_from_text("Y");
+        assert_eq!(&[0], bbow.positions("y"));
+        bbow.clear();
+        assert!(bbow.positions("y").is_empty());
+        bbow = bbow.extend_from_text("z");
+        assert_eq!(&[0], bbow.positions("z"));
+    }
+
+    /// insert, increment and get_mut testing
+    #[test]
+    fn test_entry_manipulation() {
+        let mut bbow = Bbow::new();
+        assert_eq!(None, bbow.insert("gone", 0));
+        bbow.increment(String::from("owned"));
+        assert_eq!(Some(1), bbow.insert("owned", 5));
+        *bbow.get_mut("owned").unwrap() += 2;
+        assert_eq!(7, bbow.match_count("owned"));
+        *bbow.get_mut("owned").unwrap() = 0;
+        assert!(bbow.is_empty());
+    }
+
+    /// entry_for testing
+    #[test]
+    fn test_merge_keeps_keys() {
+        let mut total = Bbow::new().extend_from_text("The");
+        let key = total.counts.keys().next().unwrap().as_ptr();
+        total.merge(&Bbow::new().extend_from_text("THE"));
+        total.merge_weighted(&Bbow::new().extend_from_text("tHe"), 2.0);
+        assert_eq!(4, total.match_count("the"));
+        assert_eq!(key, total.counts.keys().next().unwrap().as_ptr());
+    }
+
+    /// interning testing
+    #[test]
+    fn test_shared_keys() {
+        let builder = Bbow::builder().track_positions(true);
+        let first = |bbow: &Bbow| bbow.counts.keys().next().unwrap().as_ptr();
+        let a = builder.build().extend_from_text("Hello");
+        let b = builder.build().extend_from_text("Hello there");
+        assert_eq!(first(&a), first(&b));
+        assert_eq!(
+            first(&a),
+            a.tracking.positions.keys().next().unwrap().as_ptr()
+        );
+        let mut c = builder.build();
+        c.insert(String::from("hello"), 2);
+        assert_eq!(first(&a), first(&c));
+        let text = String::from("hello");
+        let owned = builder.build().extend_from_text(&text).into_owned();
+        assert_eq!(first(&a), first(&owned));
+        let other = Bbow::new().extend_from_text("HELLO");
+        assert_eq!(first(&a), first(&other));
+    }
 }
@@ -0,0 +1,983 @@
+//! Word-frequency tool: print the Big Bag Of Words of some
+//! text files (or of standard input), or how often a single
+//! keyword occurs in each of them.
+//!
+//! The `eval` subcommand instead combines named bags with a
+//! small expression language; see [`parse_expr`]. The
+//! `verify` subcommand checks tokenization against golden
+//! files; see [`run_verify`].
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::{self, Read, Write};
+use std::process::exit;
+
+use bbow::{Bbow, Corpus};
+
+#[path = "escape.rs"]
+mod escape;
+
+use escape::{csv_field, json_string};
+
+const USAGE: &str = "usage: bbow [--sort alpha|count] [--top N] [--keyword WORD] \
+                     [--format table|json|csv|tsv] [FILE|GLOB|- ...]
+       bbow eval EXPR [NAME=FILE ...] [--out FILE] [--format table|json|csv|tsv]
+       bbow verify [--bless] [DIR]";
+
+/// Order in which to print the frequency table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Sort {
+    Alpha,
+    Count,
+}
+
+/// Output format of the frequency table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// Aligned columns for reading.
+    Table,
+    /// A JSON object mapping words to counts.
+    Json,
+    /// `word,count` lines with a header.
+    Csv,
+    /// `word<TAB>count` lines with a header.
+    Tsv,
+}
+
+/// Parsed command-line arguments.
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    /// Table order; the default is alphabetical for the
+    /// full table and by count for `--top`. With `--keyword`
+    /// this orders the files, which are otherwise in the
+    /// order given.
+    sort: Option<Sort>,
+    /// Print only this many of the most frequent words, or
+    /// with `--keyword` of the files with the most.
+    top: Option<usize>,
+    format: Format,
+    /// Print a per-file breakdown of this word's count
+    /// instead of the frequency table.
+    keyword: Option<String>,
+    paths: Vec<String>,
+}
+
+/// Why command-line arguments were not parsed.
+#[derive(Debug, PartialEq, Eq)]
+enum ArgError {
+    /// `-h` or `--help`: print the usage and succeed.
+    Help,
+    /// Bad arguments: print this message and fail.
+    Bad(String),
+}
+
+impl From<String> for ArgError {
+    fn from(msg: String) -> Self {
+        ArgError::Bad(msg)
+    }
+}
+
+/// The parsed arguments, or else exit: with the usage on
+/// standard output if help was asked for, and with the error
+/// on standard error and a failure status otherwise.
+fn or_exit<T>(parsed: Result<T, ArgError>) -> T {
+    match parsed {
+        Ok(parsed) => parsed,
+        Err(ArgError::Help) => {
+            println!("{USAGE}");
+            exit(0);
+        }
+        Err(ArgError::Bad(msg)) => {
+            eprintln!("{msg}");
+            exit(1);
+        }
+    }
+}
+
+fn parse_format(arg: Option<String>) -> Result<Format, String> {
+    match arg.as_deref() {
+        Some("table") => Ok(Format::Table),
+        Some("json") => Ok(Format::Json),
+        Some("csv") => Ok(Format::Csv),
+        Some("tsv") => Ok(Format::Tsv),
+        Some(s) => Err(format!("unknown format {s:?}")),
+        None => Err("--format needs an argument".to_string()),
+    }
+}
+
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Args, ArgError> {
+    let mut sort = None;
+    let mut top = None;
+    let mut format = Format::Table;
+    let mut keyword = None;
+    let mut paths = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--sort" => {
+                sort = match args.next().as_deref() {
+                    Some("alpha") => Some(Sort::Alpha),
+                    Some("count") => Some(Sort::Count),
+                    Some(s) => return Err(format!("unknown sort order {s:?}").into()),
+                    None => return Err("--sort needs an argument".to_string().into()),
+                }
+            }
+            "--top" => {
+                top = match args.next().map(|n| n.parse()) {
+                    Some(Ok(n)) => Some(n),
+                    Some(Err(e)) => return Err(format!("bad --top count: {e}").into()),
+                    None => return Err("--top needs an argument".to_string().into()),
+                }
+            }
+            "--format" => format = parse_format(args.next())?,
+            "--keyword" => match args.next() {
+                Some(word) => keyword = Some(word.to_lowercase()),
+                None => return Err("--keyword needs an argument".to_string().into()),
+            },
+            "-h" | "--help" => return Err(ArgError::Help),
+            s if s.starts_with("--") => return Err(format!("unknown flag {s:?}").into()),
+            _ => paths.push(arg),
+        }
+    }
+    Ok(Args {
+        sort,
+        top,
+        format,
+        keyword,
+        paths,
+    })
+}
+
+/// Read the text at `path`, where `-` is standard input.
+fn read_text(path: &str) -> io::Result<String> {
+    if path != "-" {
+        return std::fs::read_to_string(path);
+    }
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text)?;
+    Ok(text)
+}
+
+/// Does `name` match the glob `pattern`? In the pattern,
+/// `*` matches any run of characters and `?` any single
+/// character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    // Classic backtracking matcher: remember the most recent
+    // `*` and retry from there on a mismatch.
+    let (mut p, mut n) = (0, 0);
+    let mut star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((sp, sn)) => {
+                    p = sp + 1;
+                    n = sn + 1;
+                    star = Some((sp, sn + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Expand `path` into the sorted list of files it names.
+/// Wildcards are only supported in the last component of
+/// the path; a path without wildcards names itself.
+fn expand_glob(path: &str) -> io::Result<Vec<String>> {
+    let (dir, pattern) = match path.rfind('/') {
+        Some(i) => (&path[..=i], &path[i + 1..]),
+        None => ("", path),
+    };
+    if !path.contains(['*', '?']) {
+        return Ok(vec![path.to_string()]);
+    }
+    if dir.contains(['*', '?']) {
+        let msg = format!("{path}: wildcards are only supported in file names");
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+    }
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(if dir.is_empty() { "." } else { dir })? {
+        let name = entry?.file_name();
+        let name = name.to_string_lossy();
+        if glob_match(pattern, &name) {
+            paths.push(format!("{dir}{name}"));
+        }
+    }
+    if paths.is_empty() {
+        let msg = format!("{path}: no matching files");
+        return Err(io::Error::new(io::ErrorKind::NotFound, msg));
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Read the texts named by `paths`, or standard input if
+/// there are none, as `(path, text)` pairs.
+fn read_texts(paths: &[String]) -> io::Result<Vec<(String, String)>> {
+    if paths.is_empty() {
+        return Ok(vec![("-".to_string(), read_text("-")?)]);
+    }
+    let mut texts = Vec::new();
+    for path in paths {
+        for path in expand_glob(path)? {
+            let text = read_text(&path)?;
+            texts.push((path, text));
+        }
+    }
+    Ok(texts)
+}
+
+/// The `(file, count)` rows of `keyword` in each document
+/// of `corpus`.
+fn breakdown<'a>(corpus: &'a Corpus, keyword: &str) -> Vec<(&'a str, usize)> {
+    corpus
+        .iter()
+        .map(|doc| (doc.name(), doc.bbow().match_count(keyword)))
+        .collect()
+}
+
+/// The `(file, count)` rows of a breakdown selected and
+/// ordered as requested by `args`: the files with the most
+/// occurrences for `--top`, in the order given unless
+/// sorted. Files with the same count keep their order.
+fn select_files<'a>(mut rows: Vec<(&'a str, usize)>, args: &Args) -> Vec<(&'a str, usize)> {
+    if let Some(n) = args.top {
+        rows.sort_by(|(_, c1), (_, c2)| c2.cmp(c1));
+        rows.truncate(n);
+    }
+    match args.sort {
+        Some(Sort::Alpha) => rows.sort_by_key(|&(file, _)| file),
+        Some(Sort::Count) => rows.sort_by(|(_, c1), (_, c2)| c2.cmp(c1)),
+        None => {}
+    }
+    rows
+}
+
+/// The `(word, count)` rows of `bbow` selected and ordered
+/// as requested by `args`.
+fn table<'a>(bbow: &'a Bbow<'a>, args: &Args) -> Vec<(&'a str, usize)> {
+    let mut rows = match args.top {
+        Some(n) => bbow.top_n(n),
+        None => bbow.iter().collect(),
+    };
+    let default_sort = if args.top.is_some() {
+        Sort::Count
+    } else {
+        Sort::Alpha
+    };
+    match args.sort.unwrap_or(default_sort) {
+        Sort::Alpha => rows.sort_by_key(|&(w, _)| w),
+        Sort::Count => rows.sort_by(|(w1, c1), (w2, c2)| c2.cmp(c1).then(w1.cmp(w2))),
+    }
+    rows
+}
+
+/// `field` escaped for TSV if it needs it: tabs, line
+/// breaks and backslashes become `\t`, `\n`, `\r` and `\\`,
+/// so that each row stays one line of tab-separated fields.
+fn tsv_field(field: &str) -> Cow<'_, str> {
+    if !field.contains(['\t', '\n', '\r', '\\']) {
+        return field.into();
+    }
+    let mut escaped = String::with_capacity(field.len() + 2);
+    for c in field.chars() {
+        match c {
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\\' => escaped.push_str("\\\\"),
+            c => escaped.push(c),
+        }
+    }
+    escaped.into()
+}
+
+/// Write `rows` to `w` in the given `format`. The `key`
+/// names the first column in formats with a header.
+fn write_table<W: Write>(
+    mut w: W,
+    key: &str,
+    rows: &[(&str, usize)],
+    format: Format,
+) -> io::Result<()> {
+    match format {
+        Format::Table => {
+            let width = rows.iter().map(|(word, _)| word.chars().count()).max();
+            for (word, count) in rows {
+                writeln!(w, "{word:<width$}  {count}", width = width.unwrap_or(0))?;
+            }
+        }
+        Format::Json => {
+            write!(w, "{{")?;
+            for (i, (word, count)) in rows.iter().enumerate() {
+                let sep = if i == 0 { "" } else { "," };
+                write!(w, "{sep}\n  {}: {count}", json_string(word))?;
+            }
+            writeln!(w, "{}}}", if rows.is_empty() { "" } else { "\n" })?;
+        }
+        Format::Csv => {
+            writeln!(w, "{key},count")?;
+            for (word, count) in rows {
+                writeln!(w, "{},{count}", csv_field(word))?;
+            }
+        }
+        Format::Tsv => {
+            writeln!(w, "{key}\tcount")?;
+            for (word, count) in rows {
+                writeln!(w, "{}\t{count}", tsv_field(word))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write the breakdown `rows` of a keyword, and the `total`
+/// count over all files, to `w` in the given `format`. JSON
+/// gives the files as an array, since a file may be named
+/// twice, or be named `total`; the other formats end with a
+/// `total` row.
+fn write_breakdown<W: Write>(
+    mut w: W,
+    rows: &[(&str, usize)],
+    total: usize,
+    format: Format,
+) -> io::Result<()> {
+    if format != Format::Json {
+        let mut rows = rows.to_vec();
+        rows.push(("total", total));
+        return write_table(w, "file", &rows, format);
+    }
+    write!(w, "{{\n  \"files\": [")?;
+    for (i, (file, count)) in rows.iter().enumerate() {
+        let sep = if i == 0 { "" } else { "," };
+        let file = json_string(file);
+        write!(w, "{sep}\n    {{\"file\": {file}, \"count\": {count}}}")?;
+    }
+    let end = if rows.is_empty() { "" } else { "\n  " };
+    writeln!(w, "{end}],\n  \"total\": {total}\n}}")
+}
+
+/// Parsed arguments of the `eval` subcommand.
+#[derive(Debug, PartialEq, Eq)]
+struct EvalArgs {
+    expr: String,
+    /// The `(name, path)` of each bag to load. Files ending
+    /// in `.csv` are read as saved bags; anything else is
+    /// read as text.
+    bags: Vec<(String, String)>,
+    /// Write the result here instead of to standard output.
+    out: Option<String>,
+    /// Output format; the default is chosen by the extension
+    /// of the output file.
+    format: Option<Format>,
+}
+
+fn parse_eval_args<I: IntoIterator<Item = String>>(args: I) -> Result<EvalArgs, ArgError> {
+    let mut expr = None;
+    let mut bags = Vec::new();
+    let mut out = None;
+    let mut format = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => match args.next() {
+                Some(path) => out = Some(path),
+                None => return Err("--out needs an argument".to_string().into()),
+            },
+            "--format" => format = Some(parse_format(args.next())?),
+            "-h" | "--help" => return Err(ArgError::Help),
+            s if s.starts_with("--") => return Err(format!("unknown flag {s:?}").into()),
+            _ if expr.is_none() => expr = Some(arg),
+            _ => match arg.split_once('=') {
+                Some((name, path)) if !name.is_empty() => {
+                    bags.push((name.to_string(), path.to_string()))
+                }
+                _ => return Err(format!("expected NAME=FILE, got {arg:?}").into()),
+            },
+        }
+    }
+    let expr = expr.ok_or_else(|| USAGE.to_string())?;
+    Ok(EvalArgs {
+        expr,
+        bags,
+        out,
+        format,
+    })
+}
+
+/// A bag expression.
+#[derive(Debug, PartialEq, Eq)]
+enum Expr {
+    /// A named bag.
+    Bag(String),
+    /// A binary operation, named by its operator character.
+    Op(char, Box<Expr>, Box<Expr>),
+}
+
+/// Parse a bag expression. Bags are named by identifiers,
+/// and combined with the operators
+///
+/// * `A + B`: the sum of the counts in `A` and `B`
+/// * `A | B`: union, with the larger of the counts
+/// * `A & B`: intersection, with the smaller of the counts
+/// * `A - B`: the words of `A` that are not in `B`
+///
+/// where `&` binds tighter than the others, operators of
+/// equal precedence group left to right, and parentheses
+/// group as usual.
+fn parse_expr(text: &str) -> Result<Expr, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if "+-|&()".contains(c) {
+            tokens.push(c.to_string());
+            chars.next();
+        } else if c.is_alphanumeric() || c == '_' {
+            let mut name = String::new();
+            while let Some(&c) = chars.peek().filter(|&&c| c.is_alphanumeric() || c == '_') {
+                name.push(c);
+                chars.next();
+            }
+            tokens.push(name);
+        } else {
+            return Err(format!("unexpected {c:?} in expression"));
+        }
+    }
+
+    fn sum(tokens: &[String], pos: &mut usize) -> Result<Expr, String> {
+        let mut left = product(tokens, pos)?;
+        while let Some(op @ ("+" | "-" | "|")) = tokens.get(*pos).map(String::as_str) {
+            *pos += 1;
+            let right = product(tokens, pos)?;
+            left = Expr::Op(op.chars().next().unwrap(), Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn product(tokens: &[String], pos: &mut usize) -> Result<Expr, String> {
+        let mut left = atom(tokens, pos)?;
+        while tokens.get(*pos).map(String::as_str) == Some("&") {
+            *pos += 1;
+            let right = atom(tokens, pos)?;
+            left = Expr::Op('&', Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn atom(tokens: &[String], pos: &mut usize) -> Result<Expr, String> {
+        let token = tokens.get(*pos).ok_or("unexpected end of expression")?;
+        *pos += 1;
+        match token.as_str() {
+            "(" => {
+                let expr = sum(tokens, pos)?;
+                if tokens.get(*pos).map(String::as_str) != Some(")") {
+                    return Err("missing ')' in expression".to_string());
+                }
+                *pos += 1;
+                Ok(expr)
+            }
+            t if t.starts_with(|c: char| c.is_alphanumeric() || c == '_') => {
+                Ok(Expr::Bag(t.to_string()))
+            }
+            t => Err(format!("unexpected {t:?} in expression")),
+        }
+    }
+
+    let mut pos = 0;
+    let expr = sum(&tokens, &mut pos)?;
+    match tokens.get(pos) {
+        None => Ok(expr),
+        Some(t) => Err(format!("unexpected {t:?} in expression")),
+    }
+}
+
+/// Evaluate `expr` with the given named `bags`.
+fn eval_expr<'a>(expr: &Expr, bags: &BTreeMap<String, Bbow<'a>>) -> Result<Bbow<'a>, String> {
+    match expr {
+        Expr::Bag(name) => bags
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("no bag named {name:?}")),
+        Expr::Op(op, left, right) => {
+            let mut left = eval_expr(left, bags)?;
+            let right = eval_expr(right, bags)?;
+            Ok(match op {
+                '+' => {
+                    left.merge(&right);
+                    left
+                }
+                '|' => left.union(&right),
+                '&' => left.intersection(&right),
+                '-' => left.difference(&right),
+                _ => unreachable!("unknown operator {op}"),
+            })
+        }
+    }
+}
+
+/// Run the `eval` subcommand.
+fn run_eval(args: &EvalArgs) -> Result<(), String> {
+    let expr = parse_expr(&args.expr)?;
+    let mut texts = Vec::new();
+    for (name, path) in &args.bags {
+        let text = read_text(path).map_err(|e| format!("{path}: {e}"))?;
+        texts.push((name, path, text));
+    }
+    let mut bags = BTreeMap::new();
+    for (name, path, text) in &texts {
+        let bbow = if path.ends_with(".csv") {
+            Bbow::from_csv(text.as_bytes()).map_err(|e| format!("{path}: {e}"))?
+        } else {
+            Bbow::new().extend_from_text(text)
+        };
+        bags.insert(name.to_string(), bbow);
+    }
+    let result = eval_expr(&expr, &bags)?;
+    let rows: Vec<(&str, usize)> = result.iter().collect();
+    let out_format = |path: &str| match path.rsplit_once('.') {
+        Some((_, "json")) => Format::Json,
+        Some((_, "csv")) => Format::Csv,
+        Some((_, "tsv")) => Format::Tsv,
+        _ => Format::Table,
+    };
+    match &args.out {
+        Some(path) => {
+            let format = args.format.unwrap_or_else(|| out_format(path));
+            std::fs::File::create(path)
+                .and_then(|file| write_table(io::BufWriter::new(file), "word", &rows, format))
+                .map_err(|e| format!("{path}: {e}"))
+        }
+        None => {
+            let format = args.format.unwrap_or(Format::Table);
+            write_table(io::stdout().lock(), "word", &rows, format).map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Default directory of golden files for `verify`.
+const GOLDEN_DIR: &str = "tests/golden";
+
+fn parse_verify_args<I: IntoIterator<Item = String>>(args: I) -> Result<(String, bool), ArgError> {
+    let mut dir = None;
+    let mut bless = false;
+    for arg in args {
+        match arg.as_str() {
+            "--bless" => bless = true,
+            "-h" | "--help" => return Err(ArgError::Help),
+            s if s.starts_with("--") => return Err(format!("unknown flag {s:?}").into()),
+            _ if dir.is_none() => dir = Some(arg),
+            _ => return Err(USAGE.to_string().into()),
+        }
+    }
+    Ok((dir.unwrap_or_else(|| GOLDEN_DIR.to_string()), bless))
+}
+
+/// The lines of `expected` and `actual` that differ, marked
+/// with `-` and `+` respectively. Both are sorted CSV, so a
+/// line-set difference is a readable diff.
+fn golden_diff(expected: &str, actual: &str) -> Vec<String> {
+    let expected: std::collections::BTreeSet<&str> = expected.lines().collect();
+    let actual: std::collections::BTreeSet<&str> = actual.lines().collect();
+    let removed = expected.difference(&actual).map(|l| format!("-{l}"));
+    let added = actual.difference(&expected).map(|l| format!("+{l}"));
+    removed.chain(added).collect()
+}
+
+/// Run the `verify` subcommand: for each `NAME.txt` in
+/// `dir`, check that the CSV of its bag matches the golden
+/// file `NAME.csv`, reporting any differences. With `bless`,
+/// rewrite the golden files to match instead. Returns
+/// whether every golden file matched.
+fn run_verify(dir: &str, bless: bool) -> Result<bool, String> {
+    let mut inputs: Vec<_> = std::fs::read_dir(dir)
+        .and_then(|entries| {
+            entries
+                .map(|e| Ok(e?.path()))
+                .collect::<io::Result<Vec<_>>>()
+        })
+        .map_err(|e| format!("{dir}: {e}"))?;
+    inputs.retain(|path| path.extension().is_some_and(|ext| ext == "txt"));
+    inputs.sort();
+    if inputs.is_empty() {
+        return Err(format!("{dir}: no .txt inputs"));
+    }
+    let mut ok = true;
+    for input in inputs {
+        let golden = input.with_extension("csv");
+        let name = input.file_stem().unwrap_or_default().to_string_lossy();
+        let text = std::fs::read_to_string(&input).map_err(|e| format!("{input:?}: {e}"))?;
+        let mut actual = Vec::new();
+        Bbow::new()
+            .extend_from_text(&text)
+            .to_csv(&mut actual)
+            .map_err(|e| e.to_string())?;
+        let actual = String::from_utf8(actual).expect("CSV of a bag is UTF-8");
+        if bless {
+            std::fs::write(&golden, &actual).map_err(|e| format!("{golden:?}: {e}"))?;
+            println!("blessed {name}");
+            continue;
+        }
+        match std::fs::read_to_string(&golden) {
+            Ok(expected) if expected == actual => println!("ok {name}"),
+            Ok(expected) => {
+                ok = false;
+                println!("FAIL {name}");
+                for line in golden_diff(&expected, &actual) {
+                    println!("    {line}");
+                }
+            }
+            Err(e) => {
+                ok = false;
+                println!("FAIL {name}: {golden:?}: {e}");
+            }
+        }
+    }
+    Ok(ok)
+}
+
+fn main() {
+    let mut argv = std::env::args().skip(1).peekable();
+    if argv.peek().map(String::as_str) == Some("verify") {
+        let (dir, bless) = or_exit(parse_verify_args(argv.skip(1)));
+        match run_verify(&dir, bless) {
+            Ok(true) => return,
+            Ok(false) => exit(1),
+            Err(msg) => {
+                eprintln!("bbow: {msg}");
+                exit(1);
+            }
+        }
+    }
+    if argv.peek().map(String::as_str) == Some("eval") {
+        let args = or_exit(parse_eval_args(argv.skip(1)));
+        if let Err(msg) = run_eval(&args) {
+            eprintln!("bbow: {msg}");
+            exit(1);
+        }
+        return;
+    }
+    let args = or_exit(parse_args(argv));
+    let texts = read_texts(&args.paths).unwrap_or_else(|e| {
+        eprintln!("bbow: {e}");
+        exit(1);
+    });
+    let mut corpus = Corpus::new();
+    for (path, text) in &texts {
+        corpus.add_text(path.as_str(), text);
+    }
+    let bbow = corpus.total();
+    let out = io::stdout().lock();
+    let written = match &args.keyword {
+        Some(keyword) => {
+            let rows = breakdown(&corpus, keyword);
+            let total = rows.iter().map(|&(_, count)| count).sum();
+            write_breakdown(out, &select_files(rows, &args), total, args.format)
+        }
+        None => write_table(out, "word", &table(&bbow, &args), args.format),
+    };
+    if let Err(e) = written {
+        eprintln!("bbow: {e}");
+        exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bbow::Apostrophes;
+
+    fn args(args: &[&str]) -> Result<Args, ArgError> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Ok(Args {
+                sort: None,
+                top: None,
+                format: Format::Table,
+                keyword: None,
+                paths: vec![]
+            }),
+            args(&[]),
+        );
+        assert_eq!(
+            Ok(Args {
+                sort: Some(Sort::Count),
+                top: Some(20),
+                format: Format::Csv,
+                keyword: Some("foo".to_string()),
+                paths: vec!["a.txt".to_string(), "-".to_string()]
+            }),
+            args(&[
+                "a.txt",
+                "--sort",
+                "count",
+                "--top",
+                "20",
+                "--format",
+                "csv",
+                "--keyword",
+                "Foo",
+                "-"
+            ]),
+        );
+        assert!(args(&["--sort"]).is_err());
+        assert!(args(&["--sort", "size"]).is_err());
+        assert!(args(&["--top", "many"]).is_err());
+        assert!(args(&["--format", "xml"]).is_err());
+        assert!(args(&["--keyword"]).is_err());
+        assert!(args(&["--bogus"]).is_err());
+    }
+
+    #[test]
+    fn test_table() {
+        let bbow = Bbow::new().extend_from_text("b a b c b a");
+        let alpha = args(&["--sort", "alpha"]).unwrap();
+        assert_eq!(vec![("a", 2), ("b", 3), ("c", 1)], table(&bbow, &alpha));
+        let count = args(&["--sort", "count"]).unwrap();
+        assert_eq!(vec![("b", 3), ("a", 2), ("c", 1)], table(&bbow, &count));
+        let csv = "word,count\n2024,3\nain't,2\n";
+        let builder = Bbow::builder().numbers(true).apostrophes(Apostrophes::Keep);
+        let bbow = Bbow::from_csv_with(csv.as_bytes(), &builder).unwrap();
+        assert_eq!(vec![("2024", 3), ("ain't", 2)], table(&bbow, &alpha));
+    }
+
+    #[test]
+    fn test_table_top() {
+        let bbow = Bbow::new().extend_from_text("b a b c b a");
+        let top = args(&["--top", "2"]).unwrap();
+        assert_eq!(vec![("b", 3), ("a", 2)], table(&bbow, &top));
+        let top_alpha = args(&["--top", "2", "--sort", "alpha"]).unwrap();
+        assert_eq!(vec![("a", 2), ("b", 3)], table(&bbow, &top_alpha));
+    }
+
+    #[test]
+    fn test_write_table() {
+        let mut out = Vec::new();
+        write_table(
+            &mut out,
+            "word",
+            &[("apple", 2), ("fig", 10)],
+            Format::Table,
+        )
+        .unwrap();
+        assert_eq!("apple  2\nfig    10\n", String::from_utf8(out).unwrap());
+    }
+
+    fn formatted(rows: &[(&str, usize)], format: Format) -> String {
+        let mut out = Vec::new();
+        write_table(&mut out, "word", rows, format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_write_table_formats() {
+        let rows = [("fig", 10), ("a\"b", 2)];
+        assert_eq!(
+            "{\n  \"fig\": 10,\n  \"a\\\"b\": 2\n}\n",
+            formatted(&rows, Format::Json)
+        );
+        assert_eq!("{}\n", formatted(&[], Format::Json));
+        assert_eq!(
+            "word,count\nfig,10\n\"a\"\"b\",2\n",
+            formatted(&rows, Format::Csv)
+        );
+        assert_eq!(
+            "word\tcount\nfig\t10\na\"b\t2\n",
+            formatted(&rows, Format::Tsv)
+        );
+        // Keys such as file names may hold tabs and line
+        // breaks.
+        assert_eq!(
+            "word\tcount\ntab\\there\t1\nnew\\nline\t2\n",
+            formatted(&[("tab\there", 1), ("new\nline", 2)], Format::Tsv)
+        );
+    }
+
+    #[test]
+    fn test_tsv_field() {
+        assert!(matches!(tsv_field("a \"b\""), Cow::Borrowed("a \"b\"")));
+        assert_eq!(r"dir\tname\n", tsv_field("dir\tname\n"));
+        assert_eq!(r"c:\\x\r", tsv_field("c:\\x\r"));
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.txt", "book.txt"));
+        assert!(glob_match("ch?.txt", "ch1.txt"));
+        assert!(glob_match("*a*b*", "xaybzb"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("*.txt", "book.md"));
+        assert!(!glob_match("ch?.txt", "ch10.txt"));
+    }
+
+    #[test]
+    fn test_expand_glob_no_wildcards() {
+        assert_eq!(vec!["a/b.txt".to_string()], expand_glob("a/b.txt").unwrap());
+        assert!(expand_glob("*/b.txt").is_err());
+    }
+
+    #[test]
+    fn test_breakdown() {
+        let mut corpus = Corpus::new();
+        corpus.add_text("b.txt", "foo bar foo");
+        corpus.add_text("c.txt", "bar");
+        corpus.add_text("a.txt", "foo");
+        let rows = breakdown(&corpus, "foo");
+        assert_eq!(vec![("b.txt", 2), ("c.txt", 0), ("a.txt", 1)], rows);
+        let select = |argv: &[&str]| select_files(rows.clone(), &args(argv).unwrap());
+        assert_eq!(rows, select(&[]));
+        assert_eq!(vec![("b.txt", 2), ("a.txt", 1)], select(&["--top", "2"]));
+        assert_eq!(
+            vec![("a.txt", 1), ("b.txt", 2)],
+            select(&["--top", "2", "--sort", "alpha"])
+        );
+        assert_eq!(
+            vec![("b.txt", 2), ("a.txt", 1), ("c.txt", 0)],
+            select(&["--sort", "count"])
+        );
+    }
+
+    #[test]
+    fn test_write_breakdown() {
+        let rows = [("total", 1), ("a.txt", 2), ("a.txt", 2)];
+        let written = |format| {
+            let mut out = Vec::new();
+            write_breakdown(&mut out, &rows, 5, format).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            "{\n  \"files\": [\n    {\"file\": \"total\", \"count\": 1},\n    \
+             {\"file\": \"a.txt\", \"count\": 2},\n    \
+             {\"file\": \"a.txt\", \"count\": 2}\n  ],\n  \"total\": 5\n}\n",
+            written(Format::Json)
+        );
+        assert_eq!(
+            "file,count\ntotal,1\na.txt,2\na.txt,2\ntotal,5\n",
+            written(Format::Csv)
+        );
+        let mut out = Vec::new();
+        write_breakdown(&mut out, &[], 0, Format::Json).unwrap();
+        assert_eq!(
+            "{\n  \"files\": [],\n  \"total\": 0\n}\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_eval_args() {
+        let args = parse_eval_args(
+            ["A - B", "A=a.txt", "B=b.csv", "--out", "m.json"]
+                .iter()
+                .map(|s| s.to_string()),
+        );
+        assert_eq!(
+            Ok(EvalArgs {
+                expr: "A - B".to_string(),
+                bags: vec![
+                    ("A".to_string(), "a.txt".to_string()),
+                    ("B".to_string(), "b.csv".to_string())
+                ],
+                out: Some("m.json".to_string()),
+                format: None,
+            }),
+            args
+        );
+        assert!(parse_eval_args(["A", "a.txt"].iter().map(|s| s.to_string())).is_err());
+        assert!(parse_eval_args(std::iter::empty()).is_err());
+    }
+
+    fn bag(name: &str) -> Box<Expr> {
+        Box::new(Expr::Bag(name.to_string()))
+    }
+
+    #[test]
+    fn test_parse_expr() {
+        assert_eq!(
+            Ok(Expr::Op(
+                '-',
+                Box::new(Expr::Op('+', bag("A"), bag("B"))),
+                bag("stop")
+            )),
+            parse_expr("(A + B) - stop"),
+        );
+        assert_eq!(
+            Ok(Expr::Op(
+                '|',
+                bag("A"),
+                Box::new(Expr::Op('&', bag("B"), bag("C")))
+            )),
+            parse_expr("A | B & C"),
+        );
+        assert_eq!(
+            Ok(Expr::Op(
+                '-',
+                Box::new(Expr::Op('-', bag("A"), bag("B"))),
+                bag("C")
+            )),
+            parse_expr("A-B-C"),
+        );
+        for bad in ["", "A +", "(A", "A B", "A * B", ")"] {
+            assert!(parse_expr(bad).is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn test_eval_expr() {
+        let mut bags = BTreeMap::new();
+        bags.insert("A".to_string(), Bbow::new().extend_from_text("the cat"));
+        bags.insert("B".to_string(), Bbow::new().extend_from_text("the dog"));
+        bags.insert("stop".to_string(), Bbow::new().extend_from_text("the"));
+        let result = eval_expr(&parse_expr("(A + B) - stop").unwrap(), &bags).unwrap();
+        assert_eq!(vec!["cat", "dog"], result.words().collect::<Vec<_>>());
+        let result = eval_expr(&parse_expr("A + B & A").unwrap(), &bags).unwrap();
+        assert_eq!(3, result.count());
+        assert!(eval_expr(&parse_expr("A + C").unwrap(), &bags).is_err());
+    }
+
+    #[test]
+    fn test_parse_verify_args() {
+        let args = |a: &[&str]| parse_verify_args(a.iter().map(|s| s.to_string()));
+        assert_eq!(Ok((GOLDEN_DIR.to_string(), false)), args(&[]));
+        assert_eq!(Ok(("g".to_string(), true)), args(&["--bless", "g"]));
+        assert!(args(&["a", "b"]).is_err());
+        assert_eq!(Err(ArgError::Help), args(&["g", "-h"]));
+    }
+
+    #[test]
+    fn test_help() {
+        assert_eq!(Err(ArgError::Help), args(&["--help"]));
+        assert_eq!(Err(ArgError::Help), args(&["a.txt", "-h"]));
+        assert_eq!(
+            Err(ArgError::Bad("unknown flag \"--hlep\"".to_string())),
+            args(&["--hlep"])
+        );
+        let eval = |a: &[&str]| parse_eval_args(a.iter().map(|s| s.to_string()));
+        assert_eq!(Err(ArgError::Help), eval(&["--help"]));
+        assert_eq!(Err(ArgError::Bad(USAGE.to_string())), eval(&[]));
+    }
+
+    #[test]
+    fn test_golden_diff() {
+        assert!(golden_diff("a,1\nb,2\n", "a,1\nb,2\n").is_empty());
+        assert_eq!(
+            vec!["-b,2".to_string(), "+b,3".to_string(), "+c,1".to_string()],
+            golden_diff("a,1\nb,2\n", "a,1\nb,3\nc,1\n"),
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Estimates of the memory used by a BBOW.
+
+use std::mem::size_of;
+
+use crate::intern::Word;
+use crate::Bbow;
+
+/// An estimate of the memory used by the words and counts of
+/// a BBOW, from [`Bbow::approx_memory_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+    borrowed_key_bytes: usize,
+    owned_key_bytes: usize,
+    map_overhead_bytes: usize,
+    count_bytes: usize,
+}
+
+impl MemoryUsage {
+    /// Bytes of words borrowed from the texts added to the
+    /// BBOW. These cost nothing beyond keeping the texts
+    /// alive, and are not counted in
+    /// [`MemoryUsage::total_bytes`].
+    pub fn borrowed_key_bytes(&self) -> usize {
+        self.borrowed_key_bytes
+    }
+
+    /// Bytes allocated for words the BBOW had to copy, such
+    /// as lowercased words.
+    pub fn owned_key_bytes(&self) -> usize {
+        self.owned_key_bytes
+    }
+
+    /// Estimated bytes used by the map itself: the key
+    /// headers, and the nodes of the tree that hold them.
+    pub fn map_overhead_bytes(&self) -> usize {
+        self.map_overhead_bytes
+    }
+
+    /// Bytes used by the counts.
+    pub fn count_bytes(&self) -> usize {
+        self.count_bytes
+    }
+
+    /// Estimated bytes used by the BBOW: everything but the
+    /// borrowed words.
+    pub fn total_bytes(&self) -> usize {
+        self.owned_key_bytes + self.map_overhead_bytes + self.count_bytes
+    }
+}
+
+/// Entries in a full node of the standard library B-tree.
+const NODE_CAPACITY: usize = 11;
+
+impl Bbow<'_> {
+    /// An estimate of the memory used by the words and counts
+    /// of this BBOW, telling borrowed words from copied ones
+    /// so that the gain from borrowing can be seen. The
+    /// estimate of the tree overhead assumes nodes half full
+    /// on average. Variants and positions are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("Hello hello world");
+    /// let usage = bbow.approx_memory_bytes();
+    /// assert_eq!(5, usage.borrowed_key_bytes());
+    /// assert_eq!(5, usage.owned_key_bytes());
+    /// assert_eq!(2 * std::mem::size_of::<usize>(), usage.count_bytes());
+    /// ```
+    pub fn approx_memory_bytes(&self) -> MemoryUsage {
+        let mut borrowed_key_bytes = 0;
+        let mut owned_key_bytes = 0;
+        for word in self.counts.keys() {
+            match word {
+                Word::Borrowed(word) => borrowed_key_bytes += word.len(),
+                Word::Shared(word) => owned_key_bytes += word.len(),
+            }
+        }
+        let n = self.len();
+        let key_headers = n * size_of::<Word>();
+        // Count the empty entry slots of half-full nodes, and
+        // a parent pointer and two 16-bit indices per node.
+        let nodes = n.div_ceil(NODE_CAPACITY / 2);
+        let slot = size_of::<Word>() + size_of::<usize>();
+        let empty_slots = nodes * NODE_CAPACITY - n;
+        let node_bytes = nodes * (size_of::<usize>() + 4) + empty_slots * slot;
+        MemoryUsage {
+            borrowed_key_bytes,
+            owned_key_bytes,
+            map_overhead_bytes: key_headers + node_bytes,
+            count_bytes: n * size_of::<usize>(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_approx_memory_bytes() {
+        let empty = Bbow::new().approx_memory_bytes();
+        assert_eq!(0, empty.total_bytes());
+        assert_eq!(0, empty.borrowed_key_bytes());
+        let borrowed = Bbow::new()
+            .extend_from_text("alpha beta")
+            .approx_memory_bytes();
+        let owned = Bbow::new()
+            .extend_from_text("ALPHA BETA")
+            .approx_memory_bytes();
+        assert_eq!(9, borrowed.borrowed_key_bytes());
+        assert_eq!(0, borrowed.owned_key_bytes());
+        assert_eq!(9, owned.owned_key_bytes());
+        assert_eq!(9, owned.total_bytes() - borrowed.total_bytes());
+    }
+}
@@ -0,0 +1,299 @@
+//! A BBOW kept in an on-disk sled database, for counts that
+//! must outlive the process or that are too big for memory.
+//!
+//! Each word is a key in a sled tree, and its count is the
+//! value as a big-endian `u64`. Counts are added with a sled
+//! merge operator, so several handles on the same tree can
+//! add words at once without losing counts. The count of all
+//! the words is kept the same way under the empty key, which
+//! is never a word, so it need not be summed up again.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::Bound;
+use std::path::Path;
+
+use crate::Bbow;
+
+/// The key of the overall number of words.
+const TOTAL: &[u8] = b"";
+
+/// A BBOW whose words and counts live in a sled tree rather
+/// than in memory. Changes are written through to the tree
+/// as they are made; [`PersistentBbow::flush`] waits for
+/// them to reach the disk.
+#[derive(Debug, Clone)]
+pub struct PersistentBbow {
+    tree: sled::Tree,
+}
+
+fn decode(count: &[u8]) -> usize {
+    let mut bytes = [0; 8];
+    let n = count.len().min(8);
+    bytes[8 - n..].copy_from_slice(&count[count.len() - n..]);
+    u64::from_be_bytes(bytes) as usize
+}
+
+fn add_counts(_word: &[u8], old: Option<&[u8]>, added: &[u8]) -> Option<Vec<u8>> {
+    let old = old.map(decode).unwrap_or(0);
+    let new = old.saturating_add(decode(added)) as u64;
+    Some(new.to_be_bytes().to_vec())
+}
+
+impl PersistentBbow {
+    /// Open the BBOW stored in the sled database at `path`,
+    /// creating an empty one if there is none.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bbow::PersistentBbow;
+    /// let mut bbow = PersistentBbow::open("counts.db").unwrap();
+    /// bbow.extend_from_text("another day of logs").unwrap();
+    /// bbow.flush().unwrap();
+    /// ```
+    pub fn open<P: AsRef<Path>>(path: P) -> sled::Result<Self> {
+        let db = sled::open(path)?;
+        Ok(Self::from_tree(db.open_tree("bbow")?))
+    }
+
+    /// Use `tree` of some already open sled database to
+    /// store a BBOW. The tree should hold nothing but words
+    /// and counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::PersistentBbow;
+    /// let db = sled::Config::new().temporary(true).open().unwrap();
+    /// let mut bbow = PersistentBbow::from_tree(db.open_tree("words").unwrap());
+    /// bbow.extend_from_text("one fish two fish").unwrap();
+    /// assert_eq!(2, bbow.match_count("fish").unwrap());
+    /// ```
+    pub fn from_tree(tree: sled::Tree) -> Self {
+        tree.set_merge_operator(add_counts);
+        Self { tree }
+    }
+
+    fn add(&mut self, word: &str, count: usize) -> sled::Result<()> {
+        let count = (count as u64).to_be_bytes();
+        self.tree.merge(word, count)?;
+        self.tree.merge(TOTAL, count)?;
+        Ok(())
+    }
+
+    /// The words and counts in the tree, in alphabetical
+    /// order, without the overall count.
+    fn entries(&self) -> sled::Iter {
+        self.tree
+            .range::<&[u8], _>((Bound::Excluded(TOTAL), Bound::Unbounded))
+    }
+
+    /// Parse the `target` text and add the sequence of
+    /// valid words contained in it to this BBOW, as
+    /// [`Bbow::extend_from_text`] does.
+    pub fn extend_from_text(&mut self, target: &str) -> sled::Result<()> {
+        self.merge(&Bbow::new().extend_from_text(target))
+    }
+
+    /// Add the words of `other`, with their counts, to this
+    /// BBOW.
+    pub fn merge(&mut self, other: &Bbow) -> sled::Result<()> {
+        for (word, &count) in &other.counts {
+            self.add(word, count)?;
+        }
+        Ok(())
+    }
+
+    /// Report the number of occurrences of the given
+    /// `keyword` in this BBOW. The keyword is looked up as
+    /// given, so the words of a BBOW built with other options
+    /// are found after [`PersistentBbow::merge`].
+    pub fn match_count(&self, keyword: &str) -> sled::Result<usize> {
+        if keyword.is_empty() {
+            return Ok(0);
+        }
+        Ok(self.tree.get(keyword)?.map_or(0, |count| decode(&count)))
+    }
+
+    /// The words in this BBOW with their counts, in
+    /// alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = sled::Result<(String, usize)>> {
+        self.entries().map(|entry| {
+            let (word, count) = entry?;
+            Ok((String::from_utf8_lossy(&word).into_owned(), decode(&count)))
+        })
+    }
+
+    /// The words in this BBOW, in alphabetical order.
+    pub fn words(&self) -> impl Iterator<Item = sled::Result<String>> {
+        self.iter().map(|entry| entry.map(|(word, _)| word))
+    }
+
+    /// The `n` most frequent words in this BBOW with their
+    /// counts, as [`Bbow::top_n`] gives them. The words are
+    /// read one at a time, and only the best `n` so far are
+    /// kept in memory.
+    pub fn top_n(&self, n: usize) -> sled::Result<Vec<(String, usize)>> {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        let mut best = BinaryHeap::with_capacity(n + 1);
+        for entry in self.entries() {
+            let (word, count) = entry?;
+            best.push(Reverse((decode(&count), Reverse(word))));
+            if best.len() > n {
+                best.pop();
+            }
+        }
+        Ok(best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((count, Reverse(word)))| {
+                (String::from_utf8_lossy(&word).into_owned(), count)
+            })
+            .collect())
+    }
+
+    /// Count the overall number of words contained in this
+    /// BBOW: multiple occurrences are considered separate.
+    pub fn count(&self) -> sled::Result<usize> {
+        Ok(self.tree.get(TOTAL)?.map_or(0, |count| decode(&count)))
+    }
+
+    /// Count the number of unique words contained in this
+    /// BBOW.
+    pub fn len(&self) -> sled::Result<usize> {
+        self.entries().try_fold(0, |n, entry| entry.map(|_| n + 1))
+    }
+
+    /// Is this BBOW empty?
+    pub fn is_empty(&self) -> sled::Result<bool> {
+        Ok(self.entries().next().transpose()?.is_none())
+    }
+
+    /// Load this BBOW into memory.
+    pub fn to_bbow(&self) -> sled::Result<Bbow<'static>> {
+        let mut bbow = Bbow::new();
+        for entry in self.iter() {
+            let (word, count) = entry?;
+            bbow.insert(word, count);
+        }
+        Ok(bbow)
+    }
+
+    /// Wait until all changes to this BBOW are on disk.
+    pub fn flush(&self) -> sled::Result<()> {
+        self.tree.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temporary() -> PersistentBbow {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        PersistentBbow::from_tree(db.open_tree("test").unwrap())
+    }
+
+    #[test]
+    fn test_persistent_matches_bbow() {
+        let text = "It ain't over untïl it ain't, over. Over!";
+        let mut persistent = temporary();
+        persistent.extend_from_text(text).unwrap();
+        let bbow = Bbow::new().extend_from_text(text);
+        assert_eq!(bbow.counts, persistent.to_bbow().unwrap().counts);
+        assert_eq!(bbow.len(), persistent.len().unwrap());
+        assert_eq!(bbow.count(), persistent.count().unwrap());
+        assert_eq!(3, persistent.match_count("over").unwrap());
+        assert_eq!(0, persistent.match_count("Over").unwrap());
+        let top = persistent.top_n(2).unwrap();
+        let top: Vec<(&str, usize)> = top.iter().map(|(w, c)| (w.as_str(), *c)).collect();
+        assert_eq!(bbow.top_n(2), top);
+    }
+
+    /// A directory that is removed when this is dropped,
+    /// even if a test fails.
+    struct TempDir(std::path::PathBuf);
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// Open the database at `dir`, waiting a little for a
+    /// handle that was just dropped to let go of its lock.
+    fn open_db(dir: &Path) -> sled::Db {
+        for _ in 0..50 {
+            match sled::open(dir) {
+                Ok(db) => return db,
+                Err(sled::Error::Io(_)) => std::thread::sleep(std::time::Duration::from_millis(20)),
+                Err(e) => panic!("{e}"),
+            }
+        }
+        sled::open(dir).unwrap()
+    }
+
+    #[test]
+    fn test_persistent_reopen() {
+        let dir =
+            TempDir(std::env::temp_dir().join(format!("bbow-persistent-{}", std::process::id())));
+        {
+            let db = open_db(&dir.0);
+            let mut bbow = PersistentBbow::from_tree(db.open_tree("bbow").unwrap());
+            bbow.extend_from_text("red fish").unwrap();
+            bbow.flush().unwrap();
+            drop(bbow);
+            drop(db);
+        }
+        {
+            let db = open_db(&dir.0);
+            let mut bbow = PersistentBbow::from_tree(db.open_tree("bbow").unwrap());
+            bbow.extend_from_text("blue fish").unwrap();
+            assert_eq!(2, bbow.match_count("fish").unwrap());
+            assert_eq!(
+                vec!["blue", "fish", "red"],
+                bbow.words().collect::<sled::Result<Vec<_>>>().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_persistent_totals() {
+        let mut persistent = temporary();
+        assert!(persistent.is_empty().unwrap());
+        assert_eq!(0, persistent.count().unwrap());
+        persistent
+            .merge(
+                &Bbow::builder()
+                    .numbers(true)
+                    .build()
+                    .extend_from_text("2024 2024 b a"),
+            )
+            .unwrap();
+        assert_eq!(2, persistent.match_count("2024").unwrap());
+        assert_eq!(0, persistent.match_count("").unwrap());
+        assert_eq!(3, persistent.len().unwrap());
+        assert_eq!(4, persistent.count().unwrap());
+        assert!(!persistent.is_empty().unwrap());
+        assert_eq!(
+            vec![("2024".to_string(), 2), ("a".to_string(), 1)],
+            persistent.top_n(2).unwrap()
+        );
+        assert_eq!(3, persistent.top_n(10).unwrap().len());
+        assert!(persistent.top_n(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(0, decode(&[]));
+        assert_eq!(258, decode(&258u64.to_be_bytes()));
+        assert_eq!(
+            Some(7u64.to_be_bytes().to_vec()),
+            add_counts(b"w", Some(&3u64.to_be_bytes()), &4u64.to_be_bytes())
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Polars DataFrame export of BBOWs and corpora, for
+//! exploratory analysis.
+
+use polars::prelude::{Column, DataFrame, PolarsResult};
+
+use crate::{Bbow, Corpus};
+
+impl Bbow<'_> {
+    /// This BBOW as a Polars DataFrame with a `word` column
+    /// and a `count` column, one row per word in
+    /// alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let df = Bbow::new().extend_from_text("b a b").to_polars().unwrap();
+    /// assert_eq!((2, 2), df.shape());
+    /// ```
+    pub fn to_polars(&self) -> PolarsResult<DataFrame> {
+        let words: Vec<&str> = self.counts.keys().map(|w| w.as_ref()).collect();
+        let counts: Vec<u64> = self.counts.values().map(|&c| c as u64).collect();
+        DataFrame::new(
+            words.len(),
+            vec![
+                Column::new("word".into(), words),
+                Column::new("count".into(), counts),
+            ],
+        )
+    }
+}
+
+impl Corpus<'_> {
+    /// The document-term data of this corpus as a Polars
+    /// DataFrame with `doc_id`, `word` and `count` columns:
+    /// one row for each word of each document, in document
+    /// order and then alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Corpus;
+    /// let mut corpus = Corpus::new();
+    /// corpus.add_text("a", "red fish");
+    /// corpus.add_text("b", "blue fish fish");
+    /// assert_eq!((4, 3), corpus.to_polars().unwrap().shape());
+    /// ```
+    pub fn to_polars(&self) -> PolarsResult<DataFrame> {
+        let mut ids = Vec::new();
+        let mut words = Vec::new();
+        let mut counts = Vec::new();
+        for (id, doc) in self.iter().enumerate() {
+            for (word, &count) in &doc.bbow().counts {
+                ids.push(id as u64);
+                words.push(word.as_ref());
+                counts.push(count as u64);
+            }
+        }
+        DataFrame::new(
+            ids.len(),
+            vec![
+                Column::new("doc_id".into(), ids),
+                Column::new("word".into(), words),
+                Column::new("count".into(), counts),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bbow_to_polars() {
+        let df = Bbow::new()
+            .extend_from_text("the cat the hat")
+            .to_polars()
+            .unwrap();
+        let words: Vec<&str> = df
+            .column("word")
+            .unwrap()
+            .str()
+            .unwrap()
+            .iter()
+            .flatten()
+            .collect();
+        let counts: Vec<u64> = df
+            .column("count")
+            .unwrap()
+            .u64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(vec!["cat", "hat", "the"], words);
+        assert_eq!(vec![1, 1, 2], counts);
+    }
+
+    #[test]
+    fn test_corpus_to_polars() {
+        let mut corpus = Corpus::new();
+        corpus.add_text("a", "x y");
+        corpus.add_text("empty", "");
+        corpus.add_text("c", "y y");
+        let df = corpus.to_polars().unwrap();
+        let ids: Vec<u64> = df
+            .column("doc_id")
+            .unwrap()
+            .u64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(vec![0, 0, 2], ids);
+        assert_eq!((0, 3), Corpus::new().to_polars().unwrap().shape());
+    }
+}
@@ -0,0 +1,77 @@
+//! Words grouped by how they begin, for exploring families
+//! of words such as "connect", "connection" and "connected".
+
+use std::collections::BTreeMap;
+
+use crate::Bbow;
+
+/// The first `len` characters of `word`, or all of it if it
+/// is shorter.
+fn prefix(word: &str, len: usize) -> &str {
+    match word.char_indices().nth(len) {
+        Some((end, _)) => &word[..end],
+        None => word,
+    }
+}
+
+impl Bbow<'_> {
+    /// The total count of the words of this BBOW beginning
+    /// with each prefix of `len` characters. A word shorter
+    /// than `len` characters is its own prefix. A `len` of 0
+    /// is taken as 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// # use std::collections::BTreeMap;
+    /// let bbow = Bbow::new().extend_from_text("connect connected connection cons con cat");
+    /// assert_eq!(
+    ///     BTreeMap::from([("cat", 1), ("con", 1), ("conn", 3), ("cons", 1)]),
+    ///     bbow.group_by_prefix(4),
+    /// );
+    /// ```
+    pub fn group_by_prefix(&self, len: usize) -> BTreeMap<&str, usize> {
+        let len = len.max(1);
+        let mut groups = BTreeMap::new();
+        // The words of a group are next to each other in
+        // order, so each group is looked up only once.
+        let mut current: Option<(&str, usize)> = None;
+        for (word, count) in self.iter() {
+            let start = prefix(word, len);
+            match &mut current {
+                Some((p, total)) if *p == start => *total += count,
+                _ => {
+                    if let Some((p, total)) = current.replace((start, count)) {
+                        groups.insert(p, total);
+                    }
+                }
+            }
+        }
+        if let Some((p, total)) = current {
+            groups.insert(p, total);
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_by_prefix() {
+        let bbow = Bbow::new().extend_from_text("Über über uber ungut ú a");
+        assert_eq!(
+            BTreeMap::from([("a", 1), ("ub", 1), ("un", 1), ("ú", 1), ("üb", 2)]),
+            bbow.group_by_prefix(2)
+        );
+        let singles = bbow.group_by_prefix(0);
+        assert_eq!(Some(&2), singles.get("u"));
+        assert_eq!(bbow.count(), singles.values().sum::<usize>());
+        assert_eq!(bbow.len(), bbow.group_by_prefix(100).len());
+        assert!(Bbow::new().group_by_prefix(3).is_empty());
+        assert_eq!("ab", prefix("abc", 2));
+        assert_eq!("", prefix("", 2));
+    }
+}
@@ -0,0 +1,157 @@
+//! Python bindings, so that a BBOW can be used from Python
+//! much like a `dict` from words to counts. Build a Python
+//! extension module with for example
+//! `maturin build --features pyo3,pyo3/extension-module`.
+//!
+//! ```python
+//! from bbow import Bbow
+//! bbow = Bbow("It ain't over untïl it ain't, over.")
+//! bbow["over"]        # 2
+//! "ain't" in bbow     # False
+//! dict(bbow.items())  # {'it': 2, 'over': 2, 'untïl': 1}
+//! ```
+
+use pyo3::exceptions::PyKeyError;
+use pyo3::prelude::*;
+
+use crate::Bbow;
+
+/// A BBOW for Python. Text passed in is copied, as Python
+/// strings cannot be borrowed.
+#[pyclass(name = "Bbow", module = "bbow", skip_from_py_object)]
+#[derive(Debug, Default, Clone)]
+pub struct PyBbow(Bbow<'static>);
+
+impl PyBbow {
+    /// The count of `word` exactly as given, if it is in
+    /// this BBOW.
+    fn lookup(&self, word: &str) -> Option<usize> {
+        let PyBbow(bbow) = self;
+        bbow.counts.get(word).copied()
+    }
+}
+
+#[pymethods]
+impl PyBbow {
+    /// Make a new BBOW of the words of `text`, if given.
+    #[new]
+    #[pyo3(signature = (text = None))]
+    fn new(text: Option<&str>) -> Self {
+        let mut bbow = Self::default();
+        if let Some(text) = text {
+            bbow.extend_from_text(text);
+        }
+        bbow
+    }
+
+    /// Add the words of `text` to this BBOW, as
+    /// [`Bbow::extend_from_text`] does.
+    fn extend_from_text(&mut self, text: &str) {
+        let words = Bbow::new().extend_from_text(text).into_owned();
+        self.0.merge(&words);
+    }
+
+    /// The number of occurrences of `keyword`, as
+    /// [`Bbow::match_count`] gives it.
+    fn match_count(&self, keyword: &str) -> usize {
+        self.0.match_count(keyword)
+    }
+
+    /// The overall number of words, as [`Bbow::count`]
+    /// gives it.
+    fn count(&self) -> usize {
+        self.0.count()
+    }
+
+    /// The `n` most frequent words with their counts, as
+    /// [`Bbow::top_n`] gives them.
+    fn top_n(&self, n: usize) -> Vec<(String, usize)> {
+        self.0
+            .top_n(n)
+            .into_iter()
+            .map(|(w, c)| (w.to_string(), c))
+            .collect()
+    }
+
+    /// The count of `word`, or `default` if it is not in
+    /// this BBOW, as `dict.get` gives it.
+    #[pyo3(signature = (word, default = 0))]
+    fn get(&self, word: &str, default: usize) -> usize {
+        self.lookup(word).unwrap_or(default)
+    }
+
+    /// The words, in alphabetical order.
+    fn keys(&self) -> Vec<String> {
+        self.0.words().map(str::to_string).collect()
+    }
+
+    /// The words with their counts, in alphabetical order.
+    fn items(&self) -> Vec<(String, usize)> {
+        self.0.iter().map(|(w, c)| (w.to_string(), c)).collect()
+    }
+
+    fn __getitem__(&self, word: &str) -> PyResult<usize> {
+        self.lookup(word)
+            .ok_or_else(|| PyKeyError::new_err(word.to_string()))
+    }
+
+    fn __contains__(&self, word: &str) -> bool {
+        self.lookup(word).is_some()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    fn __iter__(&self) -> WordIter {
+        WordIter(self.keys().into_iter())
+    }
+
+    fn __repr__(&self) -> String {
+        let items: Vec<String> = self.0.iter().map(|(w, c)| format!("{w:?}: {c}")).collect();
+        format!("Bbow({{{}}})", items.join(", "))
+    }
+}
+
+/// Iterator over the words of a [`PyBbow`], for Python's
+/// `for word in bbow`.
+#[pyclass]
+struct WordIter(std::vec::IntoIter<String>);
+
+#[pymethods]
+impl WordIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<String> {
+        slf.0.next()
+    }
+}
+
+/// The `bbow` Python module.
+#[pymodule]
+fn bbow(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyBbow>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_py_bbow() {
+        let mut bbow = PyBbow::new(Some("It ain't over untïl it ain't, over."));
+        bbow.extend_from_text("Over!");
+        assert_eq!(3, bbow.match_count("over"));
+        assert_eq!(3, bbow.get("over", 0));
+        assert_eq!(7, bbow.get("missing", 7));
+        assert!(bbow.__contains__("untïl"));
+        assert!(!bbow.__contains__("ain't"));
+        assert_eq!(3, bbow.__len__());
+        assert_eq!(vec!["it", "over", "untïl"], bbow.keys());
+        assert_eq!(vec![("over".to_string(), 3)], bbow.top_n(1));
+        assert_eq!(r#"Bbow({"it": 2, "over": 3, "untïl": 1})"#, bbow.__repr__());
+    }
+}
@@ -0,0 +1,150 @@
+//! Looking up many keywords in a BBOW at once, to score a
+//! document against a query.
+
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::Bbow;
+
+/// How many words [`Bbow::match_counts`] steps over before
+/// jumping to the next keyword with a fresh search instead.
+const MAX_STEPS: usize = 8;
+
+impl Bbow<'_> {
+    /// The [`Bbow::match_count`] of each of `keywords`, in
+    /// the order given. The keywords are sorted and looked up
+    /// in a single walk over the words of this BBOW between
+    /// the first and last of them, which is much faster than
+    /// looking each up in turn when there are many. Where
+    /// the next keyword is far ahead, the walk jumps to it
+    /// rather than stepping over every word in between, so
+    /// a few keywords spread over a large BBOW cost no more
+    /// than separate lookups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("one fish two fish red fish");
+    /// assert_eq!(vec![3, 0, 1], bbow.match_counts(&["fish", "blue", "red"]));
+    /// ```
+    pub fn match_counts(&self, keywords: &[&str]) -> Vec<usize> {
+        let mut counts = vec![0; keywords.len()];
+        let mut order: Vec<usize> = (0..keywords.len())
+            .filter(|&i| self.options.is_token(keywords[i]))
+            .collect();
+        order.sort_unstable_by_key(|&i| keywords[i]);
+        let Some(&first) = order.first() else {
+            return counts;
+        };
+        let from = |keyword: &str| {
+            self.counts
+                .range::<str, _>((Bound::Included(keyword), Bound::Unbounded))
+                .peekable()
+        };
+        let mut words = from(keywords[first]);
+        for i in order {
+            let mut steps = 0;
+            while let Some((word, &count)) = words.peek() {
+                match word.as_ref().cmp(keywords[i]) {
+                    Ordering::Less if steps == MAX_STEPS => {
+                        words = from(keywords[i]);
+                    }
+                    Ordering::Less => {
+                        words.next();
+                        steps += 1;
+                    }
+                    Ordering::Equal => {
+                        counts[i] = count;
+                        break;
+                    }
+                    Ordering::Greater => break,
+                }
+            }
+        }
+        counts
+    }
+
+    /// The total number of occurrences of `keywords` in this
+    /// BBOW: a simple measure of how well it matches a
+    /// query. A keyword given twice counts twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("one fish two fish red fish");
+    /// assert_eq!(4, bbow.score(&["fish", "red", "blue"]));
+    /// ```
+    pub fn score(&self, keywords: &[&str]) -> usize {
+        self.match_counts(keywords).into_iter().sum()
+    }
+
+    /// The sum of the occurrences of each keyword in this
+    /// BBOW times its weight, for queries where some words
+    /// matter more than others.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("one fish two fish red fish");
+    /// assert_eq!(2.5, bbow.weighted_score(&[("fish", 0.5), ("red", 1.0)]));
+    /// ```
+    pub fn weighted_score(&self, keywords: &[(&str, f64)]) -> f64 {
+        let words: Vec<&str> = keywords.iter().map(|&(word, _)| word).collect();
+        self.match_counts(&words)
+            .into_iter()
+            .zip(keywords)
+            .map(|(count, &(_, weight))| count as f64 * weight)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_counts() {
+        let bbow = Bbow::new().extend_from_text("It ain't over untïl it ain't, over. b c e");
+        let keywords = [
+            "untïl", "a", "over", "Over", "d", "it", "c", "over", "zzz", "",
+        ];
+        let expected: Vec<usize> = keywords.iter().map(|k| bbow.match_count(k)).collect();
+        assert_eq!(expected, bbow.match_counts(&keywords));
+        assert!(bbow.match_counts(&[]).is_empty());
+        assert_eq!(vec![0], Bbow::new().match_counts(&["a"]));
+        assert_eq!(0.0, bbow.weighted_score(&[]));
+        assert_eq!(6, bbow.score(&["over", "over", "it"]));
+    }
+
+    #[test]
+    fn test_match_counts_jumps() {
+        let mut bbow = Bbow::new();
+        // "aaa", "aab", ... "jjj": the numbers 0 to 999 with
+        // letters for digits.
+        let words: Vec<String> = (0..1000)
+            .map(|i: u32| {
+                let digits = format!("{i:03}");
+                digits
+                    .bytes()
+                    .map(|d| char::from(d - b'0' + b'a'))
+                    .collect()
+            })
+            .collect();
+        for (i, word) in words.iter().enumerate() {
+            bbow.insert(word.clone(), i + 1);
+        }
+        // Keywords close together, far apart, missing and
+        // repeated.
+        let mut keywords: Vec<&str> = words.iter().step_by(97).map(String::as_str).collect();
+        keywords.extend(words[500..520].iter().map(String::as_str));
+        keywords.extend(["ab", "jjjj", "a", "z"]);
+        keywords.push(&words[999]);
+        keywords.push(&words[0]);
+        let expected: Vec<usize> = keywords.iter().map(|k| bbow.match_count(k)).collect();
+        assert_eq!(expected, bbow.match_counts(&keywords));
+        assert_eq!(1000, bbow.match_counts(&[&words[999]])[0]);
+    }
+}
@@ -0,0 +1,172 @@
+//! Narration and dialogue: separating text inside
+//! quotation marks from the text around it.
+
+use crate::Bbow;
+
+/// Which part of a text to ingest.
+///
+/// Double quotation marks are recognized: straight `"`
+/// toggles between narration and dialogue, `“` and `«` open
+/// dialogue, and `”` and `»` close it. Single quotes are
+/// ignored, since they cannot be told apart from
+/// apostrophes. A blank line ends any open quotation, so
+/// that a missing closing mark (or the common convention of
+/// not closing a quotation that continues into the next
+/// paragraph) only affects one paragraph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quotation {
+    /// Only text outside quotation marks.
+    Narration,
+    /// Only text inside quotation marks.
+    Dialogue,
+}
+
+/// Split `text` into consecutive spans, each marked with
+/// whether it is inside quotation marks. The quotation
+/// marks themselves are not part of any span.
+fn quotation_spans(text: &str) -> Vec<(Quotation, &str)> {
+    let mut spans = Vec::new();
+    let mut state = Quotation::Narration;
+    let mut start = 0;
+    // Newlines seen since the last non-whitespace
+    // character, and the position of the first of them.
+    let mut newlines = 0;
+    let mut line_end = 0;
+    for (i, c) in text.char_indices() {
+        let next = match c {
+            '"' if state == Quotation::Narration => Quotation::Dialogue,
+            '"' => Quotation::Narration,
+            '“' | '«' => Quotation::Dialogue,
+            '”' | '»' => Quotation::Narration,
+            '\n' => {
+                if newlines == 0 {
+                    line_end = i;
+                }
+                newlines += 1;
+                if newlines >= 2 && state == Quotation::Dialogue {
+                    spans.push((state, &text[start..line_end]));
+                    start = line_end;
+                    state = Quotation::Narration;
+                }
+                continue;
+            }
+            c => {
+                if !c.is_whitespace() {
+                    newlines = 0;
+                }
+                continue;
+            }
+        };
+        newlines = 0;
+        spans.push((state, &text[start..i]));
+        start = i + c.len_utf8();
+        state = next;
+    }
+    spans.push((state, &text[start..]));
+    spans.retain(|(_, span)| !span.is_empty());
+    spans
+}
+
+impl<'a> Bbow<'a> {
+    /// Parse the `target` text and add the words in just
+    /// the `part` of it selected — narration outside
+    /// quotation marks, or dialogue inside them — to this
+    /// BBOW. See [`Quotation`] for how quotations are
+    /// found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::{Bbow, Quotation};
+    /// let text = "“Run,” she said. “Now!”";
+    /// let narration = Bbow::new()
+    ///     .extend_from_text_quotation(text, Quotation::Narration);
+    /// assert_eq!(vec!["said", "she"], narration.words().collect::<Vec<_>>());
+    /// let dialogue = Bbow::new()
+    ///     .extend_from_text_quotation(text, Quotation::Dialogue);
+    /// assert_eq!(vec!["now", "run"], dialogue.words().collect::<Vec<_>>());
+    /// ```
+    pub fn extend_from_text_quotation(mut self, target: &'a str, part: Quotation) -> Self {
+        for (quotation, span) in quotation_spans(target) {
+            if quotation == part {
+                self = self.extend_from_text(span);
+            }
+        }
+        self
+    }
+
+    /// Make a pair of BBOWs from the `target` text: the
+    /// first for its narration and the second for its
+    /// dialogue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let (narration, dialogue) =
+    ///     Bbow::narration_and_dialogue("He said, \"Hello, hello.\"");
+    /// assert_eq!(2, narration.count());
+    /// assert_eq!(2, dialogue.match_count("hello"));
+    /// ```
+    pub fn narration_and_dialogue(target: &'a str) -> (Self, Self) {
+        let mut narration = Bbow::new();
+        let mut dialogue = Bbow::new();
+        for (quotation, span) in quotation_spans(target) {
+            match quotation {
+                Quotation::Narration => narration = narration.extend_from_text(span),
+                Quotation::Dialogue => dialogue = dialogue.extend_from_text(span),
+            }
+        }
+        (narration, dialogue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Quotation::*;
+
+    #[test]
+    fn test_quotation_spans_straight() {
+        assert_eq!(
+            vec![(Narration, "a "), (Dialogue, "b"), (Narration, " c")],
+            quotation_spans("a \"b\" c"),
+        );
+    }
+
+    #[test]
+    fn test_quotation_spans_curly() {
+        assert_eq!(
+            vec![
+                (Dialogue, "Yes,"),
+                (Narration, " he said, "),
+                (Dialogue, "I’m here.")
+            ],
+            quotation_spans("“Yes,” he said, “I’m here.”"),
+        );
+    }
+
+    #[test]
+    fn test_quotation_spans_paragraph_break() {
+        let text = "\"Unclosed line\n\nNarration again \"quoted\"";
+        assert_eq!(
+            vec![
+                (Dialogue, "Unclosed line"),
+                (Narration, "\n\nNarration again "),
+                (Dialogue, "quoted"),
+            ],
+            quotation_spans(text),
+        );
+    }
+
+    #[test]
+    fn test_narration_and_dialogue_counts() {
+        let text = "Tom said \"go home\" and Tom went home.";
+        let (narration, dialogue) = Bbow::narration_and_dialogue(text);
+        assert_eq!(2, narration.match_count("tom"));
+        assert_eq!(1, narration.match_count("home"));
+        assert_eq!(0, narration.match_count("go"));
+        assert_eq!(1, dialogue.match_count("home"));
+        assert_eq!(2, dialogue.count());
+    }
+}
@@ -0,0 +1,129 @@
+//! Counting a uniform random sample of the words of a
+//! stream too big to count in full.
+
+use std::borrow::Cow;
+
+use rand::Rng;
+
+use crate::{words_of, Bbow};
+
+/// A uniform random sample of at most `capacity` of the
+/// words of all the texts added, kept by reservoir sampling
+/// (Vitter's Algorithm R): however many words go by, each
+/// is equally likely to be in the sample, and memory stays
+/// bounded. The sample's BBOW, scaled up, estimates the
+/// counts of the whole stream.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::Reservoir;
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// let text = "spam spam spam eggs ".repeat(1000);
+/// let mut rng = StdRng::seed_from_u64(7);
+/// let reservoir = Reservoir::new(400).extend_from_text(&text, &mut rng);
+/// assert_eq!(4000, reservoir.seen());
+/// assert_eq!(400, reservoir.to_bbow().count());
+/// let spam = reservoir.estimated_count("spam");
+/// assert!(2700.0 < spam && spam < 3300.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Reservoir<'a> {
+    capacity: usize,
+    sample: Vec<Cow<'a, str>>,
+    seen: u64,
+}
+
+impl<'a> Reservoir<'a> {
+    /// Make a new empty reservoir that keeps at most
+    /// `capacity` words.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            sample: Vec::with_capacity(capacity),
+            seen: 0,
+        }
+    }
+
+    /// Parse the `target` text, as [`Bbow::extend_from_text`]
+    /// does, and offer each of its words to the sample,
+    /// drawing from `rng` to choose.
+    ///
+    /// This is a "builder method", like
+    /// [`Bbow::extend_from_text`].
+    pub fn extend_from_text<R: Rng + ?Sized>(mut self, target: &'a str, rng: &mut R) -> Self {
+        for word in words_of(target) {
+            if self.sample.len() < self.capacity {
+                self.sample.push(word);
+            } else {
+                let i = rng.random_range(0..=self.seen);
+                if let Some(slot) = usize::try_from(i).ok().and_then(|i| self.sample.get_mut(i)) {
+                    *slot = word;
+                }
+            }
+            self.seen += 1;
+        }
+        self
+    }
+
+    /// The number of words offered to the sample so far.
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    /// A BBOW of the words in the sample.
+    pub fn to_bbow(&self) -> Bbow<'a> {
+        let mut bbow = Bbow::new();
+        for word in &self.sample {
+            bbow.increment(word.clone());
+        }
+        bbow
+    }
+
+    /// An estimate of the number of occurrences of
+    /// `keyword` in everything offered: its count in the
+    /// sample, scaled up by the fraction of words sampled.
+    pub fn estimated_count(&self, keyword: &str) -> f64 {
+        if self.sample.is_empty() {
+            return 0.0;
+        }
+        let count = self.sample.iter().filter(|word| *word == keyword).count();
+        count as f64 * self.seen as f64 / self.sample.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_reservoir_small_stream() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let reservoir = Reservoir::new(10).extend_from_text("a b b", &mut rng);
+        assert_eq!(3, reservoir.seen());
+        assert_eq!(2, reservoir.to_bbow().match_count("b"));
+        assert_eq!(2.0, reservoir.estimated_count("b"));
+        let empty = Reservoir::new(0).extend_from_text("a b", &mut rng);
+        assert_eq!(2, empty.seen());
+        assert_eq!(0.0, empty.estimated_count("a"));
+    }
+
+    #[test]
+    fn test_reservoir_uniform() {
+        // Each position of a stream of 20 words should be
+        // kept in a sample of 5 about a quarter of the time.
+        let words = "a b c d e f g h i j k l m n o p q r s t";
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut kept = [0; 20];
+        for _ in 0..2000 {
+            let reservoir = Reservoir::new(5).extend_from_text(words, &mut rng);
+            for word in reservoir.to_bbow().words() {
+                kept[usize::from(word.as_bytes()[0] - b'a')] += 1;
+            }
+        }
+        for k in kept {
+            assert!(400 < k && k < 600, "{kept:?}");
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Random sampling of words in proportion to their counts.
+
+use rand::Rng;
+
+use crate::Bbow;
+
+/// An alias table for drawing words from a BBOW in
+/// proportion to their counts in constant time per draw,
+/// after Vose, "A Linear Algorithm for Generating Random
+/// Numbers with a Given Distribution" (1991). Made by
+/// [`Bbow::sampler`].
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::Bbow;
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// let bbow = Bbow::new().extend_from_text("heads heads tails");
+/// let sampler = bbow.sampler().unwrap();
+/// let mut rng = StdRng::seed_from_u64(1);
+/// let heads = (0..3000).filter(|_| sampler.sample(&mut rng) == "heads").count();
+/// assert!(1800 < heads && heads < 2200);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sampler<'b> {
+    words: Vec<&'b str>,
+    /// Chance out of `total` of keeping each column's own
+    /// word rather than its alias. These are counts scaled
+    /// by the number of words, so they may not fit a `usize`.
+    keep: Vec<u128>,
+    alias: Vec<usize>,
+    total: u128,
+}
+
+impl Sampler<'_> {
+    /// Draw a word at random.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> &str {
+        let column = rng.random_range(0..self.words.len());
+        if rng.random_range(0..self.total) < self.keep[column] {
+            self.words[column]
+        } else {
+            self.words[self.alias[column]]
+        }
+    }
+}
+
+impl Bbow<'_> {
+    /// Draw a word of this BBOW at random, each word with
+    /// probability proportional to its count. Returns `None`
+    /// for an empty BBOW. Each draw takes time linear in the
+    /// number of distinct words: use [`Bbow::sampler`] for
+    /// repeated draws.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("only only");
+    /// assert_eq!(Some("only"), bbow.sample(&mut rand::rng()));
+    /// assert_eq!(None, Bbow::new().sample(&mut rand::rng()));
+    /// ```
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<&str> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut target = rng.random_range(0..self.count());
+        for (word, &count) in &self.counts {
+            if target < count {
+                return Some(word);
+            }
+            target -= count;
+        }
+        unreachable!("sample beyond total count")
+    }
+
+    /// An alias table for repeated random draws from this
+    /// BBOW, or `None` for an empty BBOW.
+    pub fn sampler(&self) -> Option<Sampler<'_>> {
+        if self.is_empty() {
+            return None;
+        }
+        let n = self.len();
+        let total: u128 = self.counts.values().map(|&count| count as u128).sum();
+        let words: Vec<&str> = self.words().collect();
+        // Each column holds `total` units of probability:
+        // scale the counts by `n` to match.
+        let mut keep: Vec<u128> = self
+            .counts
+            .values()
+            .map(|&count| count as u128 * n as u128)
+            .collect();
+        let mut alias = vec![0; n];
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..n).partition(|&i| keep[i] < total);
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            alias[s] = l;
+            keep[l] -= total - keep[s];
+            if keep[l] < total {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // Whatever is left is full.
+        for i in small.into_iter().chain(large) {
+            keep[i] = total;
+        }
+        Some(Sampler {
+            words,
+            keep,
+            alias,
+            total,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_sampler_table() {
+        // The table must give each word exactly its share.
+        let bbow = Bbow::new().extend_from_text("a a a a a b b c d d d d e");
+        let sampler = bbow.sampler().unwrap();
+        let n = sampler.words.len();
+        let mut shares = vec![0; n];
+        for column in 0..n {
+            shares[column] += sampler.keep[column];
+            shares[sampler.alias[column]] += sampler.total - sampler.keep[column];
+        }
+        let expected: Vec<u128> = bbow
+            .counts
+            .values()
+            .map(|&count| count as u128 * n as u128)
+            .collect();
+        assert_eq!(expected, shares);
+        assert!(Bbow::new().sampler().is_none());
+    }
+
+    #[test]
+    fn test_sampler_huge_counts() {
+        let mut bbow = Bbow::new().extend_from_text("b c");
+        bbow.insert("a", usize::MAX / 2);
+        let sampler = bbow.sampler().unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!((0..100).all(|_| sampler.sample(&mut rng) == "a"));
+        bbow.insert("b", usize::MAX / 2);
+        let sampler = bbow.sampler().unwrap();
+        let a = (0..1000)
+            .filter(|_| sampler.sample(&mut rng) == "a")
+            .count();
+        assert!(400 < a && a < 600, "{a}");
+    }
+
+    #[test]
+    fn test_sample_frequencies() {
+        let bbow = Bbow::new().extend_from_text("a b b b");
+        let sampler = bbow.sampler().unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        let draws = 4000;
+        let linear = (0..draws)
+            .filter(|_| bbow.sample(&mut rng) == Some("a"))
+            .count();
+        let alias = (0..draws)
+            .filter(|_| sampler.sample(&mut rng) == "a")
+            .count();
+        for a in [linear, alias] {
+            assert!(800 < a && a < 1200, "{a}");
+        }
+    }
+}
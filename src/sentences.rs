@@ -0,0 +1,157 @@
+//! Splitting text into sentences.
+
+use crate::Bbow;
+
+/// Common English abbreviations, lowercase and without
+/// their final period, that do not end a sentence.
+const ABBREVIATIONS: &[&str] = &[
+    "approx", "cf", "co", "corp", "dept", "dr", "e.g", "fig", "gen", "gov", "i.e", "inc", "jr",
+    "lt", "ltd", "mr", "mrs", "ms", "mt", "p", "pp", "prof", "rev", "sen", "sgt", "sr", "st",
+    "vol", "vs",
+];
+
+/// Abbreviations that are also words, and so only do not end
+/// a sentence when a number follows, as in "No. 5".
+const NUMBER_ABBREVIATIONS: &[&str] = &["no"];
+
+/// Is the word before `end`, the index of a period in
+/// `text`, an abbreviation such as "Dr." or an initial such
+/// as the "J." of "J. Smith"? "No." is only taken for one if
+/// a number follows it.
+fn is_abbreviation(text: &str, end: usize) -> bool {
+    let start = text[..end]
+        .rfind(|c: char| c.is_whitespace() || matches!(c, '(' | '[' | '"' | '“' | '\''))
+        .map_or(0, |i| {
+            i + text[i..].chars().next().map_or(1, char::len_utf8)
+        });
+    let word = &text[start..end];
+    let mut chars = word.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return c.is_uppercase();
+    }
+    let is = |abbreviations: &[&str]| {
+        abbreviations
+            .iter()
+            .any(|abbreviation| word.eq_ignore_ascii_case(abbreviation))
+    };
+    let number_follows = || {
+        text[end + 1..]
+            .trim_start()
+            .starts_with(|c: char| c.is_ascii_digit())
+    };
+    is(ABBREVIATIONS) || (is(NUMBER_ABBREVIATIONS) && number_follows())
+}
+
+/// The sentences of `text`, trimmed of surrounding
+/// whitespace. A sentence ends at `.`, `!` or `?`, and any
+/// closing quotes or brackets after it, that is followed by
+/// whitespace or the end of the text. A period after a
+/// common abbreviation such as "Mr." or "e.g.", or after an
+/// initial such as the "J." of "J. Smith", does not end a
+/// sentence.
+///
+/// # Examples
+///
+/// ```
+/// let text = "Dr. Watson met J. Smith. \"Who are you?\" he asked. Version 1.2 is out";
+/// assert_eq!(
+///     vec!["Dr. Watson met J. Smith.", "\"Who are you?\"", "he asked.", "Version 1.2 is out"],
+///     bbow::sentences(text),
+/// );
+/// ```
+pub fn sentences(text: &str) -> Vec<&str> {
+    let is_closer = |c: char| matches!(c, '"' | '\'' | ')' | ']' | '”' | '’');
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if !matches!(c, '.' | '!' | '?') {
+            continue;
+        }
+        let abbreviation = c == '.' && is_abbreviation(text, i);
+        while let Some(&(_, c)) = chars.peek() {
+            if !(is_closer(c) || matches!(c, '.' | '!' | '?')) {
+                break;
+            }
+            chars.next();
+        }
+        let end = chars.peek().map(|&(i, _)| i).unwrap_or(text.len());
+        if abbreviation || end < text.len() && !text[end..].starts_with(char::is_whitespace) {
+            continue;
+        }
+        let sentence = text[start..end].trim();
+        if !sentence.is_empty() {
+            result.push(sentence);
+        }
+        start = end;
+    }
+    let rest = text[start..].trim();
+    if !rest.is_empty() {
+        result.push(rest);
+    }
+    result
+}
+
+impl<'a> Bbow<'a> {
+    /// A BBOW for each of the [`sentences`] of `text`, in
+    /// order, for statistics of each sentence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bags = Bbow::per_sentence_bags("The cat sat. The cat, the hat!");
+    /// assert_eq!(2, bags.len());
+    /// assert_eq!(vec![1, 2], bags.iter().map(|b| b.match_count("the")).collect::<Vec<_>>());
+    /// ```
+    pub fn per_sentence_bags(text: &'a str) -> Vec<Bbow<'a>> {
+        sentences(text)
+            .into_iter()
+            .map(|sentence| Bbow::new().extend_from_text(sentence))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentences() {
+        let text = "Hello there. (Is it you?) Version 1.2 is out";
+        assert_eq!(
+            vec!["Hello there.", "(Is it you?)", "Version 1.2 is out"],
+            sentences(text),
+        );
+        assert!(sentences("  ").is_empty());
+        assert_eq!(
+            vec!["Wait...", "what?!", "Oh."],
+            sentences("Wait... what?! Oh.")
+        );
+    }
+
+    #[test]
+    fn test_abbreviations() {
+        let text = "Mrs. Ames, e.g. the one from St. Paul, saw (Prof. X. Y. Zed) today. I left.";
+        assert_eq!(
+            vec![
+                "Mrs. Ames, e.g. the one from St. Paul, saw (Prof. X. Y. Zed) today.",
+                "I left."
+            ],
+            sentences(text),
+        );
+        assert_eq!(vec!["It was a.", "Then b."], sentences("It was a. Then b."));
+        assert_eq!(
+            vec!["I said no.", "We left at dawn."],
+            sentences("I said no. We left at dawn."),
+        );
+        assert_eq!(
+            vec!["Is it over?", "No.", "It is not."],
+            sentences("Is it over? No. It is not."),
+        );
+        assert_eq!(
+            vec!["See No. 5 and no. 6.", "Done."],
+            sentences("See No. 5 and no. 6. Done."),
+        );
+    }
+}
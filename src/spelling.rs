@@ -0,0 +1,218 @@
+//! Spelling suggestions from the words of a BBOW: the words
+//! most often seen are the likeliest corrections of a word
+//! that is close to them.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{lowercase, Bbow};
+
+/// The optimal string alignment distance between `a` and
+/// `b`: the number of characters inserted, deleted or
+/// replaced, or pairs of neighbouring characters swapped,
+/// to change one into the other. Returns `None` if it is
+/// more than `max`.
+fn distance(a: &[char], b: &[char], max: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    // Three rows of the usual dynamic programming table.
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        if current.iter().min().is_some_and(|&d| d > max) {
+            return None;
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    Some(previous[b.len()]).filter(|&d| d <= max)
+}
+
+/// Sort `candidates` most frequent first, then closest
+/// first, then alphabetically, and drop the distances.
+fn ranked(mut candidates: Vec<(&str, usize, usize)>) -> Vec<(&str, usize)> {
+    candidates.sort_unstable_by(|(w1, c1, d1), (w2, c2, d2)| {
+        c2.cmp(c1).then(d1.cmp(d2)).then(w1.cmp(w2))
+    });
+    candidates.into_iter().map(|(w, c, _)| (w, c)).collect()
+}
+
+/// Every string made by deleting up to `n` characters from
+/// `word`, including `word` itself.
+fn deletions(word: &str, n: usize) -> HashSet<String> {
+    let mut all = HashSet::from([word.to_string()]);
+    let mut last = all.clone();
+    for _ in 0..n {
+        let mut next = HashSet::new();
+        for word in &last {
+            for (i, c) in word.char_indices() {
+                let mut shorter = word.clone();
+                shorter.replace_range(i..i + c.len_utf8(), "");
+                if !all.contains(&shorter) {
+                    next.insert(shorter);
+                }
+            }
+        }
+        all.extend(next.iter().cloned());
+        last = next;
+    }
+    all
+}
+
+/// A spelling corrector for the words of a BBOW, made by
+/// [`Bbow::speller`]. Each word is indexed under the strings
+/// left by deleting a few of its characters, as SymSpell
+/// does, so that finding the words near a misspelling only
+/// takes a few lookups, however many words there are.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::Bbow;
+/// let bbow = Bbow::new().extend_from_text("the cat sat on the mat then ran");
+/// let speller = bbow.speller(1);
+/// assert_eq!(vec![("the", 2), ("then", 1)], speller.suggest("thn", 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Speller<'b> {
+    max_distance: usize,
+    words: HashMap<String, Vec<(&'b str, usize)>>,
+}
+
+impl<'b> Speller<'b> {
+    /// The words in the BBOW at most `max_distance` edits
+    /// from `word`, as [`Bbow::suggest`] gives them.
+    /// `max_distance` is limited to the distance the speller
+    /// was made for.
+    pub fn suggest(&self, word: &str, max_distance: usize) -> Vec<(&'b str, usize)> {
+        let max_distance = max_distance.min(self.max_distance);
+        let word = lowercase(word);
+        let chars: Vec<char> = word.chars().collect();
+        let mut seen = HashSet::new();
+        let mut candidates = Vec::new();
+        for deleted in deletions(&word, max_distance) {
+            for &(candidate, count) in self.words.get(&deleted).into_iter().flatten() {
+                if !seen.insert(candidate) {
+                    continue;
+                }
+                let other: Vec<char> = candidate.chars().collect();
+                if let Some(d) = distance(&chars, &other, max_distance) {
+                    candidates.push((candidate, count, d));
+                }
+            }
+        }
+        ranked(candidates)
+    }
+}
+
+impl Bbow<'_> {
+    /// The words in this BBOW at most `max_distance` edits
+    /// from `word` as possible corrections, with their
+    /// counts: most frequent first, then closest first. An
+    /// edit inserts, deletes or replaces a character, or
+    /// swaps two neighbouring ones. The `word` is lowercased
+    /// first, and is itself listed if it is in this BBOW.
+    ///
+    /// This looks at every word in the BBOW; to correct
+    /// many words, use a [`Speller`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat sat on the mat then ran");
+    /// assert_eq!(vec![("the", 2)], bbow.suggest("Teh", 1));
+    /// assert_eq!(vec![("the", 2), ("then", 1)], bbow.suggest("Teh", 2));
+    /// assert_eq!(vec![("cat", 1), ("mat", 1), ("sat", 1)], bbow.suggest("bat", 1));
+    /// assert!(bbow.suggest("dog", 1).is_empty());
+    /// ```
+    pub fn suggest(&self, word: &str, max_distance: usize) -> Vec<(&str, usize)> {
+        let chars: Vec<char> = lowercase(word).chars().collect();
+        let candidates = self
+            .iter()
+            .filter_map(|(candidate, count)| {
+                let other: Vec<char> = candidate.chars().collect();
+                distance(&chars, &other, max_distance).map(|d| (candidate, count, d))
+            })
+            .collect();
+        ranked(candidates)
+    }
+
+    /// A [`Speller`] for the words of this BBOW, for
+    /// suggestions at most `max_distance` edits away. The
+    /// index grows quickly with `max_distance`, which is
+    /// usually 1 or 2.
+    pub fn speller(&self, max_distance: usize) -> Speller<'_> {
+        let mut words: HashMap<String, Vec<(&str, usize)>> = HashMap::new();
+        for (word, count) in self.iter() {
+            for deleted in deletions(word, max_distance) {
+                words.entry(deleted).or_default().push((word, count));
+            }
+        }
+        Speller {
+            max_distance,
+            words,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(a: &str, b: &str) -> Option<usize> {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        distance(&a, &b, 3)
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(Some(0), d("", ""));
+        assert_eq!(Some(3), d("", "abc"));
+        assert_eq!(Some(1), d("teh", "the"));
+        assert_eq!(Some(1), d("über", "uber"));
+        assert_eq!(Some(3), d("kitten", "sitting"));
+        assert_eq!(None, d("a", "abcde"));
+        assert_eq!(None, d("abcd", "wxyz"));
+    }
+
+    #[test]
+    fn test_deletions() {
+        let all = deletions("abc", 2);
+        assert_eq!(7, all.len());
+        assert!(all.contains("b") && all.contains("ac") && all.contains("abc"));
+        assert_eq!(HashSet::from(["ab".to_string()]), deletions("ab", 0));
+        assert_eq!(3, deletions("üü", 5).len());
+    }
+
+    #[test]
+    fn test_speller_matches_suggest() {
+        let bbow = Bbow::new()
+            .extend_from_text("It ain't over untïl it ain't, over. Ovens overt oven lover untie");
+        for max in 0..=2 {
+            let speller = bbow.speller(max);
+            for word in [
+                "over", "ovr", "Untl", "oevr", "it", "t", "", "xyzzy", "lovers",
+            ] {
+                assert_eq!(
+                    bbow.suggest(word, max),
+                    speller.suggest(word, max),
+                    "{word} {max}"
+                );
+            }
+        }
+        assert_eq!(vec![("over", 2)], bbow.speller(1).suggest("ovr", 9));
+    }
+}
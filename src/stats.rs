@@ -0,0 +1,420 @@
+//! Statistics of the word distribution of a BBOW.
+
+use std::collections::BTreeMap;
+use std::ops::RangeBounds;
+
+use crate::Bbow;
+
+/// Summary statistics of a BBOW, from [`Bbow::stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    vocabulary_size: usize,
+    token_count: usize,
+    mean_word_length: f64,
+    max_count: usize,
+    min_count: usize,
+}
+
+impl Stats {
+    /// Number of distinct words.
+    pub fn vocabulary_size(&self) -> usize {
+        self.vocabulary_size
+    }
+
+    /// Total number of word occurrences.
+    pub fn token_count(&self) -> usize {
+        self.token_count
+    }
+
+    /// Ratio of distinct words to word occurrences, or 0 for
+    /// an empty BBOW.
+    pub fn type_token_ratio(&self) -> f64 {
+        if self.token_count == 0 {
+            return 0.0;
+        }
+        self.vocabulary_size as f64 / self.token_count as f64
+    }
+
+    /// Mean length in characters of the word occurrences,
+    /// or 0 for an empty BBOW.
+    pub fn mean_word_length(&self) -> f64 {
+        self.mean_word_length
+    }
+
+    /// Count of the most frequent word, or 0 for an empty
+    /// BBOW.
+    pub fn max_count(&self) -> usize {
+        self.max_count
+    }
+
+    /// Count of the least frequent word, or 0 for an empty
+    /// BBOW.
+    pub fn min_count(&self) -> usize {
+        self.min_count
+    }
+}
+
+impl Bbow<'_> {
+    /// Summary statistics of this BBOW, gathered in one pass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let stats = Bbow::new().extend_from_text("the cat saw the dog").stats();
+    /// assert_eq!(4, stats.vocabulary_size());
+    /// assert_eq!(5, stats.token_count());
+    /// assert_eq!(0.8, stats.type_token_ratio());
+    /// assert_eq!(3.0, stats.mean_word_length());
+    /// assert_eq!((2, 1), (stats.max_count(), stats.min_count()));
+    /// ```
+    pub fn stats(&self) -> Stats {
+        let mut token_count = 0;
+        let mut total_length = 0;
+        let mut max_count = 0;
+        let mut min_count = usize::MAX;
+        for (word, &count) in &self.counts {
+            token_count += count;
+            total_length += word.chars().count() * count;
+            max_count = max_count.max(count);
+            min_count = min_count.min(count);
+        }
+        let mean_word_length = if token_count == 0 {
+            0.0
+        } else {
+            total_length as f64 / token_count as f64
+        };
+        Stats {
+            vocabulary_size: self.len(),
+            token_count,
+            mean_word_length,
+            max_count,
+            min_count: if self.is_empty() { 0 } else { min_count },
+        }
+    }
+
+    /// The words of this BBOW with their ranks and counts,
+    /// most frequent first, as `(rank, word, count)`. Ranks
+    /// start at 1. Words with equal counts are given
+    /// consecutive ranks in alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("b a b c b a");
+    /// assert_eq!(vec![(1, "b", 3), (2, "a", 2), (3, "c", 1)], bbow.rank_frequency());
+    /// ```
+    pub fn rank_frequency(&self) -> Vec<(usize, &str, usize)> {
+        self.top_n(self.len())
+            .into_iter()
+            .enumerate()
+            .map(|(i, (word, count))| (i + 1, word, count))
+            .collect()
+    }
+
+    /// The rank of `word` in [`Bbow::rank_frequency`]: 1 for
+    /// the most frequent word, and so on, with words of equal
+    /// counts in alphabetical order. Returns `None` if `word`
+    /// is not in this BBOW. This takes one pass over the
+    /// words, without sorting them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("b a b c b a");
+    /// assert_eq!(Some(1), bbow.rank("b"));
+    /// assert_eq!(Some(3), bbow.rank("c"));
+    /// assert_eq!(None, bbow.rank("d"));
+    /// ```
+    pub fn rank(&self, word: &str) -> Option<usize> {
+        let (word, &count) = self.counts.get_key_value(word)?;
+        let ahead = self
+            .counts
+            .iter()
+            .filter(|&(w, &c)| c > count || c == count && w < word)
+            .count();
+        Some(ahead + 1)
+    }
+
+    /// The slope of the least-squares line through the
+    /// points `(ln rank, ln count)` of
+    /// [`Bbow::rank_frequency`]. Zipf's law predicts a slope
+    /// of about -1 for natural text. Returns `None` for a
+    /// BBOW of fewer than two words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a a a a a a b b b c c d");
+    /// let slope = bbow.zipf_slope().unwrap();
+    /// assert!(-1.5 < slope && slope < -0.5);
+    /// ```
+    pub fn zipf_slope(&self) -> Option<f64> {
+        let points: Vec<(f64, f64)> = self
+            .rank_frequency()
+            .into_iter()
+            .map(|(rank, _, count)| ((rank as f64).ln(), (count as f64).ln()))
+            .collect();
+        if points.len() < 2 {
+            return None;
+        }
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+        let sxy: f64 = points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum();
+        let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        Some(sxy / sxx)
+    }
+
+    /// Each word of this BBOW with its relative frequency,
+    /// its count divided by [`Bbow::count`], in alphabetical
+    /// order. This is the maximum-likelihood unigram model of
+    /// the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a b b b");
+    /// assert_eq!(vec![("a", 0.25), ("b", 0.75)], bbow.probabilities().collect::<Vec<_>>());
+    /// ```
+    pub fn probabilities(&self) -> impl Iterator<Item = (&str, f64)> {
+        let total = self.count() as f64;
+        self.counts
+            .iter()
+            .map(move |(word, &count)| (word.as_ref(), count as f64 / total))
+    }
+
+    /// The relative frequency of `word` in this BBOW, or 0 if
+    /// it does not occur.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a b b b");
+    /// assert_eq!(0.75, bbow.probability("b"));
+    /// assert_eq!(0.0, bbow.probability("c"));
+    /// ```
+    pub fn probability(&self, word: &str) -> f64 {
+        match self.counts.get(word) {
+            Some(&count) => count as f64 / self.count() as f64,
+            None => 0.0,
+        }
+    }
+
+    /// The add-`k` smoothed probability of `word`:
+    /// `(count + k) / (total + k * vocab_size)`, so that words
+    /// not in this BBOW get a small probability rather than
+    /// 0. With `k` = 1 this is Laplace smoothing. The
+    /// `vocab_size` is the number of distinct words the model
+    /// should allow for, usually at least [`Bbow::len`].
+    /// Returns 0 if the denominator is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a a b");
+    /// assert_eq!(0.5, bbow.smoothed_probability("a", 1.0, 3));
+    /// assert_eq!(1.0 / 6.0, bbow.smoothed_probability("unseen", 1.0, 3));
+    /// ```
+    pub fn smoothed_probability(&self, word: &str, k: f64, vocab_size: usize) -> f64 {
+        let count = self.counts.get(word).copied().unwrap_or(0) as f64;
+        let denominator = self.count() as f64 + k * vocab_size as f64;
+        if denominator == 0.0 {
+            return 0.0;
+        }
+        (count + k) / denominator
+    }
+
+    /// The Shannon entropy in bits of the distribution of
+    /// words in this BBOW: 0 when there is only one word,
+    /// and `log2(n)` when `n` words occur equally often.
+    /// Returns 0 for an empty BBOW.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// assert_eq!(2.0, Bbow::new().extend_from_text("a b c d").entropy());
+    /// assert_eq!(0.0, Bbow::new().extend_from_text("a a a").entropy());
+    /// ```
+    pub fn entropy(&self) -> f64 {
+        self.probabilities()
+            .map(|(_, p)| -p * p.log2())
+            .sum::<f64>()
+            .max(0.0)
+    }
+
+    /// The words that occur exactly once in this BBOW, in
+    /// alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("the cat saw the dog");
+    /// assert_eq!(vec!["cat", "dog", "saw"], bbow.hapax_legomena().collect::<Vec<_>>());
+    /// ```
+    pub fn hapax_legomena(&self) -> impl Iterator<Item = &str> {
+        self.words_with_count(1)
+    }
+
+    /// The words that occur exactly `n` times in this BBOW,
+    /// in alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a b b c c d d d");
+    /// assert_eq!(vec!["b", "c"], bbow.words_with_count(2).collect::<Vec<_>>());
+    /// ```
+    pub fn words_with_count(&self, n: usize) -> impl Iterator<Item = &str> {
+        self.words_in_count_range(n..=n)
+    }
+
+    /// The words whose counts in this BBOW lie in `range`, in
+    /// alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("a b b c c d d d");
+    /// assert_eq!(vec!["a", "b", "c"], bbow.words_in_count_range(1..3).collect::<Vec<_>>());
+    /// assert_eq!(vec!["d"], bbow.words_in_count_range(3..).collect::<Vec<_>>());
+    /// ```
+    pub fn words_in_count_range<R: RangeBounds<usize>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = &str> {
+        self.counts
+            .iter()
+            .filter(move |(_, count)| range.contains(count))
+            .map(|(word, _)| word.as_ref())
+    }
+
+    /// The frequency spectrum of this BBOW: for each count
+    /// `n` that some word has, the number of words that occur
+    /// exactly `n` times. This count-of-counts is the input
+    /// to Good-Turing estimation and to analyses of how the
+    /// vocabulary grows with the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// # use std::collections::BTreeMap;
+    /// let bbow = Bbow::new().extend_from_text("a b b c c d d d");
+    /// assert_eq!(BTreeMap::from([(1, 1), (2, 2), (3, 1)]), bbow.frequency_spectrum());
+    /// ```
+    pub fn frequency_spectrum(&self) -> BTreeMap<usize, usize> {
+        let mut spectrum = BTreeMap::new();
+        for &count in self.counts.values() {
+            *spectrum.entry(count).or_insert(0) += 1;
+        }
+        spectrum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zipf_slope_exact() {
+        // Counts exactly proportional to 1 / rank.
+        let mut bbow = Bbow::new();
+        for (word, count) in [("a", 60), ("b", 30), ("c", 20), ("d", 15), ("e", 12)] {
+            bbow.counts.insert(word.into(), count);
+        }
+        let slope = bbow.zipf_slope().unwrap();
+        assert!((slope + 1.0).abs() < 1e-9, "{slope}");
+        assert_eq!(None, Bbow::new().extend_from_text("only only").zipf_slope());
+        assert!(Bbow::new().rank_frequency().is_empty());
+    }
+    #[test]
+    fn test_rank() {
+        let bbow = Bbow::new().extend_from_text("e d d c c c b b b a a a a");
+        for (rank, word, _) in bbow.rank_frequency() {
+            assert_eq!(Some(rank), bbow.rank(word), "{word}");
+        }
+        assert_eq!(None, bbow.rank("A"));
+        assert_eq!(None, Bbow::new().rank("a"));
+    }
+    #[test]
+    fn test_count_bands() {
+        let bbow = Bbow::new().extend_from_text("a b b c c c");
+        assert_eq!(
+            vec!["a", "b", "c"],
+            bbow.words_in_count_range(..).collect::<Vec<_>>()
+        );
+        assert_eq!(0, bbow.words_in_count_range(4..).count());
+        assert_eq!(0, bbow.words_in_count_range(2..2).count());
+        assert_eq!(0, bbow.words_with_count(0).count());
+        assert_eq!(0, Bbow::new().hapax_legomena().count());
+    }
+    #[test]
+    fn test_frequency_spectrum() {
+        let bbow = Bbow::new().extend_from_text("the cat and the hat and the bat");
+        let spectrum = bbow.frequency_spectrum();
+        assert_eq!(BTreeMap::from([(1, 3), (2, 1), (3, 1)]), spectrum);
+        assert_eq!(bbow.len(), spectrum.values().sum::<usize>());
+        assert_eq!(
+            bbow.count(),
+            spectrum.iter().map(|(n, w)| n * w).sum::<usize>()
+        );
+        assert!(Bbow::new().frequency_spectrum().is_empty());
+    }
+    #[test]
+    fn test_stats() {
+        let stats = Bbow::new().extend_from_text("Ça ça va va").stats();
+        assert_eq!(0.5, stats.type_token_ratio());
+        assert_eq!(2.0, stats.mean_word_length());
+        let stats = Bbow::new().stats();
+        assert_eq!(0, stats.vocabulary_size());
+        assert_eq!(0, stats.token_count());
+        assert_eq!(0.0, stats.type_token_ratio());
+        assert_eq!(0.0, stats.mean_word_length());
+        assert_eq!((0, 0), (stats.max_count(), stats.min_count()));
+    }
+    #[test]
+    fn test_entropy() {
+        let bbow = Bbow::new().extend_from_text("a a b c");
+        assert!((bbow.entropy() - 1.5).abs() < 1e-12);
+        assert_eq!(0.0, Bbow::new().entropy());
+    }
+    #[test]
+    fn test_probabilities() {
+        let bbow = Bbow::new().extend_from_text("x y z z");
+        let total: f64 = bbow.probabilities().map(|(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        assert_eq!(0.5, bbow.probability("z"));
+        let empty = Bbow::new();
+        assert_eq!(0, empty.probabilities().count());
+        assert_eq!(0.0, empty.probability("z"));
+    }
+    #[test]
+    fn test_smoothed_probability() {
+        let bbow = Bbow::new().extend_from_text("a a b");
+        let vocabulary = ["a", "b", "c", "d"];
+        let total: f64 = vocabulary
+            .iter()
+            .map(|w| bbow.smoothed_probability(w, 0.5, vocabulary.len()))
+            .sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        assert_eq!(
+            bbow.probability("a"),
+            bbow.smoothed_probability("a", 0.0, 4)
+        );
+        assert_eq!(0.0, Bbow::new().smoothed_probability("a", 1.0, 0));
+    }
+}
@@ -0,0 +1,293 @@
+//! Reducing words to their stems, so that "connect",
+//! "connected" and "connecting" are counted as one word.
+
+use std::borrow::Cow;
+
+use crate::TokenFilter;
+
+/// A stemmer: a [`TokenFilter`] that strips the inflectional
+/// and derivational suffixes of words. The stems are not
+/// always words themselves: "ponies" becomes "poni".
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::{Bbow, Stemmer};
+/// let stemmer = Stemmer::english();
+/// assert_eq!("connect", stemmer.stem("connections"));
+/// let bbow = Bbow::builder()
+///     .filter(stemmer)
+///     .build()
+///     .extend_from_text("Connect, connected, connecting.");
+/// assert_eq!(3, bbow.match_count("connect"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stemmer(Algorithm);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Porter,
+}
+
+impl Stemmer {
+    /// The Porter stemmer for English (Porter, 1980). Only
+    /// lowercase ASCII words are stemmed; other words are
+    /// left alone.
+    pub fn english() -> Self {
+        Self(Algorithm::Porter)
+    }
+
+    /// The stem of `word`.
+    pub fn stem<'t>(&self, word: &'t str) -> Cow<'t, str> {
+        match self.0 {
+            Algorithm::Porter => porter(word),
+        }
+    }
+}
+
+impl TokenFilter for Stemmer {
+    fn apply<'t>(&self, word: Cow<'t, str>) -> Option<Cow<'t, str>> {
+        Some(match word {
+            Cow::Borrowed(word) => self.stem(word),
+            Cow::Owned(word) => Cow::Owned(self.stem(&word).into_owned()),
+        })
+    }
+}
+
+/// Is `b[i]` a consonant? A "y" is a consonant at the start
+/// of a word or after a vowel.
+fn is_consonant(b: &[u8], i: usize) -> bool {
+    match b[i] {
+        b'a' | b'e' | b'i' | b'o' | b'u' => false,
+        b'y' => i == 0 || !is_consonant(b, i - 1),
+        _ => true,
+    }
+}
+
+/// The measure of `stem`: the number of times a run of
+/// vowels is followed by a run of consonants.
+fn measure(stem: &[u8]) -> usize {
+    let mut m = 0;
+    let mut vowel = false;
+    for i in 0..stem.len() {
+        if !is_consonant(stem, i) {
+            vowel = true;
+        } else if vowel {
+            m += 1;
+            vowel = false;
+        }
+    }
+    m
+}
+
+fn has_vowel(stem: &[u8]) -> bool {
+    (0..stem.len()).any(|i| !is_consonant(stem, i))
+}
+
+/// Does `stem` end with a double consonant, such as "tt"?
+fn ends_double_consonant(stem: &[u8]) -> bool {
+    let n = stem.len();
+    n >= 2 && stem[n - 1] == stem[n - 2] && is_consonant(stem, n - 1)
+}
+
+/// Does `stem` end consonant-vowel-consonant, where the last
+/// consonant is not "w", "x" or "y", as "hop" does?
+fn ends_cvc(stem: &[u8]) -> bool {
+    let n = stem.len();
+    n >= 3
+        && is_consonant(stem, n - 3)
+        && !is_consonant(stem, n - 2)
+        && is_consonant(stem, n - 1)
+        && !matches!(stem[n - 1], b'w' | b'x' | b'y')
+}
+
+/// If `word` ends with one of the `rules`' suffixes, the
+/// first such, replace it as that rule says when its stem
+/// has a measure above `min_measure`. Returns whether a
+/// suffix matched, whether or not it was replaced.
+fn replace_suffix(word: &mut Vec<u8>, rules: &[(&str, &str)], min_measure: usize) -> bool {
+    for (suffix, replacement) in rules {
+        if let Some(stem) = word.strip_suffix(suffix.as_bytes()) {
+            if measure(stem) > min_measure {
+                word.truncate(stem.len());
+                word.extend_from_slice(replacement.as_bytes());
+            }
+            return true;
+        }
+    }
+    false
+}
+
+const STEP_2: &[(&str, &str)] = &[
+    ("ational", "ate"),
+    ("tional", "tion"),
+    ("enci", "ence"),
+    ("anci", "ance"),
+    ("izer", "ize"),
+    ("abli", "able"),
+    ("alli", "al"),
+    ("entli", "ent"),
+    ("eli", "e"),
+    ("ousli", "ous"),
+    ("ization", "ize"),
+    ("ation", "ate"),
+    ("ator", "ate"),
+    ("alism", "al"),
+    ("iveness", "ive"),
+    ("fulness", "ful"),
+    ("ousness", "ous"),
+    ("aliti", "al"),
+    ("iviti", "ive"),
+    ("biliti", "ble"),
+];
+
+const STEP_3: &[(&str, &str)] = &[
+    ("icate", "ic"),
+    ("ative", ""),
+    ("alize", "al"),
+    ("iciti", "ic"),
+    ("ical", "ic"),
+    ("ful", ""),
+    ("ness", ""),
+];
+
+/// The suffixes of step 4, longest first where one ends
+/// another.
+const STEP_4: &[&str] = &[
+    "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment", "ent", "ion", "ou",
+    "ism", "ate", "iti", "ous", "ive", "ize",
+];
+
+fn porter(word: &str) -> Cow<'_, str> {
+    if word.len() <= 2 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return Cow::Borrowed(word);
+    }
+    let mut b = word.as_bytes().to_vec();
+
+    // Step 1a: plurals.
+    if b.ends_with(b"sses") || b.ends_with(b"ies") {
+        b.truncate(b.len() - 2);
+    } else if b.ends_with(b"s") && !b.ends_with(b"ss") {
+        b.pop();
+    }
+
+    // Step 1b: past tenses and participles.
+    if let Some(stem) = b.strip_suffix(b"eed") {
+        if measure(stem) > 0 {
+            b.pop();
+        }
+    } else if let Some(stem) = b
+        .strip_suffix(b"ed")
+        .or_else(|| b.strip_suffix(b"ing"))
+        .filter(|stem| has_vowel(stem))
+    {
+        b.truncate(stem.len());
+        if b.ends_with(b"at") || b.ends_with(b"bl") || b.ends_with(b"iz") {
+            b.push(b'e');
+        } else if ends_double_consonant(&b) && !matches!(b[b.len() - 1], b'l' | b's' | b'z') {
+            b.pop();
+        } else if measure(&b) == 1 && ends_cvc(&b) {
+            b.push(b'e');
+        }
+    }
+
+    // Step 1c: a final "y" after a vowel becomes "i".
+    if b.ends_with(b"y") && has_vowel(&b[..b.len() - 1]) {
+        *b.last_mut().expect("b ends with y") = b'i';
+    }
+
+    // Steps 2 and 3: derivational suffixes.
+    replace_suffix(&mut b, STEP_2, 0);
+    replace_suffix(&mut b, STEP_3, 0);
+
+    // Step 4: remove the suffix of a long enough stem.
+    if let Some(stem) = STEP_4.iter().find_map(|suffix| {
+        let stem = b.strip_suffix(suffix.as_bytes())?;
+        (*suffix != "ion" || stem.ends_with(b"s") || stem.ends_with(b"t")).then_some(stem)
+    }) {
+        if measure(stem) > 1 {
+            b.truncate(stem.len());
+        }
+    }
+
+    // Step 5: a final "e", and a final double "l".
+    if let Some(stem) = b.strip_suffix(b"e") {
+        let m = measure(stem);
+        if m > 1 || m == 1 && !ends_cvc(stem) {
+            b.pop();
+        }
+    }
+    if b.ends_with(b"ll") && measure(&b) > 1 {
+        b.pop();
+    }
+
+    if b == word.as_bytes() {
+        Cow::Borrowed(word)
+    } else {
+        Cow::Owned(String::from_utf8(b).expect("stems of ASCII words are ASCII"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_porter() {
+        // Examples from Porter's paper, and the stems the
+        // whole algorithm gives for them.
+        let cases = [
+            ("caresses", "caress"),
+            ("ponies", "poni"),
+            ("ties", "ti"),
+            ("caress", "caress"),
+            ("cats", "cat"),
+            ("feed", "feed"),
+            ("agreed", "agre"),
+            ("plastered", "plaster"),
+            ("bled", "bled"),
+            ("motoring", "motor"),
+            ("sing", "sing"),
+            ("conflated", "conflat"),
+            ("troubled", "troubl"),
+            ("sized", "size"),
+            ("hopping", "hop"),
+            ("tanned", "tan"),
+            ("falling", "fall"),
+            ("hissing", "hiss"),
+            ("fizzed", "fizz"),
+            ("failing", "fail"),
+            ("filing", "file"),
+            ("happy", "happi"),
+            ("sky", "sky"),
+            ("relational", "relat"),
+            ("conditional", "condit"),
+            ("rational", "ration"),
+            ("generalization", "gener"),
+            ("electrical", "electr"),
+            ("adjustable", "adjust"),
+            ("effective", "effect"),
+            ("adoption", "adopt"),
+            ("controlling", "control"),
+            ("roll", "roll"),
+            ("probate", "probat"),
+            ("rate", "rate"),
+            ("cease", "ceas"),
+        ];
+        for (word, stem) in cases {
+            assert_eq!(stem, porter(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn test_stemmer_filter() {
+        let stemmer = Stemmer::english();
+        assert!(matches!(stemmer.stem("cat"), Cow::Borrowed("cat")));
+        assert_eq!("Running", stemmer.stem("Running"));
+        assert_eq!("café", stemmer.stem("café"));
+        assert_eq!(
+            Some(Cow::Owned::<str>("run".to_string())),
+            stemmer.apply(Cow::Owned("running".to_string()))
+        );
+    }
+}
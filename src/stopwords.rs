@@ -0,0 +1,185 @@
+//! Stop words: very common function words that carry
+//! little content and usually dominate raw counts. A list
+//! is built in for English, and one can be induced from
+//! the counts of a BBOW for other languages.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use crate::{lowercase, Bbow};
+
+/// The stop list, sorted so that it can be binary-searched.
+#[rustfmt::skip]
+const ENGLISH: &[&str] = &[
+    "a", "about", "above", "after", "again", "against", "all", "am", "an", "and", "any", "are",
+    "as", "at", "be", "because", "been", "before", "being", "below", "between", "both", "but",
+    "by", "can", "could", "did", "do", "does", "doing", "down", "during", "each", "few", "for",
+    "from", "further", "had", "has", "have", "having", "he", "her", "here", "hers", "herself",
+    "him", "himself", "his", "how", "i", "if", "in", "into", "is", "it", "its", "itself", "just",
+    "me", "more", "most", "my", "myself", "no", "nor", "not", "now", "of", "off", "on", "once",
+    "only", "or", "other", "our", "ours", "ourselves", "out", "over", "own", "same", "she",
+    "should", "so", "some", "such", "than", "that", "the", "their", "theirs", "them",
+    "themselves", "then", "there", "these", "they", "this", "those", "through", "to", "too",
+    "under", "until", "up", "very", "was", "we", "were", "what", "when", "where", "which",
+    "while", "who", "whom", "why", "will", "with", "would", "you", "your", "yours", "yourself",
+    "yourselves",
+];
+
+/// Is `word` (already lowercased) an English stop word?
+pub(crate) fn is_english_stopword(word: &str) -> bool {
+    ENGLISH.binary_search(&word).is_ok()
+}
+
+/// A set of stop words for a [`BbowBuilder`](crate::BbowBuilder)
+/// to leave out of BBOWs. Stop words are matched against
+/// the lowercased form of each word. Copies of a set share
+/// its words.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::StopWords;
+/// let stop = StopWords::english().with_words(["said"]);
+/// assert!(stop.contains("the"));
+/// assert!(stop.contains("said"));
+/// assert!(!stop.contains("whale"));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct StopWords(Arc<BTreeSet<String>>);
+
+impl StopWords {
+    /// Make an empty set of stop words.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The built-in list of English stop words.
+    pub fn english() -> Self {
+        Self::new().with_words(ENGLISH)
+    }
+
+    /// This set with `words` added to it. The words are
+    /// lowercased.
+    pub fn with_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let set = Arc::make_mut(&mut self.0);
+        set.extend(words.into_iter().map(|w| w.as_ref().to_lowercase()));
+        self
+    }
+
+    /// Is `word` in this set, ignoring case?
+    pub fn contains(&self, word: &str) -> bool {
+        self.0.contains(lowercase(word).as_ref())
+    }
+
+    /// Number of stop words in this set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Is this set empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The stop words of this set, in alphabetical order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl Bbow<'_> {
+    /// A stop list induced from this BBOW: its most
+    /// frequent words, taken in the order of
+    /// [`Bbow::top_n`] until together they cover
+    /// `top_fraction` of all the word occurrences. This
+    /// stands in for a bundled list in languages that have
+    /// none. The fraction is clamped to between 0 and 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bbow = Bbow::new().extend_from_text("la la la de de de el mar sol luna");
+    /// let stop = bbow.induce_stopwords(0.5);
+    /// assert_eq!(2, stop.len());
+    /// assert!(stop.contains("la") && stop.contains("de"));
+    /// ```
+    pub fn induce_stopwords(&self, top_fraction: f64) -> StopWords {
+        let target = top_fraction.clamp(0.0, 1.0) * self.count() as f64;
+        let mut covered = 0;
+        let words = self
+            .top_k_iter()
+            .take_while(|&(_, count)| {
+                let taking = (covered as f64) < target;
+                covered += count;
+                taking
+            })
+            .map(|(word, _)| word);
+        StopWords::new().with_words(words)
+    }
+
+    /// Remove the words of [`Bbow::induce_stopwords`] from
+    /// this BBOW, and return them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let mut bbow = Bbow::new().extend_from_text("la la la de de de el mar sol luna");
+    /// bbow.remove_induced_stopwords(0.5);
+    /// assert_eq!(vec!["el", "luna", "mar", "sol"], bbow.words().collect::<Vec<_>>());
+    /// ```
+    pub fn remove_induced_stopwords(&mut self, top_fraction: f64) -> StopWords {
+        let stop = self.induce_stopwords(top_fraction);
+        self.retain(|word, _| !stop.contains(word));
+        stop
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_list_sorted() {
+        assert!(ENGLISH.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_is_english_stopword() {
+        assert!(is_english_stopword("the"));
+        assert!(is_english_stopword("yourselves"));
+        assert!(!is_english_stopword("banana"));
+        assert!(!is_english_stopword("The"));
+    }
+
+    #[test]
+    fn test_stop_words_set() {
+        assert_eq!(ENGLISH.len(), StopWords::english().len());
+        let stop = StopWords::new().with_words(["Foo", "bar"]);
+        let copy = stop.clone().with_words(["baz"]);
+        assert_eq!((2, 3), (stop.len(), copy.len()));
+        assert!(stop.contains("FOO"));
+        assert!(!stop.contains("baz"));
+        assert!(StopWords::new().is_empty());
+    }
+
+    #[test]
+    fn test_induce_stopwords() {
+        let bbow = Bbow::new().extend_from_text("a a a a b b b c c d");
+        let sizes: Vec<usize> = [-1.0, 0.0, 0.1, 0.4, 0.41, 0.7, 0.9, 1.0, 2.0]
+            .into_iter()
+            .map(|f| bbow.induce_stopwords(f).len())
+            .collect();
+        assert_eq!(vec![0, 0, 1, 1, 2, 2, 3, 4, 4], sizes);
+        assert!(Bbow::new().induce_stopwords(0.5).is_empty());
+        let mut bbow = bbow;
+        let stop = bbow.remove_induced_stopwords(0.5);
+        assert!(stop.contains("a") && stop.contains("b"));
+        assert_eq!(3, bbow.count());
+    }
+}
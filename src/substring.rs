@@ -0,0 +1,214 @@
+//! Counting where a string occurs in text, even inside
+//! longer words, for when matching whole words is not
+//! enough.
+
+use std::sync::OnceLock;
+
+use crate::lowercase;
+
+/// A suffix array over lowercased texts, counting the
+/// occurrences of any string in them, including those inside
+/// longer words and across word boundaries. Occurrences may
+/// overlap, but never run from one text into the next.
+///
+/// The index keeps the texts, and two numbers for each of
+/// their characters, so it takes many times the space of the
+/// texts themselves. Adding a text only stores it: the
+/// suffixes of all the texts are sorted by the first count
+/// after texts were added, by prefix doubling, in
+/// O(n log² n) time for n characters in all, and often less
+/// as the sort stops once all suffixes are told apart. A
+/// count then takes O(m log n) time for a pattern of m
+/// characters.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::{Bbow, SubstringIndex};
+/// let text = "Moreover, it's over. Overall, fine.";
+/// let index = SubstringIndex::new().extend_from_text(text);
+/// assert_eq!(3, index.substring_count("over"));
+/// assert_eq!(1, Bbow::new().extend_from_text(text).match_count("over"));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SubstringIndex {
+    text: String,
+    /// The end of each text in `text`.
+    ends: Vec<usize>,
+    /// The start of each suffix and the end of the text it
+    /// is in, in order of the suffixes, once sorted.
+    suffixes: OnceLock<Vec<(usize, usize)>>,
+}
+
+/// The suffixes of the texts of `text` ending at `ends`, as
+/// `(start, end)` pairs in order, sorted by prefix doubling:
+/// each round orders the suffixes by their first `2k`
+/// characters, given their ranks by the first `k`. Each
+/// text is followed by a separator that sorts before any
+/// character and differs from those of the other texts, so
+/// that no suffix compares past the end of its text.
+fn sort_suffixes(text: &str, ends: &[usize]) -> Vec<(usize, usize)> {
+    // The suffix starting at each position, or `None` for a
+    // separator, and the rank of each position by its first
+    // character.
+    let mut places = Vec::new();
+    let mut rank = Vec::new();
+    let mut start = 0;
+    for (i, &end) in ends.iter().enumerate() {
+        for (j, c) in text[start..end].char_indices() {
+            places.push(Some((start + j, end)));
+            rank.push(ends.len() + c as usize);
+        }
+        places.push(None);
+        rank.push(i);
+        start = end;
+    }
+    let n = rank.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut next = vec![0; n];
+    let mut k = 1;
+    loop {
+        let key = |i: usize| (rank[i], rank.get(i + k).map_or(0, |r| r + 1));
+        order.sort_unstable_by_key(|&i| key(i));
+        for j in 1..n {
+            next[order[j]] = next[order[j - 1]] + usize::from(key(order[j - 1]) != key(order[j]));
+        }
+        std::mem::swap(&mut rank, &mut next);
+        if n == 0 || rank[order[n - 1]] == n - 1 {
+            break;
+        }
+        k *= 2;
+    }
+    order.into_iter().filter_map(|i| places[i]).collect()
+}
+
+impl SubstringIndex {
+    /// Make a new empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the `target` text to this index. The suffixes of
+    /// all the texts are sorted again by the next count.
+    ///
+    /// This is a "builder method", like
+    /// [`Bbow::extend_from_text`](crate::Bbow::extend_from_text).
+    pub fn extend_from_text(mut self, target: &str) -> Self {
+        self.text.push_str(&lowercase(target));
+        self.ends.push(self.text.len());
+        self.suffixes = OnceLock::new();
+        self
+    }
+
+    /// The suffixes of the texts, sorted if they are not yet.
+    fn suffixes(&self) -> &[(usize, usize)] {
+        self.suffixes
+            .get_or_init(|| sort_suffixes(&self.text, &self.ends))
+    }
+
+    /// The number of times `pattern` occurs in the texts of
+    /// this index, ignoring case. The empty string occurs
+    /// nowhere.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::SubstringIndex;
+    /// let index = SubstringIndex::new().extend_from_text("Banana").extend_from_text("nab");
+    /// assert_eq!(2, index.substring_count("ANA"));
+    /// assert_eq!(3, index.substring_count("na"));
+    /// assert_eq!(0, index.substring_count("aab"));
+    /// ```
+    pub fn substring_count(&self, pattern: &str) -> usize {
+        let pattern = lowercase(pattern);
+        if pattern.is_empty() {
+            return 0;
+        }
+        let suffix = |&(start, end): &(usize, usize)| &self.text[start..end];
+        let suffixes = self.suffixes();
+        let first = suffixes.partition_point(|s| suffix(s) < pattern.as_ref());
+        suffixes[first..].partition_point(|s| suffix(s).starts_with(pattern.as_ref()))
+    }
+
+    /// Is nothing indexed?
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Overlapping occurrences of `pattern` in `text`, the
+    /// slow way.
+    fn naive_count(text: &str, pattern: &str) -> usize {
+        (0..text.len())
+            .filter(|&i| text.is_char_boundary(i) && text[i..].starts_with(pattern))
+            .count()
+    }
+
+    #[test]
+    fn test_substring_count() {
+        let texts = [
+            "It ain't over untïl it ain't, over.",
+            "Moreover, ÜBER über aaaa",
+            "",
+            "a",
+            "overover",
+        ];
+        let index = texts.iter().fold(SubstringIndex::new(), |index, text| {
+            index.extend_from_text(text)
+        });
+        let patterns = [
+            "over", "OVER", "a", "aa", "ain't", "ü", "über", "t", "r.", "er ", "ra", "zz", "ï",
+        ];
+        for pattern in patterns {
+            let lower = pattern.to_lowercase();
+            let expected: usize = texts
+                .iter()
+                .map(|text| naive_count(&text.to_lowercase(), &lower))
+                .sum();
+            assert_eq!(expected, index.substring_count(pattern), "{pattern}");
+        }
+        // Not across the end of one text and the start of the
+        // next.
+        assert_eq!(0, index.substring_count("aaaaa"));
+        assert_eq!(0, index.substring_count(""));
+        assert!(!index.is_empty());
+        assert!(SubstringIndex::new().is_empty());
+        assert_eq!(0, SubstringIndex::new().substring_count("a"));
+    }
+
+    #[test]
+    fn test_sort_suffixes() {
+        let texts = ["banana", "", "ananas", "abracadabra", "ünïcödé aa"];
+        let mut text = String::new();
+        let mut ends = Vec::new();
+        for t in texts {
+            text.push_str(t);
+            ends.push(text.len());
+        }
+        let sorted = sort_suffixes(&text, &ends);
+        let suffixes: Vec<&str> = sorted.iter().map(|&(s, e)| &text[s..e]).collect();
+        let mut expected = suffixes.clone();
+        expected.sort_unstable();
+        assert_eq!(expected, suffixes);
+        assert_eq!(text.chars().count(), sorted.len());
+        assert!(sort_suffixes("", &[]).is_empty());
+        assert!(sort_suffixes("", &[0, 0]).is_empty());
+    }
+
+    #[test]
+    fn test_repetitive_text() {
+        let text = "ab".repeat(50_000);
+        let index = SubstringIndex::new()
+            .extend_from_text(&text)
+            .extend_from_text("b");
+        assert_eq!(50_000, index.substring_count("ab"));
+        assert_eq!(49_999, index.substring_count("abab"));
+        assert_eq!(50_001, index.substring_count("b"));
+        let index = index.extend_from_text("ab");
+        assert_eq!(50_001, index.substring_count("ab"));
+    }
+}
@@ -0,0 +1,71 @@
+//! Extractive summarization.
+//!
+//! Each sentence of a document is scored by how frequent
+//! its content words are in the document as a whole; the
+//! best-scoring sentences make up the summary.
+
+use crate::stopwords::is_english_stopword;
+use crate::{sentences, Bbow};
+
+/// Choose the `n_sentences` most representative sentences
+/// of `text` and return them in their original order.
+///
+/// A sentence's score is the mean count, in the bag of the
+/// whole `text`, of its words that are not English stop
+/// words. Sentences with no such words score zero; ties go
+/// to the earlier sentence.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::summarize;
+/// let text = "Cats purr. Dogs bark at cats. Cats nap in the sun.";
+/// assert_eq!(
+///     vec!["Cats purr.", "Dogs bark at cats."],
+///     summarize(text, 2),
+/// );
+/// ```
+pub fn summarize(text: &str, n_sentences: usize) -> Vec<&str> {
+    let bbow = Bbow::new().extend_from_text(text);
+    let sentences = sentences(text);
+    let mut scored: Vec<(usize, f64)> = sentences
+        .iter()
+        .enumerate()
+        .map(|(i, sentence)| {
+            let content = Bbow::new().extend_from_text(sentence);
+            let (mut total, mut n) = (0, 0);
+            for w in content.words().filter(|w| !is_english_stopword(w)) {
+                let count = content.match_count(w);
+                total += count * bbow.match_count(w);
+                n += count;
+            }
+            let score = if n == 0 { 0.0 } else { total as f64 / n as f64 };
+            (i, score)
+        })
+        .collect();
+    scored.sort_by(|(i1, s1), (i2, s2)| s2.total_cmp(s1).then(i1.cmp(i2)));
+    scored.truncate(n_sentences);
+    scored.sort_by_key(|&(i, _)| i);
+    scored.into_iter().map(|(i, _)| sentences[i]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_original_order() {
+        let text = "The grey whale swam. Whales sing. A whale song is a whale call. It rained.";
+        assert_eq!(
+            vec!["The grey whale swam.", "A whale song is a whale call."],
+            summarize(text, 2),
+        );
+    }
+
+    #[test]
+    fn test_summarize_short_text() {
+        assert_eq!(vec!["Only one."], summarize("Only one.", 3));
+        assert!(summarize("", 3).is_empty());
+        assert!(summarize("Some text.", 0).is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+//! Emoji and other pictographic symbols as countable
+//! tokens.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Is `token` a single emoji or other pictographic symbol,
+/// together with any modifiers and joined symbols in the
+/// same grapheme cluster?
+pub(crate) fn is_symbol(token: &str) -> bool {
+    let mut graphemes = token.graphemes(true);
+    let (Some(grapheme), None) = (graphemes.next(), graphemes.next()) else {
+        return false;
+    };
+    grapheme.chars().next().is_some_and(|c| {
+        matches!(c,
+            '\u{a9}' | '\u{ae}' | '\u{2122}'
+            | '\u{2190}'..='\u{21ff}'   // Arrows
+            | '\u{2300}'..='\u{23ff}'   // Miscellaneous technical
+            | '\u{25a0}'..='\u{27bf}'   // Shapes, symbols and dingbats
+            | '\u{2b00}'..='\u{2bff}'   // Miscellaneous symbols and arrows
+            | '\u{1f000}'..='\u{1faff}' // Emoji blocks
+        )
+    })
+}
+
+/// Split `token` so that each symbol in it is a piece of
+/// its own, leaving the text between symbols in one piece.
+pub(crate) fn split_symbols(token: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    for (i, grapheme) in token.grapheme_indices(true) {
+        if is_symbol(grapheme) {
+            if start < i {
+                pieces.push(&token[start..i]);
+            }
+            pieces.push(grapheme);
+            start = i + grapheme.len();
+        }
+    }
+    if start < token.len() {
+        pieces.push(&token[start..]);
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_symbol() {
+        for symbol in ["😂", "❤️", "👍🏽", "👨‍👩‍👧", "🇳🇿", "→", "©"]
+        {
+            assert!(is_symbol(symbol), "{symbol}");
+        }
+        for other in ["", "a", ",", "😂😂", "é", "1"] {
+            assert!(!is_symbol(other), "{other}");
+        }
+    }
+
+    #[test]
+    fn test_split_symbols() {
+        assert_eq!(vec!["🎉", "party", "🎉"], split_symbols("🎉party🎉"));
+        assert_eq!(vec!["😂", "😂"], split_symbols("😂😂"));
+        assert_eq!(vec!["(", "👍🏽", ")."], split_symbols("(👍🏽)."));
+        assert_eq!(vec!["plain"], split_symbols("plain"));
+    }
+}
@@ -0,0 +1,406 @@
+//! A BBOW kept in a radix tree, which stores the common
+//! beginnings of words once.
+
+use std::cmp::Ordering;
+use std::mem::size_of;
+
+use crate::{words_of, Bbow};
+
+/// No node: the end of a list of children.
+const NONE: u32 = u32::MAX;
+
+/// A node of the tree. Its label, the piece of a word on
+/// the edge to it, is `labels[start..end]` of the tree. Its
+/// children are a list through `next`, in order of their
+/// labels. No two children of a node have labels beginning
+/// with the same character.
+#[derive(Debug, Clone)]
+struct Node {
+    /// The count of the word spelled by the path to this
+    /// node, or 0 if no word ends here.
+    count: usize,
+    start: u32,
+    end: u32,
+    first_child: u32,
+    next: u32,
+}
+
+/// The length in bytes of the longest common beginning of
+/// `a` and `b` that ends between characters.
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|&((_, x), y)| x != y)
+        .map_or(a.len().min(b.len()), |((i, _), _)| i)
+}
+
+/// `n` as an index of a [`TrieBbow`].
+fn index(n: usize) -> u32 {
+    u32::try_from(n)
+        .ok()
+        .filter(|&n| n != NONE)
+        .expect("a TrieBbow holds fewer than 2^32 - 1 nodes and bytes of labels")
+}
+
+/// A BBOW whose words are kept in a radix tree: each edge of
+/// the tree is labelled with a piece of a word, and words
+/// with a common beginning share the edges that spell it.
+/// The nodes are kept in one array and the labels in one
+/// string, so a word costs little more than the letters it
+/// does not share. For a large vocabulary of a natural
+/// language, with its many words that differ only in their
+/// endings, this holds the words in much less space than an
+/// ordinary BBOW, and finding all the words that begin a
+/// certain way takes time in proportion to the length of the
+/// beginning, plus the number of words found. Words are
+/// split as by [`Bbow::new`], and are owned by the tree.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::TrieBbow;
+/// let trie = TrieBbow::new().extend_from_text("connect connected connection cat");
+/// assert_eq!(1, trie.match_count("connected"));
+/// let words: Vec<String> = trie.with_prefix("conn").map(|(word, _)| word).collect();
+/// assert_eq!(vec!["connect", "connected", "connection"], words);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TrieBbow {
+    /// The nodes, with the root first.
+    nodes: Vec<Node>,
+    labels: String,
+    len: usize,
+    count: usize,
+}
+
+impl Default for TrieBbow {
+    fn default() -> Self {
+        let root = Node {
+            count: 0,
+            start: 0,
+            end: 0,
+            first_child: NONE,
+            next: NONE,
+        };
+        Self {
+            nodes: vec![root],
+            labels: String::new(),
+            len: 0,
+            count: 0,
+        }
+    }
+}
+
+impl PartialEq for TrieBbow {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for TrieBbow {}
+
+impl TrieBbow {
+    /// Make a new empty BBOW.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn node(&self, i: u32) -> &Node {
+        &self.nodes[i as usize]
+    }
+
+    fn label(&self, i: u32) -> &str {
+        let node = self.node(i);
+        &self.labels[node.start as usize..node.end as usize]
+    }
+
+    /// The child of node `parent` whose label begins with the
+    /// first character of `key`, if there is one, and the
+    /// child before where it is or would be.
+    fn child(&self, parent: u32, key: &str) -> (Option<u32>, u32) {
+        let first = key.chars().next();
+        let (mut before, mut i) = (NONE, self.node(parent).first_child);
+        while i != NONE {
+            match self.label(i).chars().next().cmp(&first) {
+                Ordering::Less => (before, i) = (i, self.node(i).next),
+                Ordering::Equal => return (Some(i), before),
+                Ordering::Greater => break,
+            }
+        }
+        (None, before)
+    }
+
+    /// The link to the child of `parent` after `before`, or
+    /// to its first child if `before` is [`NONE`].
+    fn slot(&mut self, parent: u32, before: u32) -> &mut u32 {
+        if before == NONE {
+            &mut self.nodes[parent as usize].first_child
+        } else {
+            &mut self.nodes[before as usize].next
+        }
+    }
+
+    fn push(&mut self, node: Node) -> u32 {
+        let i = index(self.nodes.len());
+        self.nodes.push(node);
+        i
+    }
+
+    /// Add `n` occurrences of `word`.
+    fn add(&mut self, word: &str, n: usize) {
+        self.count += n;
+        let (mut parent, mut key) = (0, word);
+        loop {
+            if key.is_empty() {
+                let node = &mut self.nodes[parent as usize];
+                if node.count == 0 {
+                    self.len += 1;
+                }
+                node.count += n;
+                return;
+            }
+            let (found, before) = self.child(parent, key);
+            let Some(i) = found else {
+                let start = index(self.labels.len());
+                self.labels.push_str(key);
+                let leaf = self.push(Node {
+                    count: n,
+                    start,
+                    end: index(self.labels.len()),
+                    first_child: NONE,
+                    next: NONE,
+                });
+                let slot = self.slot(parent, before);
+                let next = std::mem::replace(slot, leaf);
+                self.nodes[leaf as usize].next = next;
+                self.len += 1;
+                return;
+            };
+            let common = common_prefix_len(self.label(i), key);
+            let mut i = i;
+            if common < self.label(i).len() {
+                // Split the edge where the key leaves it: a new
+                // node takes the shared part of the label, with
+                // the old node below it.
+                let old = self.node(i).clone();
+                let split = old.start + index(common);
+                let middle = self.push(Node {
+                    count: 0,
+                    start: old.start,
+                    end: split,
+                    first_child: i,
+                    next: old.next,
+                });
+                let node = &mut self.nodes[i as usize];
+                node.start = split;
+                node.next = NONE;
+                *self.slot(parent, before) = middle;
+                i = middle;
+            }
+            (parent, key) = (i, &key[common..]);
+        }
+    }
+
+    /// The node reached by following `key` from the root,
+    /// and the word spelled by the path to it, which has
+    /// `key` as a prefix.
+    fn find(&self, key: &str) -> Option<(u32, String)> {
+        let (mut i, mut rest) = (0, key);
+        while !rest.is_empty() {
+            let child = self.child(i, rest).0?;
+            let label = self.label(child);
+            if let Some(left) = rest.strip_prefix(label) {
+                rest = left;
+            } else if let Some(tail) = label.strip_prefix(rest) {
+                return Some((child, format!("{key}{tail}")));
+            } else {
+                return None;
+            }
+            i = child;
+        }
+        Some((i, key.to_string()))
+    }
+
+    /// Parse the `target` text and add the sequence of
+    /// valid words contained in it to this BBOW, as
+    /// [`Bbow::extend_from_text`] does.
+    ///
+    /// This is a "builder method", like
+    /// [`Bbow::extend_from_text`].
+    pub fn extend_from_text(mut self, target: &str) -> Self {
+        for word in words_of(target) {
+            self.add(&word, 1);
+        }
+        self
+    }
+
+    /// Report the number of occurrences of the given
+    /// `keyword` in this BBOW. The keyword is looked up as
+    /// given, so the words of a BBOW built with other options
+    /// are found after [`Bbow::to_trie`].
+    pub fn match_count(&self, keyword: &str) -> usize {
+        match self.find(keyword) {
+            Some((i, word)) if word.len() == keyword.len() => self.node(i).count,
+            _ => 0,
+        }
+    }
+
+    /// The words in this BBOW with their counts, in
+    /// alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (String, usize)> + '_ {
+        self.with_prefix("")
+    }
+
+    /// The words in this BBOW that begin with `prefix`, with
+    /// their counts, in alphabetical order.
+    pub fn with_prefix(&self, prefix: &str) -> impl Iterator<Item = (String, usize)> + '_ {
+        let mut start = self.find(prefix);
+        // Nodes still to visit, each with the word spelled by
+        // the path to its parent.
+        let mut stack: Vec<(u32, String)> = Vec::new();
+        std::iter::from_fn(move || loop {
+            let (i, word) = match start.take() {
+                Some(first) => first,
+                None => {
+                    let (i, mut word) = stack.pop()?;
+                    let next = self.node(i).next;
+                    if next != NONE {
+                        stack.push((next, word.clone()));
+                    }
+                    word.push_str(self.label(i));
+                    (i, word)
+                }
+            };
+            let node = self.node(i);
+            if node.first_child != NONE {
+                stack.push((node.first_child, word.clone()));
+            }
+            if node.count > 0 {
+                return Some((word, node.count));
+            }
+        })
+    }
+
+    /// Count the overall number of words contained in this
+    /// BBOW: multiple occurrences are considered separate.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Count the number of unique words contained in this
+    /// BBOW.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is this BBOW empty?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Give back as much unused room as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.nodes.shrink_to_fit();
+        self.labels.shrink_to_fit();
+    }
+
+    /// An estimate of the bytes used by this BBOW, for
+    /// comparison with [`crate::MemoryUsage::total_bytes`].
+    pub fn approx_memory_bytes(&self) -> usize {
+        size_of::<Self>() + self.nodes.capacity() * size_of::<Node>() + self.labels.capacity()
+    }
+
+    /// An ordinary BBOW of the same words and counts.
+    pub fn to_bbow(&self) -> Bbow<'static> {
+        let mut bbow = Bbow::new();
+        for (word, count) in self.iter() {
+            bbow.insert(word, count);
+        }
+        bbow
+    }
+}
+
+impl Bbow<'_> {
+    /// A copy of the words and counts of this BBOW in a
+    /// [`TrieBbow`].
+    pub fn to_trie(&self) -> TrieBbow {
+        let mut trie = TrieBbow::new();
+        for (word, count) in self.iter() {
+            trie.add(word, count);
+        }
+        trie.shrink_to_fit();
+        trie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_prefix_len() {
+        assert_eq!(3, common_prefix_len("abcd", "abce"));
+        assert_eq!(2, common_prefix_len("ab", "abc"));
+        // "é" and "è" share their first byte, but not a
+        // character.
+        assert_eq!(1, common_prefix_len("aé", "aè"));
+        assert_eq!(0, common_prefix_len("", "a"));
+    }
+
+    #[test]
+    fn test_trie() {
+        let text = "romane romanus romulus rubens ruber rubicon rubicundus rom r é è";
+        let trie = TrieBbow::new()
+            .extend_from_text(text)
+            .extend_from_text("ruber Ruber");
+        let bbow = Bbow::new()
+            .extend_from_text(text)
+            .extend_from_text("ruber Ruber");
+        assert_eq!(bbow.to_trie(), trie);
+        assert_eq!(bbow, trie.to_bbow());
+        assert_eq!((bbow.len(), bbow.count()), (trie.len(), trie.count()));
+        let words: Vec<(String, usize)> = trie.iter().collect();
+        let expected: Vec<(String, usize)> = bbow.iter().map(|(w, c)| (w.to_string(), c)).collect();
+        assert_eq!(expected, words);
+        for (word, count) in bbow.iter() {
+            assert_eq!(count, trie.match_count(word), "{word}");
+        }
+        for missing in ["", "ro", "roman", "rubicons", "e", "ruber!"] {
+            assert_eq!(0, trie.match_count(missing), "{missing}");
+        }
+        let numbers = Bbow::builder()
+            .numbers(true)
+            .build()
+            .extend_from_text("2024 2024");
+        assert_eq!(2, numbers.to_trie().match_count("2024"));
+    }
+
+    #[test]
+    fn test_trie_memory() {
+        // Every stem with every ending, as in the inflections
+        // of a natural language.
+        let mut bbow = Bbow::new();
+        for stem in ["walk", "talk", "jump", "count", "paint", "listen"] {
+            for ending in ["", "s", "ed", "ing", "er", "ers"] {
+                bbow.insert(format!("{stem}{ending}"), 1);
+            }
+        }
+        let trie = bbow.to_trie();
+        assert!(trie.approx_memory_bytes() < bbow.approx_memory_bytes().total_bytes());
+        assert_eq!(bbow, trie.to_bbow());
+    }
+
+    #[test]
+    fn test_with_prefix() {
+        let trie = TrieBbow::new().extend_from_text("romane romanus romulus rom rubens");
+        let words = |prefix| trie.with_prefix(prefix).map(|(w, _)| w).collect::<Vec<_>>();
+        assert_eq!(vec!["rom", "romane", "romanus", "romulus"], words("ro"));
+        assert_eq!(vec!["romane", "romanus"], words("roma"));
+        assert_eq!(vec!["romanus"], words("romanus"));
+        assert!(words("romanuss").is_empty());
+        assert!(words("x").is_empty());
+        assert_eq!(5, words("").len());
+        assert!(TrieBbow::new().iter().next().is_none());
+        assert!(TrieBbow::new().is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+//! Vocabularies: fixed lists of words, each with an index,
+//! for turning BBOWs into vectors of counts.
+
+use std::collections::HashMap;
+
+use crate::{Bbow, Corpus};
+
+/// A list of distinct words, where each word has an index
+/// given by its place in the list. Words not in the list
+/// have no index.
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::Vocabulary;
+/// let vocabulary = Vocabulary::from_words(["fish", "red", "fish", "blue"]);
+/// assert_eq!(3, vocabulary.len());
+/// assert_eq!(Some(1), vocabulary.index_of("red"));
+/// assert_eq!(Some("blue"), vocabulary.word(2));
+/// assert_eq!(None, vocabulary.index_of("green"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<String>,
+    indices: HashMap<String, usize>,
+}
+
+impl Vocabulary {
+    /// Make a new empty vocabulary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make a vocabulary of `words`, in the order given.
+    /// Repeated words keep their first index.
+    pub fn from_words<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut vocabulary = Self::new();
+        for word in words {
+            vocabulary.insert(word.as_ref());
+        }
+        vocabulary
+    }
+
+    /// Add `word` at the end of this vocabulary if it is not
+    /// already in it, returning its index.
+    pub fn insert(&mut self, word: &str) -> usize {
+        if let Some(&index) = self.indices.get(word) {
+            return index;
+        }
+        let index = self.words.len();
+        self.words.push(word.to_string());
+        self.indices.insert(word.to_string(), index);
+        index
+    }
+
+    /// The index of `word`, if it is in this vocabulary.
+    pub fn index_of(&self, word: &str) -> Option<usize> {
+        self.indices.get(word).copied()
+    }
+
+    /// The word with index `index`, if any.
+    pub fn word(&self, index: usize) -> Option<&str> {
+        self.words.get(index).map(String::as_str)
+    }
+
+    /// The words of this vocabulary in order of index.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
+    }
+
+    /// Number of words in this vocabulary.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Is this vocabulary empty?
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// The counts in `bbow` of the words of this vocabulary,
+    /// in order of index.
+    pub fn counts(&self, bbow: &Bbow) -> Vec<usize> {
+        self.words().map(|word| bbow.match_count(word)).collect()
+    }
+}
+
+impl Bbow<'_> {
+    /// A [`Vocabulary`] of the words of this BBOW, in
+    /// alphabetical order.
+    pub fn vocabulary(&self) -> Vocabulary {
+        Vocabulary::from_words(self.words())
+    }
+}
+
+impl Corpus<'_> {
+    /// A [`Vocabulary`] of the words found in at least
+    /// `min_documents` documents of this corpus, in
+    /// alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Corpus;
+    /// let mut corpus = Corpus::new();
+    /// corpus.add_text("a", "red fish");
+    /// corpus.add_text("b", "blue fish");
+    /// assert_eq!(vec!["fish"], corpus.vocabulary(2).words().collect::<Vec<_>>());
+    /// assert_eq!(3, corpus.vocabulary(1).len());
+    /// ```
+    pub fn vocabulary(&self, min_documents: usize) -> Vocabulary {
+        let index = self.inverted_index();
+        Vocabulary::from_words(
+            index
+                .words()
+                .filter(|w| index.document_frequency(w) >= min_documents),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vocabulary() {
+        let mut vocabulary = Vocabulary::new();
+        assert!(vocabulary.is_empty());
+        assert_eq!(0, vocabulary.insert("one"));
+        assert_eq!(1, vocabulary.insert("two"));
+        assert_eq!(0, vocabulary.insert("one"));
+        assert_eq!(2, vocabulary.len());
+        assert_eq!(None, vocabulary.word(2));
+        let bbow = Bbow::new().extend_from_text("two two three");
+        assert_eq!(vec![0, 2], vocabulary.counts(&bbow));
+        assert_eq!(
+            vec!["three", "two"],
+            bbow.vocabulary().words().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! WebAssembly bindings, so that a BBOW can be used from
+//! JavaScript in a browser. Build with for example
+//! `wasm-pack build --features wasm`.
+//!
+//! The bag is exported to JavaScript as `Bbow`:
+//!
+//! ```js
+//! const bbow = new Bbow();
+//! bbow.extendFromText("It ain't over untïl it ain't, over.");
+//! bbow.matchCount("over"); // 2
+//! bbow.toJson(); // '{"it":2,"over":2,"untïl":1}'
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::Bbow;
+
+/// A BBOW for JavaScript. Text passed in is copied, as
+/// JavaScript strings cannot be borrowed.
+#[wasm_bindgen(js_name = Bbow)]
+#[derive(Debug, Default, Clone)]
+pub struct WasmBbow(Bbow<'static>);
+
+#[wasm_bindgen(js_class = Bbow)]
+impl WasmBbow {
+    /// Make a new empty BBOW.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the words of `target` to this BBOW, as
+    /// [`Bbow::extend_from_text`] does.
+    #[wasm_bindgen(js_name = extendFromText)]
+    pub fn extend_from_text(&mut self, target: &str) {
+        let words = Bbow::new().extend_from_text(target).into_owned();
+        self.0.merge(&words);
+    }
+
+    /// The number of occurrences of `keyword`, as
+    /// [`Bbow::match_count`] gives it.
+    #[wasm_bindgen(js_name = matchCount)]
+    pub fn match_count(&self, keyword: &str) -> usize {
+        self.0.match_count(keyword)
+    }
+
+    /// The overall number of words, as [`Bbow::count`]
+    /// gives it.
+    pub fn count(&self) -> usize {
+        self.0.count()
+    }
+
+    /// The number of distinct words, as [`Bbow::len`] gives
+    /// it.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.0.len()
+    }
+
+    /// This BBOW as a JSON object from words to counts, as
+    /// [`Bbow::to_json_string`] gives it.
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        self.0.to_json_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wasm_bbow() {
+        let mut bbow = WasmBbow::new();
+        bbow.extend_from_text(&String::from("It ain't over untïl it ain't, over."));
+        bbow.extend_from_text("Over!");
+        assert_eq!(3, bbow.match_count("over"));
+        assert_eq!(6, bbow.count());
+        assert_eq!(3, bbow.length());
+        assert_eq!(r#"{"it":2,"over":3,"untïl":1}"#, bbow.to_json());
+    }
+}
@@ -0,0 +1,66 @@
+//! BBOWs of overlapping stretches of a long text.
+
+use std::borrow::Cow;
+
+use crate::{words_of, Bbow};
+
+impl<'a> Bbow<'a> {
+    /// A BBOW for each window of `window_size` consecutive
+    /// words of `text`, with a new window starting every
+    /// `step` words. Comparing neighbouring windows shows how
+    /// the vocabulary, and so the topic, drifts through a
+    /// long document. Only whole windows are made, except
+    /// that a nonempty text shorter than a window gives one
+    /// BBOW of all its words. A `window_size` or `step` of 0
+    /// is taken as 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::Bbow;
+    /// let bags = Bbow::windowed_bags("cats purr, cats nap, dogs bark, dogs run", 4, 2);
+    /// assert_eq!(3, bags.len());
+    /// let cats: Vec<usize> = bags.iter().map(|b| b.match_count("cats")).collect();
+    /// assert_eq!(vec![2, 1, 0], cats);
+    /// ```
+    pub fn windowed_bags(text: &'a str, window_size: usize, step: usize) -> Vec<Bbow<'a>> {
+        let words: Vec<Cow<'a, str>> = words_of(text).collect();
+        let (window_size, step) = (window_size.max(1), step.max(1));
+        if words.is_empty() {
+            return Vec::new();
+        }
+        let last_start = words.len().saturating_sub(window_size);
+        (0..=last_start)
+            .step_by(step)
+            .map(|start| {
+                let mut bbow = Bbow::new();
+                for word in &words[start..words.len().min(start + window_size)] {
+                    bbow.increment(word.clone());
+                }
+                bbow
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(bags: &[Bbow]) -> Vec<usize> {
+        bags.iter().map(Bbow::count).collect()
+    }
+
+    #[test]
+    fn test_windowed_bags() {
+        let text = "a b c d e f g";
+        assert_eq!(vec![3, 3, 3], counts(&Bbow::windowed_bags(text, 3, 2)));
+        assert_eq!(vec![7], counts(&Bbow::windowed_bags(text, 7, 1)));
+        assert_eq!(vec![7], counts(&Bbow::windowed_bags(text, 10, 3)));
+        assert_eq!(vec![1; 7], counts(&Bbow::windowed_bags(text, 0, 0)));
+        assert_eq!(vec![2, 2], counts(&Bbow::windowed_bags(text, 2, 4)));
+        assert!(Bbow::windowed_bags("", 2, 1).is_empty());
+        let bags = Bbow::windowed_bags(text, 3, 2);
+        assert_eq!(vec!["e", "f", "g"], bags[2].words().collect::<Vec<_>>());
+    }
+}
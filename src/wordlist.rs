@@ -0,0 +1,142 @@
+//! Lists of words kept in files, one word per line.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::StopWords;
+
+/// The words of a word list file: one word per line, with
+/// anything after a `#` a comment, and surrounding
+/// whitespace and blank lines ignored.
+fn parse(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|word| !word.is_empty())
+}
+
+/// A list of words, such as a list of terms to keep or to
+/// look for, in the order given. Lists maintained outside
+/// the program can be read with [`WordList::from_path`].
+///
+/// # Examples
+///
+/// ```
+/// # use bbow::WordList;
+/// let list = WordList::from_words(["whale", "sea", "whale"]);
+/// assert_eq!(vec!["whale", "sea"], list.words().collect::<Vec<_>>());
+/// assert!(list.contains("sea"));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WordList {
+    words: Vec<String>,
+    set: HashSet<String>,
+}
+
+impl WordList {
+    /// Make a list of `words`, leaving out repeats.
+    pub fn from_words<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut list = Self::default();
+        for word in words {
+            let word = word.as_ref();
+            if list.set.insert(word.to_string()) {
+                list.words.push(word.to_string());
+            }
+        }
+        list
+    }
+
+    /// Read a list from the file at `path`, with one word
+    /// per line. Blank lines, surrounding whitespace, and
+    /// anything after a `#` are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::WordList;
+    /// let path = std::env::temp_dir().join(format!("bbow-doc-{}.words", std::process::id()));
+    /// std::fs::write(&path, "# Terms of art\nharpoon\n  try-works  # rendering\n\n").unwrap();
+    /// let list = WordList::from_path(&path).unwrap();
+    /// assert_eq!(vec!["harpoon", "try-works"], list.words().collect::<Vec<_>>());
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Ok(Self::from_words(parse(&text)))
+    }
+
+    /// The words of this list, in order.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
+    }
+
+    /// Is `word` in this list?
+    pub fn contains(&self, word: &str) -> bool {
+        self.set.contains(word)
+    }
+
+    /// Number of words in this list.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Is this list empty?
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+impl StopWords {
+    /// Read a set of stop words from the file at `path`, in
+    /// the format of [`WordList::from_path`]. The words are
+    /// lowercased.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bbow::StopWords;
+    /// let path = std::env::temp_dir().join(format!("bbow-doc-{}.stop", std::process::id()));
+    /// std::fs::write(&path, "# Dutch\nde\nhet\nEen\n").unwrap();
+    /// let stop = StopWords::from_path(&path).unwrap();
+    /// assert_eq!(3, stop.len());
+    /// assert!(stop.contains("een"));
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Ok(Self::new().with_words(parse(&text)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "one\r\n#two\n three # 3\n\n\t\n#\nfour#";
+        assert_eq!(
+            vec!["one", "three", "four"],
+            parse(text).collect::<Vec<_>>()
+        );
+        assert_eq!(0, parse("").count());
+    }
+
+    #[test]
+    fn test_from_path() {
+        let path = std::env::temp_dir().join(format!("bbow-wordlist-{}", std::process::id()));
+        fs::write(&path, "b\na\nb # again\n").unwrap();
+        let list = WordList::from_path(&path).unwrap();
+        assert_eq!(vec!["b", "a"], list.words().collect::<Vec<_>>());
+        assert_eq!(2, StopWords::from_path(&path).unwrap().len());
+        fs::remove_file(&path).unwrap();
+        assert!(WordList::from_path(&path).is_err());
+        assert!(StopWords::from_path(&path).is_err());
+        assert!(WordList::default().is_empty());
+    }
+}
@@ -0,0 +1,23 @@
+//! Golden-file regression test of the tokenization rules:
+//! runs `bbow verify` over the inputs in `tests/golden`.
+//!
+//! After an intended change to the rules, run `cargo run --
+//! verify --bless` and review the changes to the golden
+//! files.
+
+use std::process::Command;
+
+#[test]
+fn test_golden_files() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden");
+    let output = Command::new(env!("CARGO_BIN_EXE_bbow"))
+        .args(["verify", dir])
+        .output()
+        .expect("bbow should run");
+    assert!(
+        output.status.success(),
+        "golden files differ:\n{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr),
+    );
+}